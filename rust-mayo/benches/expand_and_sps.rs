@@ -0,0 +1,50 @@
+// rust-mayo/benches/expand_and_sps.rs
+//
+// Times expand_matrices and compute_sps separately for each parameter set,
+// since together they dominate verification.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rust_mayo::crypto::{compute_sps, expand_matrices};
+use rust_mayo::params::{Mayo1, Mayo2, Mayo3, Mayo5, MayoParams};
+
+fn bench_expand_matrices<P: MayoParams>(c: &mut Criterion, name: &str) {
+    let seed = vec![0x42u8; P::PK_SEED_BYTES];
+    c.bench_function(&format!("expand_matrices/{}", name), |b| {
+        b.iter(|| expand_matrices::<P>(black_box(&seed)).unwrap())
+    });
+}
+
+fn bench_compute_sps<P: MayoParams>(c: &mut Criterion, name: &str) {
+    let seed = vec![0x42u8; P::PK_SEED_BYTES];
+    let (p1, p2, p3) = expand_matrices::<P>(&seed).unwrap().into_parts();
+    // Deterministic k x n signature matrix with every nibble value represented
+    let s_matrix: Vec<Vec<u8>> = (0..P::K_PARAM)
+        .map(|i| (0..P::N_PARAM).map(|j| ((i * 7 + j * 3) % 16) as u8).collect())
+        .collect();
+
+    c.bench_function(&format!("compute_sps/{}", name), |b| {
+        b.iter(|| compute_sps::<P>(black_box(&s_matrix), black_box(&p1), black_box(&p2), black_box(&p3)))
+    });
+}
+
+fn expand_benches(c: &mut Criterion) {
+    bench_expand_matrices::<Mayo1>(c, "MAYO-1");
+    bench_expand_matrices::<Mayo2>(c, "MAYO-2");
+    bench_expand_matrices::<Mayo3>(c, "MAYO-3");
+    bench_expand_matrices::<Mayo5>(c, "MAYO-5");
+}
+
+fn sps_benches(c: &mut Criterion) {
+    bench_compute_sps::<Mayo1>(c, "MAYO-1");
+    bench_compute_sps::<Mayo2>(c, "MAYO-2");
+    bench_compute_sps::<Mayo3>(c, "MAYO-3");
+    bench_compute_sps::<Mayo5>(c, "MAYO-5");
+}
+
+criterion_group! {
+    name = benches;
+    // compute_sps is slow for the larger parameter sets; keep the sample count small
+    config = Criterion::default().sample_size(10);
+    targets = expand_benches, sps_benches
+}
+criterion_main!(benches);
@@ -0,0 +1,27 @@
+// rust-mayo/benches/f16_mul.rs
+//
+// Times a million F16 multiplies, the innermost operation of every expansion,
+// evaluation and elimination step.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rust_mayo::f16::F16;
+
+const MULTIPLIES: usize = 1_000_000;
+
+fn bench_f16_mul(c: &mut Criterion) {
+    // Operands cycle through every pair of field elements
+    let operands: Vec<(F16, F16)> = (0..256).map(|i| (F16::new((i >> 4) as u8), F16::new((i & 0xF) as u8))).collect();
+    c.bench_function("f16_mul/1M", |b| {
+        b.iter(|| {
+            let mut acc = F16::new(0);
+            for i in 0..MULTIPLIES {
+                let (x, y) = operands[i % operands.len()];
+                acc = acc + black_box(x) * black_box(y);
+            }
+            acc
+        })
+    });
+}
+
+criterion_group!(benches, bench_f16_mul);
+criterion_main!(benches);
@@ -0,0 +1,111 @@
+// rust-mayo/benches/matrix_layout.rs
+//
+// Compares `&m * &n` on the row-major Matrix against an experimental column-major
+// storage, for square matrices of each P1 dimension (v = n - o). A row-major kernel
+// over raw slices with the same loop shape is timed as well, so that the layout is
+// measured separately from the per-element get/set overhead of `&m * &n`. The
+// column-major variant lives only here; Matrix stays row-major unless it wins.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use rust_mayo::f16::F16;
+use rust_mayo::matrix::Matrix;
+
+// P1 side length for MAYO-1, MAYO-2, MAYO-3 and MAYO-5
+const P1_DIMENSIONS: [(&str, usize); 4] = [("MAYO-1", 78), ("MAYO-2", 60), ("MAYO-3", 89), ("MAYO-5", 121)];
+
+// Fixed so that every run times the same matrices
+const SEED: u64 = 0x4d41_594f;
+
+// Column-major copy of a Matrix: element (r, c) is at c * rows + r
+struct ColMajor {
+    elements: Vec<F16>,
+    rows: usize,
+    cols: usize,
+}
+
+impl ColMajor {
+    fn from_matrix(m: &Matrix) -> Self {
+        let mut elements = Vec::with_capacity(m.rows() * m.cols());
+        for c in 0..m.cols() {
+            for r in 0..m.rows() {
+                elements.push(m.get(r, c).unwrap());
+            }
+        }
+        ColMajor { elements, rows: m.rows(), cols: m.cols() }
+    }
+
+    // C = A * B with every operand column-major: column c of C accumulates the columns
+    // of A scaled by B[k, c], so both inner loops walk contiguous memory
+    fn mul(&self, rhs: &ColMajor) -> ColMajor {
+        assert_eq!(self.cols, rhs.rows);
+        let mut elements = vec![F16::new(0); self.rows * rhs.cols];
+        for c in 0..rhs.cols {
+            let out = &mut elements[c * self.rows..(c + 1) * self.rows];
+            for k in 0..self.cols {
+                let scale = rhs.elements[c * rhs.rows + k];
+                let a_col = &self.elements[k * self.rows..(k + 1) * self.rows];
+                for (o, &a) in out.iter_mut().zip(a_col) {
+                    *o = *o + a * scale;
+                }
+            }
+        }
+        ColMajor { elements, rows: self.rows, cols: rhs.cols }
+    }
+}
+
+// Row-major copy with the mirrored loop: row r of C accumulates the rows of B scaled
+// by A[r, k]
+struct RowMajor {
+    elements: Vec<F16>,
+    rows: usize,
+    cols: usize,
+}
+
+impl RowMajor {
+    fn from_matrix(m: &Matrix) -> Self {
+        let elements = (0..m.rows()).flat_map(|r| (0..m.cols()).map(move |c| m.get(r, c).unwrap())).collect();
+        RowMajor { elements, rows: m.rows(), cols: m.cols() }
+    }
+
+    fn mul(&self, rhs: &RowMajor) -> RowMajor {
+        assert_eq!(self.cols, rhs.rows);
+        let mut elements = vec![F16::new(0); self.rows * rhs.cols];
+        for r in 0..self.rows {
+            let out = &mut elements[r * rhs.cols..(r + 1) * rhs.cols];
+            for k in 0..self.cols {
+                let scale = self.elements[r * self.cols + k];
+                let b_row = &rhs.elements[k * rhs.cols..(k + 1) * rhs.cols];
+                for (o, &b) in out.iter_mut().zip(b_row) {
+                    *o = *o + scale * b;
+                }
+            }
+        }
+        RowMajor { elements, rows: self.rows, cols: rhs.cols }
+    }
+}
+
+fn bench_layouts(c: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(SEED);
+    for (name, v) in P1_DIMENSIONS {
+        let a = Matrix::random(v, v, &mut rng);
+        let b = Matrix::random(v, v, &mut rng);
+        c.bench_function(&format!("matmul_row_major/{}", name), |bench| {
+            bench.iter(|| (black_box(&a) * black_box(&b)).unwrap())
+        });
+
+        let (a_row, b_row) = (RowMajor::from_matrix(&a), RowMajor::from_matrix(&b));
+        c.bench_function(&format!("matmul_row_major_slices/{}", name), |bench| {
+            bench.iter(|| black_box(&a_row).mul(black_box(&b_row)))
+        });
+
+        let (a_col, b_col) = (ColMajor::from_matrix(&a), ColMajor::from_matrix(&b));
+        c.bench_function(&format!("matmul_col_major/{}", name), |bench| {
+            bench.iter(|| black_box(&a_col).mul(black_box(&b_col)))
+        });
+    }
+}
+
+criterion_group!(benches, bench_layouts);
+criterion_main!(benches);
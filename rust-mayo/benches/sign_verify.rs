@@ -0,0 +1,55 @@
+// rust-mayo/benches/sign_verify.rs
+//
+// Times signing and verification for MAYO-1 on fixed inputs, so runs are comparable.
+// The key comes from BENCH_SK_SEED through generate_keypair_from_seed, and signing makes
+// SIGN_ATTEMPTS attempts under a fixed salt through sign_chunk, so a signer that never finds
+// a solution still finishes each iteration. One attempt already takes seconds, so the
+// signing figure is effectively a per-attempt cost.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rust_mayo::crypto::{generate_keypair_from_seed, sign_chunk, verify_generic, SigningState};
+use rust_mayo::params::{Mayo1, MayoParams};
+
+// sk_seed of the benchmark key: CSK_BYTES bytes of 0x5a
+const BENCH_SK_SEED: u8 = 0x5a;
+// Salt of every benchmark signature: SALT_BYTES bytes of 0xa5
+const BENCH_SALT: u8 = 0xa5;
+const SIGN_ATTEMPTS: usize = 1;
+const MESSAGE: &[u8] = b"rust-mayo benchmark message";
+
+fn bench_keypair() -> (Vec<u8>, Vec<u8>) {
+    generate_keypair_from_seed::<Mayo1>(&[BENCH_SK_SEED; Mayo1::CSK_BYTES]).unwrap()
+}
+
+// Attempt counter 0 followed by the fixed salt, in SigningState::to_bytes layout
+fn bench_state() -> SigningState {
+    let mut bytes = 0u32.to_le_bytes().to_vec();
+    bytes.extend_from_slice(&[BENCH_SALT; Mayo1::SALT_BYTES]);
+    SigningState::from_bytes::<Mayo1>(&bytes).unwrap()
+}
+
+fn sign_benches(c: &mut Criterion) {
+    let (sk, _pk) = bench_keypair();
+    c.bench_function(&format!("sign_chunk/MAYO-1/{}_attempt", SIGN_ATTEMPTS), |b| {
+        b.iter(|| {
+            let mut state = bench_state();
+            sign_chunk::<Mayo1>(black_box(&sk), black_box(MESSAGE), &mut state, SIGN_ATTEMPTS)
+        })
+    });
+}
+
+fn verify_benches(c: &mut Criterion) {
+    let (_sk, pk) = bench_keypair();
+    // Verification does the same work whether or not the signature is valid
+    let signature = vec![0x11u8; Mayo1::SIG_BYTES];
+    c.bench_function("verify/MAYO-1", |b| {
+        b.iter(|| verify_generic::<Mayo1>(black_box(&pk), black_box(MESSAGE), black_box(&signature)))
+    });
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = sign_benches, verify_benches
+}
+criterion_main!(benches);
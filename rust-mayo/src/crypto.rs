@@ -1,12 +1,22 @@
 // rust-mayo/src/crypto.rs
 
-use sha3::Shake256;
 use rand::RngCore;
 use rand::rngs::OsRng;
 use std::error::Error;
 use std::fmt;
-use crate::params::{MayoParams, Mayo1};
+use std::marker::PhantomData;
+use crate::params::MayoParams;
+#[cfg(feature = "mayo1")]
+use crate::params::Mayo1;
 use crate::f16::F16;
+use crate::prf::{Prf, Shake256Prf};
+use crate::vector::Vector;
+use crate::matrix::Matrix;
+use crate::poly::mul_x_mod_tail;
+use crate::mayo_operations::{mvec_matmul, MVector};
+#[cfg(feature = "debug-dump")]
+use crate::mayo_operations::count_exact_matches_packed;
+use zeroize::Zeroizing;
 
 #[derive(Debug)]
 pub enum CryptoError {
@@ -14,7 +24,16 @@ pub enum CryptoError {
     SigningError,
     VerificationError,
     InvalidKeyLength,
+    InvalidEncodingLength,
+    // Text or byte encoding that failed to parse; carries the parser's error
+    InvalidEncoding(Box<dyn Error + Send + Sync>),
+    // Reading or writing keys, signatures or test vectors failed
+    Io(std::io::Error),
     MatrixError,
+    // A secret and public key that do not belong together
+    KeyPairMismatch,
+    // The message exceeds the parameter set's MAX_MESSAGE_LEN, so it cannot be signed
+    MessageTooLong { len: usize, max: usize },
 }
 
 impl fmt::Display for CryptoError {
@@ -24,38 +43,53 @@ impl fmt::Display for CryptoError {
             CryptoError::SigningError => write!(f, "Signing failed"),
             CryptoError::VerificationError => write!(f, "Verification failed"),
             CryptoError::InvalidKeyLength => write!(f, "Invalid key length"),
+            CryptoError::InvalidEncodingLength => write!(f, "Invalid encoded length"),
+            CryptoError::InvalidEncoding(_) => write!(f, "Invalid encoding"),
+            CryptoError::Io(_) => write!(f, "I/O error"),
             CryptoError::MatrixError => write!(f, "Matrix operation failed"),
+            CryptoError::KeyPairMismatch => write!(f, "Public key does not match secret key"),
+            CryptoError::MessageTooLong { len, max } => {
+                write!(f, "Message is {} bytes, the limit is {}", len, max)
+            }
+        }
+    }
+}
+
+// The wrapped error is exposed through source() rather than repeated in Display
+impl Error for CryptoError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            CryptoError::InvalidEncoding(e) => Some(e.as_ref()),
+            CryptoError::Io(e) => Some(e),
+            _ => None,
         }
     }
 }
 
-impl Error for CryptoError {}
+impl From<std::io::Error> for CryptoError {
+    fn from(e: std::io::Error) -> Self {
+        CryptoError::Io(e)
+    }
+}
 
 // Make functions public for testing
+// Used for message hashing and secret key expansion; matrix expansion goes through P::Prf
 pub fn shake256_digest(input: &[u8], output_len: usize) -> Vec<u8> {
-    use sha3::digest::{Update, ExtendableOutput, XofReader};
-    let mut shake = Shake256::default();
-    shake.update(input);
-    let mut output = vec![0u8; output_len];
-    let mut reader = shake.finalize_xof();
-    reader.read(&mut output);
-    output
+    Shake256Prf::expand(input, output_len)
 }
 
-// Decode nibble-packed bytes to elements (exact match to C implementation)
-fn decode_elements(input: &[u8], output: &mut [u8]) {
-    let output_len = output.len();
-    for i in 0..(output_len / 2) {
-        if i < input.len() {
-            output[2 * i] = input[i] & 0x0F;
-            if (2 * i + 1) < output_len {
-                output[2 * i + 1] = (input[i] >> 4) & 0x0F;
-            }
-        }
+// Decode nibble-packed bytes to elements, low nibble first; input must be exactly ceil(count/2) bytes
+pub fn decode_elements_checked(input: &[u8], count: usize) -> Result<Vec<F16>, CryptoError> {
+    if input.len() != count.div_ceil(2) {
+        return Err(CryptoError::InvalidEncodingLength);
     }
-    if output_len % 2 == 1 && (output_len / 2) < input.len() {
-        output[output_len - 1] = input[output_len / 2] & 0x0F;
+    let mut elements = Vec::with_capacity(count);
+    for i in 0..count {
+        let byte = input[i / 2];
+        let nibble = if i % 2 == 0 { byte & 0x0F } else { byte >> 4 };
+        elements.push(F16::new(nibble));
     }
+    Ok(elements)
 }
 
 // Encode elements to nibble-packed bytes (exact match to C implementation)
@@ -69,8 +103,196 @@ fn encode_elements(input: &[u8], output: &mut [u8]) {
     }
 }
 
-// Expand P1, P2, P3 matrices from public key seed (matching C implementation structure)
-pub fn expand_matrices<P: MayoParams>(seed_pk: &[u8]) -> Result<(Vec<F16>, Vec<F16>, Vec<F16>), CryptoError> {
+// Position of entry (i, j), i <= j, in the row-major upper triangle of an n x n matrix
+pub fn symmetric_index(i: usize, j: usize, n: usize) -> usize {
+    let (i, j) = if i <= j { (i, j) } else { (j, i) };
+    i * (2 * n - i + 1) / 2 + (j - i)
+}
+
+// Coefficient (i, j) of equation eq from a flat sequence of upper-triangular n x n matrices.
+// The lower triangle mirrors the upper one, so p_coeff(p, eq, i, j, n) == p_coeff(p, eq, j, i, n).
+// Coefficients past the end of `p` read as zero, matching the evaluators' bounds checks.
+pub fn p_coeff(p: &[F16], eq: usize, i: usize, j: usize, n: usize) -> F16 {
+    let coeffs_per_eq = (n * (n + 1)) / 2;
+    p.get(eq * coeffs_per_eq + symmetric_index(i, j, n))
+        .copied()
+        .unwrap_or(F16::new(0))
+}
+
+// P1, P2 and P3 for all M_PARAM equations in the flat layout expand_matrices produces, read
+// through (eq, i, j) accessors instead of hand-computed offsets. P1 (v x v) and P3 (o x o)
+// store only the upper triangle, so their accessors accept i and j in either order.
+pub struct PMatrices<P: MayoParams> {
+    p1: Vec<F16>,
+    p2: Vec<F16>,
+    p3: Vec<F16>,
+    // whipping_coefficients::<P>(), built once per key for the whipped evaluation
+    whipping: Vec<Vec<F16>>,
+    // fn() -> P keeps PMatrices Send + Sync for any P, so it can be shared across threads
+    _params: PhantomData<fn() -> P>,
+}
+
+impl<P: MayoParams> PMatrices<P> {
+    // Each sequence must hold exactly M_PARAM matrices of the size P prescribes
+    pub fn new(p1: Vec<F16>, p2: Vec<F16>, p3: Vec<F16>) -> Result<Self, CryptoError> {
+        if !expansion_lengths_match::<P>(&p1, &p2, &p3) {
+            return Err(CryptoError::MatrixError);
+        }
+        Ok(PMatrices { p1, p2, p3, whipping: whipping_coefficients::<P>(), _params: PhantomData })
+    }
+
+    pub fn from_seed(pk_seed: &[u8]) -> Result<Self, CryptoError> {
+        expand_matrices::<P>(pk_seed)
+    }
+
+    // P1 and P2 expanded from the key's pk_seed, P3 decoded from the key itself. Rejects keys
+    // validate_public_key rejects.
+    pub fn from_compact_pk(cpk: &[u8]) -> Result<Self, CryptoError> {
+        let mut matrices = Self::zeroed();
+        matrices.fill_from_compact_pk(cpk, &mut Vec::new())?;
+        Ok(matrices)
+    }
+
+    // from_compact_pk into existing buffers, reusing `stream` for the PRF output
+    fn fill_from_compact_pk(&mut self, cpk: &[u8], stream: &mut Vec<u8>) -> Result<(), CryptoError> {
+        validate_public_key::<P>(cpk)?;
+        let (pk_seed, p3_bytes) = cpk.split_at(P::PK_SEED_BYTES);
+        expand_matrices_with_stream::<P>(pk_seed, stream, &mut self.p1, &mut self.p2, &mut self.p3)?;
+        for (i, x) in self.p3.iter_mut().enumerate() {
+            *x = F16::new(p3_bytes[i / 2] >> (4 * (i % 2)));
+        }
+        Ok(())
+    }
+
+    // All-zero buffers of the full expansion lengths, filled later by expand_matrices_into
+    fn zeroed() -> Self {
+        PMatrices {
+            p1: vec![F16::new(0); P::M_PARAM * P::P1_ELEMS_PER_MATRIX],
+            p2: vec![F16::new(0); P::M_PARAM * P::P2_ELEMS_PER_MATRIX],
+            p3: vec![F16::new(0); P::M_PARAM * P::P3_ELEMS_PER_MATRIX],
+            whipping: whipping_coefficients::<P>(),
+            _params: PhantomData,
+        }
+    }
+
+    // P1[eq][i][j] for vinegar indices i, j < v
+    pub fn p1(&self, eq: usize, i: usize, j: usize) -> F16 {
+        let v = P::N_PARAM - P::O_PARAM;
+        assert!(eq < P::M_PARAM && i < v && j < v, "P1 index ({}, {}, {}) out of range", eq, i, j);
+        self.p1[eq * P::P1_ELEMS_PER_MATRIX + symmetric_index(i, j, v)]
+    }
+
+    // P2[eq][i][j] for vinegar index i < v and oil index j < o
+    pub fn p2(&self, eq: usize, i: usize, j: usize) -> F16 {
+        let v = P::N_PARAM - P::O_PARAM;
+        let o = P::O_PARAM;
+        assert!(eq < P::M_PARAM && i < v && j < o, "P2 index ({}, {}, {}) out of range", eq, i, j);
+        self.p2[eq * P::P2_ELEMS_PER_MATRIX + i * o + j]
+    }
+
+    // P3[eq][i][j] for oil indices i, j < o
+    pub fn p3(&self, eq: usize, i: usize, j: usize) -> F16 {
+        let o = P::O_PARAM;
+        assert!(eq < P::M_PARAM && i < o && j < o, "P3 index ({}, {}, {}) out of range", eq, i, j);
+        self.p3[eq * P::P3_ELEMS_PER_MATRIX + symmetric_index(i, j, o)]
+    }
+
+    // The reduction table of whipping_coefficients, computed when the matrices were built
+    pub fn whipping(&self) -> &[Vec<F16>] {
+        &self.whipping
+    }
+
+    // The underlying flat sequences (P1, P2, P3)
+    pub fn as_slices(&self) -> (&[F16], &[F16], &[F16]) {
+        (&self.p1, &self.p2, &self.p3)
+    }
+
+    // The flat sequences by value, for callers that keep or modify them
+    pub fn into_parts(self) -> (Vec<F16>, Vec<F16>, Vec<F16>) {
+        (self.p1, self.p2, self.p3)
+    }
+}
+
+// Whether P1, P2, P3 each hold exactly M_PARAM matrices of the size P prescribes
+fn expansion_lengths_match<P: MayoParams>(p1: &[F16], p2: &[F16], p3: &[F16]) -> bool {
+    p1.len() == P::M_PARAM * P::P1_ELEMS_PER_MATRIX
+        && p2.len() == P::M_PARAM * P::P2_ELEMS_PER_MATRIX
+        && p3.len() == P::M_PARAM * P::P3_ELEMS_PER_MATRIX
+}
+
+// Overwrites the n x n `full` with the matrix of equation eq, rebuilt from its upper triangle
+fn fill_symmetric_matrix(full: &mut Matrix, p: &[F16], eq: usize) {
+    let n = full.rows();
+    for i in 0..n {
+        for j in 0..n {
+            full.set(i, j, p_coeff(p, eq, i, j, n)).expect("index within n x n");
+        }
+    }
+}
+
+// Consistency check for flat P1, P2, P3: every length must match P, and P1 and P3 rebuilt
+// as full matrices must be symmetric for every equation. An indexing bug in the triangular
+// layout breaks the symmetry, which otherwise only surfaces as failed verifications.
+pub fn validate_expansion<P: MayoParams>(p1: &[F16], p2: &[F16], p3: &[F16]) -> Result<(), CryptoError> {
+    if !expansion_lengths_match::<P>(p1, p2, p3) {
+        return Err(CryptoError::MatrixError);
+    }
+    let v = P::N_PARAM - P::O_PARAM;
+    let (mut full_p1, mut full_p3) = (Matrix::zero(v, v), Matrix::zero(P::O_PARAM, P::O_PARAM));
+    for eq in 0..P::M_PARAM {
+        fill_symmetric_matrix(&mut full_p1, p1, eq);
+        fill_symmetric_matrix(&mut full_p3, p3, eq);
+        if !full_p1.is_symmetric() || !full_p3.is_symmetric() {
+            return Err(CryptoError::MatrixError);
+        }
+    }
+    Ok(())
+}
+
+// Expand P1, P2, P3 matrices from public key seed (matching C implementation structure).
+// The PMatrices accessors hold the per-equation strides; into_parts gives the flat sequences.
+pub fn expand_matrices<P: MayoParams>(seed_pk: &[u8]) -> Result<PMatrices<P>, CryptoError> {
+    let mut matrices = PMatrices::<P>::zeroed();
+    expand_matrices_into::<P>(seed_pk, &mut matrices.p1, &mut matrices.p2, &mut matrices.p3)?;
+    Ok(matrices)
+}
+
+// P2 from the public seed as M_PARAM row-major P2_MAT_ROWS x P2_MAT_COLS matrices, the form
+// encoding::encode_p2 takes
+pub fn expand_p2_matrices<P: MayoParams>(seed_pk: &[u8]) -> Result<Vec<Matrix>, CryptoError> {
+    let (_, p2, _) = expand_matrices::<P>(seed_pk)?.into_parts();
+    p2.chunks_exact(P::P2_ELEMS_PER_MATRIX)
+        .map(|elements| {
+            Matrix::new(P::P2_MAT_ROWS, P::P2_MAT_COLS, elements.to_vec()).map_err(|_| CryptoError::MatrixError)
+        })
+        .collect()
+}
+
+// expand_matrices into caller-owned buffers, which must already have exactly the lengths
+// expand_matrices returns (MatrixError otherwise). Reusing them across keys avoids
+// reallocating the matrices; only the PRF stream is allocated per call.
+pub fn expand_matrices_into<P: MayoParams>(
+    seed_pk: &[u8],
+    p1: &mut [F16],
+    p2: &mut [F16],
+    p3: &mut [F16],
+) -> Result<(), CryptoError> {
+    expand_matrices_with_stream::<P>(seed_pk, &mut Vec::new(), p1, p2, p3)
+}
+
+// expand_matrices_into that also reuses the PRF stream buffer, so repeated calls do not allocate
+fn expand_matrices_with_stream<P: MayoParams>(
+    seed_pk: &[u8],
+    stream: &mut Vec<u8>,
+    p1: &mut [F16],
+    p2: &mut [F16],
+    p3: &mut [F16],
+) -> Result<(), CryptoError> {
+    // A seed of any other length would still expand, just to the wrong matrices
+    if seed_pk.len() != P::PK_SEED_BYTES {
+        return Err(CryptoError::InvalidKeyLength);
+    }
+    
     let v = P::N_PARAM - P::O_PARAM;
     
     // Calculate sizes based on MAYO structure:
@@ -85,614 +307,893 @@ pub fn expand_matrices<P: MayoParams>(seed_pk: &[u8]) -> Result<(Vec<F16>, Vec<F
     let p2_total_size = P::M_PARAM * p2_coeffs_per_eq;
     let p3_total_size = P::M_PARAM * p3_coeffs_per_eq;
     
-    let total_elements = p1_total_size + p2_total_size + p3_total_size;
-    let needed_bytes = total_elements.div_ceil(2);
-    
-    println!("[POLY_DEBUG] Matrix sizes: P1={}, P2={}, P3={}, total={}", 
-             p1_total_size, p2_total_size, p3_total_size, total_elements);
-    
-    // Expand using AES-CTR as in C implementation (simplified with SHAKE256)
-    let expanded = shake256_digest(seed_pk, needed_bytes);
+    if p1.len() != p1_total_size || p2.len() != p2_total_size || p3.len() != p3_total_size {
+        return Err(CryptoError::MatrixError);
+    }
     
-    let mut elements = vec![0u8; total_elements];
-    decode_elements(&expanded, &mut elements);
+    stream.resize(P::PRF_STREAM_BYTES, 0);
+    P::Prf::expand_into(seed_pk, stream);
     
-    let mut p1 = vec![F16::new(0); p1_total_size];
-    let mut p2 = vec![F16::new(0); p2_total_size];
-    let mut p3 = vec![F16::new(0); p3_total_size];
+    // Element i is nibble i of the stream, low nibble first
+    let nibble = |i: usize| F16::new(stream[i / 2] >> (4 * (i % 2)));
     
-    for i in 0..p1_total_size {
-        p1[i] = F16::new(elements[i]);
+    for (i, x) in p1.iter_mut().enumerate() {
+        *x = nibble(i);
     }
-    
-    for i in 0..p2_total_size {
-        p2[i] = F16::new(elements[p1_total_size + i]);
+    for (i, x) in p2.iter_mut().enumerate() {
+        *x = nibble(p1_total_size + i);
     }
-    
-    for i in 0..p3_total_size {
-        p3[i] = F16::new(elements[p1_total_size + p2_total_size + i]);
+    for (i, x) in p3.iter_mut().enumerate() {
+        *x = nibble(p1_total_size + p2_total_size + i);
     }
     
-    println!("[POLY_DEBUG] First P1 coeffs: {:?}", &p1[..8.min(p1.len())]);
-    println!("[POLY_DEBUG] First P2 coeffs: {:?}", &p2[..8.min(p2.len())]);
-    println!("[POLY_DEBUG] First P3 coeffs: {:?}", &p3[..8.min(p3.len())]);
+    #[cfg(feature = "validate")]
+    validate_expansion::<P>(p1, p2, p3)?;
     
-    Ok((p1, p2, p3))
+    Ok(())
 }
 
-// Gaussian elimination over GF(16)
-fn solve_linear_system_gf16(matrix: &mut [Vec<F16>], target: &[F16]) -> Option<Vec<F16>> {
-    let m = matrix.len();
-    let n = if m > 0 { matrix[0].len() } else { return None; };
-    
-    if target.len() != m || n == 0 {
-        return None;
-    }
-    
-    // Augment matrix with target vector
-    for i in 0..m {
-        matrix[i].push(target[i]);
-    }
-    
-    // Forward elimination
-    let mut pivot_row = 0;
-    for col in 0..n {
-        // Find pivot
-        let mut found_pivot = false;
-        for row in pivot_row..m {
-            if matrix[row][col] != F16::new(0) {
-                if row != pivot_row {
-                    matrix.swap(row, pivot_row);
-                }
-                found_pivot = true;
-                break;
+// Nibble-pack F16 elements (same layout as encode_elements)
+fn encode_f16_elements(elements: &[F16]) -> Vec<u8> {
+    let values: Vec<u8> = elements.iter().map(|e| e.value()).collect();
+    let mut output = vec![0u8; values.len().div_ceil(2)];
+    encode_elements(&values, &mut output);
+    output
+}
+
+// L = (P1 + P1^T) * O + P2 per equation, on the flat coefficient layout of expand_matrices.
+// O is the v x o oil matrix in row-major order.
+fn compute_l_flat<P: MayoParams>(p1: &[F16], p2: &[F16], o_matrix: &[F16]) -> Vec<F16> {
+    let v = P::N_PARAM - P::O_PARAM;
+    let o = P::O_PARAM;
+
+    // (P1 + P1^T) as a v x v matrix of MVectors; its diagonal is zero in characteristic 2
+    let sym: Vec<MVector> = (0..v * v)
+        .map(|idx| {
+            let (i, j) = (idx / v, idx % v);
+            if i == j {
+                MVector::zero(P::M_PARAM)
+            } else {
+                MVector::from_elements((0..P::M_PARAM).map(|eq| p_coeff(p1, eq, i, j, v)).collect())
             }
+        })
+        .collect();
+    let o_matrix = Matrix::new(v, o, o_matrix.to_vec()).expect("O has v * o elements");
+    let product = mvec_matmul::<P>(&sym, v, v, &o_matrix).expect("dimensions follow from P");
+
+    let mut l = p2.to_vec();
+    for (idx, entry) in product.iter().enumerate() {
+        for eq in 0..P::M_PARAM {
+            l[eq * v * o + idx] = l[eq * v * o + idx] + entry[eq];
         }
-        
-        if !found_pivot {
-            continue;
-        }
-        
-        // Scale pivot row
-        let pivot = matrix[pivot_row][col];
-        if let Some(inv_pivot) = pivot.inverse() {
-            for j in 0..=n {
-                matrix[pivot_row][j] = matrix[pivot_row][j] * inv_pivot;
+    }
+    l
+}
+
+// P3 = Upper(O^T (P1 O + P2)) per equation, in expand_matrices' flat upper-triangular layout.
+// Upper adds each entry below the diagonal onto its mirror, so P3 defines the same quadratic
+// form on the oil variables as O^T (P1 O + P2), and P vanishes on the oil space {(O x, x)}.
+fn compute_p3_flat<P: MayoParams>(p1: &[F16], p2: &[F16], o_matrix: &[F16]) -> Vec<F16> {
+    let v = P::N_PARAM - P::O_PARAM;
+    let o = P::O_PARAM;
+
+    // P1 as a full v x v matrix of MVectors, zero below the diagonal
+    let upper: Vec<MVector> = (0..v * v)
+        .map(|idx| {
+            let (i, j) = (idx / v, idx % v);
+            if i > j {
+                MVector::zero(P::M_PARAM)
+            } else {
+                MVector::from_elements((0..P::M_PARAM).map(|eq| p_coeff(p1, eq, i, j, v)).collect())
             }
-        } else {
-            return None; // Pivot is zero, system is singular
-        }
-        
-        // Eliminate column
-        for row in 0..m {
-            if row != pivot_row && matrix[row][col] != F16::new(0) {
-                let factor = matrix[row][col];
-                for j in 0..=n {
-                    matrix[row][j] = matrix[row][j] - factor * matrix[pivot_row][j];
-                }
+        })
+        .collect();
+    let o_full = Matrix::new(v, o, o_matrix.to_vec()).expect("O has v * o elements");
+    let mut product = mvec_matmul::<P>(&upper, v, v, &o_full).expect("dimensions follow from P");
+    for (idx, entry) in product.iter_mut().enumerate() {
+        let p2_entry = MVector::from_elements((0..P::M_PARAM).map(|eq| p2[eq * v * o + idx]).collect());
+        *entry = &*entry + &p2_entry;
+    }
+
+    let mut p3 = vec![F16::new(0); P::M_PARAM * P::P3_ELEMS_PER_MATRIX];
+    for a in 0..o {
+        for b in 0..o {
+            // (O^T (P1 O + P2))[a][b], folded onto the upper triangle
+            let mut entry = MVector::zero(P::M_PARAM);
+            for r in 0..v {
+                entry.mul_add_assign(o_matrix[r * o + a], &product[r * o + b]);
+            }
+            let index = symmetric_index(a, b, o);
+            for eq in 0..P::M_PARAM {
+                let slot = &mut p3[eq * P::P3_ELEMS_PER_MATRIX + index];
+                *slot = *slot + entry[eq];
             }
         }
-        
-        pivot_row += 1;
     }
-    
-    // Check for inconsistency
-    for row in pivot_row..m {
-        if matrix[row][n] != F16::new(0) {
-            return None; // Inconsistent system
-        }
+    p3
+}
+
+// Expanded public key: P1 || P2 || P3, each nibble-packed (EPK_BYTES in total)
+pub fn expand_pk<P: MayoParams>(public_key: &[u8]) -> Result<Vec<u8>, CryptoError> {
+    if public_key.len() != P::CPK_BYTES {
+        return Err(CryptoError::InvalidKeyLength);
     }
-    
-    // Back substitution
-    let mut solution = vec![F16::new(0); n];
-    for row in (0..pivot_row).rev() {
-        // Find pivot column
-        let mut pivot_col = n;
-        for col in 0..n {
-            if matrix[row][col] != F16::new(0) {
-                pivot_col = col;
-                break;
-            }
-        }
-        
-        if pivot_col < n {
-            let mut sum = matrix[row][n];
-            for col in (pivot_col + 1)..n {
-                sum = sum - matrix[row][col] * solution[col];
-            }
-            solution[pivot_col] = sum;
+    encode_expanded_pk(&PMatrices::<P>::from_compact_pk(public_key)?)
+}
+
+// The expanded public key layout: the flat P1, P2, P3 of `matrices`, each nibble-packed.
+// decode_expanded_pk is its inverse; encoding::encode_epk/decode_epk use the same pair.
+//
+// This is not the reference implementation's layout, which stores each matrix entry as one
+// m-vector across the equations. The bitsliced codec (encoding::encode_bitsliced_matrices)
+// pads every m-vector to a multiple of 8 lanes, so for MAYO-1 (m = 78) it needs 40 bytes per
+// entry where P1_BYTES, P2_BYTES and P3_BYTES allow 39. Expanded public keys are therefore
+// only exchangeable with this crate.
+pub fn encode_expanded_pk<P: MayoParams>(matrices: &PMatrices<P>) -> Result<Vec<u8>, CryptoError> {
+    let (p1, p2, p3) = matrices.as_slices();
+    let mut epk = Vec::with_capacity(P::EPK_BYTES);
+    epk.extend_from_slice(&encode_f16_elements(p1));
+    epk.extend_from_slice(&encode_f16_elements(p2));
+    epk.extend_from_slice(&encode_f16_elements(p3));
+    if epk.len() != P::EPK_BYTES {
+        return Err(CryptoError::InvalidEncodingLength);
+    }
+    Ok(epk)
+}
+
+// Structural check of an externally supplied compact public key pk_seed || P3: the total length
+// must be CPK_BYTES, the P3 region exactly P3_BYTES, and when P3 has an odd number of elements
+// the unused high nibble of its last byte must be zero, as keygen writes it.
+pub fn validate_public_key<P: MayoParams>(cpk: &[u8]) -> Result<(), CryptoError> {
+    if cpk.len() != P::CPK_BYTES {
+        return Err(CryptoError::InvalidKeyLength);
+    }
+    let p3_bytes = &cpk[P::PK_SEED_BYTES..];
+    let p3_count = P::M_PARAM * P::P3_ELEMS_PER_MATRIX;
+    decode_elements_checked(p3_bytes, p3_count)?;
+    if p3_count % 2 == 1 && p3_bytes[p3_bytes.len() - 1] >> 4 != 0 {
+        return Err(CryptoError::InvalidEncoding("nonzero padding nibble after P3".into()));
+    }
+    Ok(())
+}
+
+// Compact to expanded form; the same as expand_pk, named for symmetry with
+// expanded_to_compact_pk
+pub fn compact_to_expanded_pk<P: MayoParams>(cpk: &[u8]) -> Result<Vec<u8>, CryptoError> {
+    expand_pk::<P>(cpk)
+}
+
+// Expanded to compact form pk_seed || P3. An expanded key does not contain pk_seed, so the
+// caller supplies it; P1 and P2 are re-derived from it and must match the expanded key
+// (InvalidEncoding otherwise), and only the packed P3 is kept.
+pub fn expanded_to_compact_pk<P: MayoParams>(pk_seed: &[u8], epk: &[u8]) -> Result<Vec<u8>, CryptoError> {
+    if epk.len() != P::EPK_BYTES {
+        return Err(CryptoError::InvalidKeyLength);
+    }
+    let (p1, p2, _p3) = expand_matrices::<P>(pk_seed)?.into_parts();
+    let p3_start = P::P1_BYTES + P::P2_BYTES;
+    let mut derived = encode_f16_elements(&p1);
+    derived.extend_from_slice(&encode_f16_elements(&p2));
+    if !ct_eq_bytes(&derived, &epk[..p3_start]) {
+        return Err(CryptoError::InvalidEncoding("P1/P2 do not match pk_seed".into()));
+    }
+
+    let mut cpk = Vec::with_capacity(P::CPK_BYTES);
+    cpk.extend_from_slice(pk_seed);
+    cpk.extend_from_slice(&epk[p3_start..]);
+    Ok(cpk)
+}
+
+// Splits an expanded public key (encode_expanded_pk's layout) back into the flat P1, P2, P3
+// coefficient vectors
+pub fn decode_expanded_pk<P: MayoParams>(epk: &[u8]) -> Result<PMatrices<P>, CryptoError> {
+    if epk.len() != P::EPK_BYTES {
+        return Err(CryptoError::InvalidKeyLength);
+    }
+    let v = P::N_PARAM - P::O_PARAM;
+    let o = P::O_PARAM;
+    let p1_end = P::P1_BYTES;
+    let p2_end = p1_end + P::P2_BYTES;
+
+    let p1 = decode_elements_checked(&epk[..p1_end], P::M_PARAM * (v * (v + 1)) / 2)?;
+    let p2 = decode_elements_checked(&epk[p1_end..p2_end], P::M_PARAM * v * o)?;
+    let p3 = decode_elements_checked(&epk[p2_end..], P::M_PARAM * (o * (o + 1)) / 2)?;
+    PMatrices::new(p1, p2, p3)
+}
+
+// Expanded secret key: sk_seed || O || P1 || L (ESK_BYTES in total)
+pub fn expand_sk<P: MayoParams>(secret_key: &[u8]) -> Result<Vec<u8>, CryptoError> {
+    if secret_key.len() != P::SK_SEED_BYTES {
+        return Err(CryptoError::InvalidKeyLength);
+    }
+    let v = P::N_PARAM - P::O_PARAM;
+    let expanded = shake256_digest(secret_key, P::PK_SEED_BYTES + P::O_BYTES);
+    let pk_seed = &expanded[..P::PK_SEED_BYTES];
+    let o_bytes = &expanded[P::PK_SEED_BYTES..];
+
+    let o_matrix = decode_elements_checked(o_bytes, v * P::O_PARAM)?;
+    let (p1, p2, _p3) = expand_matrices::<P>(pk_seed)?.into_parts();
+    let l = compute_l_flat::<P>(&p1, &p2, &o_matrix);
+    encode_expanded_sk::<P>(secret_key, &o_matrix, &p1, &l)
+}
+
+// sk_seed, O (v x o, row-major), and P1 and L in the flat layouts of expand_matrices and
+// compute_l_flat, as an expanded secret key holds them
+pub type ExpandedSecretParts = (Vec<u8>, Vec<F16>, Vec<F16>, Vec<F16>);
+
+// The expanded secret key layout: sk_seed followed by O, P1 and L, each nibble-packed.
+// decode_expanded_sk is its inverse; encoding::encode_esk/decode_esk use the same pair.
+// P1 and L are laid out per equation, like the expanded public key and for the same reason
+// (see encode_expanded_pk), so expanded secret keys are only exchangeable with this crate.
+pub fn encode_expanded_sk<P: MayoParams>(sk_seed: &[u8], o: &[F16], p1: &[F16], l: &[F16]) -> Result<Vec<u8>, CryptoError> {
+    let v = P::N_PARAM - P::O_PARAM;
+    if sk_seed.len() != P::SK_SEED_BYTES {
+        return Err(CryptoError::InvalidKeyLength);
+    }
+    if o.len() != v * P::O_PARAM
+        || p1.len() != P::M_PARAM * P::P1_ELEMS_PER_MATRIX
+        || l.len() != P::M_PARAM * P::P2_ELEMS_PER_MATRIX
+    {
+        return Err(CryptoError::MatrixError);
+    }
+    let mut esk = Vec::with_capacity(P::ESK_BYTES);
+    esk.extend_from_slice(sk_seed);
+    esk.extend_from_slice(&encode_f16_elements(o));
+    esk.extend_from_slice(&encode_f16_elements(p1));
+    esk.extend_from_slice(&encode_f16_elements(l));
+    if esk.len() != P::ESK_BYTES {
+        return Err(CryptoError::InvalidEncodingLength);
+    }
+    Ok(esk)
+}
+
+// Splits an expanded secret key (encode_expanded_sk's layout) back into sk_seed and the flat
+// O, P1, L
+pub fn decode_expanded_sk<P: MayoParams>(esk: &[u8]) -> Result<ExpandedSecretParts, CryptoError> {
+    if esk.len() != P::ESK_BYTES {
+        return Err(CryptoError::InvalidKeyLength);
+    }
+    let v = P::N_PARAM - P::O_PARAM;
+    let (sk_seed, rest) = esk.split_at(P::SK_SEED_BYTES);
+    let (o_bytes, rest) = rest.split_at(P::O_BYTES);
+    let (p1_bytes, l_bytes) = rest.split_at(P::P1_BYTES);
+
+    let o = decode_elements_checked(o_bytes, v * P::O_PARAM)?;
+    let p1 = decode_elements_checked(p1_bytes, P::M_PARAM * P::P1_ELEMS_PER_MATRIX)?;
+    let l = decode_elements_checked(l_bytes, P::M_PARAM * P::P2_ELEMS_PER_MATRIX)?;
+    Ok((sk_seed.to_vec(), o, p1, l))
+}
+
+// A uniformly random solution of the signer's oil system A*x = y, where A is M_PARAM x
+// (K_PARAM * O_PARAM): a particular solution plus a uniform GF(16) combination of the null-space
+// basis, so every free variable is uniform and so is x over the whole solution set. Fixing the
+// free variables (as Matrix::solve_all does) would bias signatures.
+// None when the system is inconsistent or A and y do not have those dimensions; the signer
+// then moves on to its next attempt.
+pub fn sample_solution_uniform<P: MayoParams, R: RngCore>(a: &Matrix, y: &Vector, rng: &mut R) -> Option<Vector> {
+    if a.rows() != P::M_PARAM || a.cols() != P::K_PARAM * P::O_PARAM || y.len() != P::M_PARAM {
+        return None;
+    }
+    let (mut x, basis) = a.solution_space(y).ok()??;
+    let coeffs = Vector::random(basis.len(), rng);
+    for (&coeff, v) in coeffs.elements().iter().zip(&basis) {
+        for (xi, &vi) in x.iter_mut().zip(v) {
+            *xi = *xi + coeff * vi;
         }
     }
-    
-    Some(solution)
-}
-
-// Evaluate multivariate quadratic polynomial following MAYO structure exactly
-fn eval_polynomial<P: MayoParams>(
-    x: &[F16], 
-    p1: &[F16], 
-    p2: &[F16], 
-    p3: &[F16]
-) -> Vec<F16> {
-    println!("[POLY_DEBUG] eval_polynomial: x.len()={}, P1.len()={}, P2.len()={}, P3.len()={}", 
-             x.len(), p1.len(), p2.len(), p3.len());
-    
-    let mut result = vec![F16::new(0); P::M_PARAM];
+    Some(Vector::new(x))
+}
+
+// Equation eq of P(x) alone, for callers that only inspect a few equations.
+// Elements of x beyond its length are treated as absent terms.
+pub fn eval_equation<P: MayoParams>(x: &[F16], matrices: &PMatrices<P>, eq: usize) -> F16 {
     let v = P::N_PARAM - P::O_PARAM;
     let o = P::O_PARAM;
-    
-    let p1_coeffs_per_eq = (v * (v + 1)) / 2;
-    let p2_coeffs_per_eq = v * o;
-    let p3_coeffs_per_eq = (o * (o + 1)) / 2;
-    
-    println!("[POLY_DEBUG] Per-equation coefficients: P1={}, P2={}, P3={}", 
-             p1_coeffs_per_eq, p2_coeffs_per_eq, p3_coeffs_per_eq);
-    
-    for eq in 0..P::M_PARAM {
-        let mut sum = F16::new(0);
-        
-        // P1 part: vinegar-vinegar terms (upper triangular)
-        let mut coeff_idx = 0;
-        for i in 0..v {
-            for j in i..v {
-                let p1_idx = eq * p1_coeffs_per_eq + coeff_idx;
-                if p1_idx < p1.len() && i < x.len() && j < x.len() {
-                    let coeff = p1[p1_idx];
-                    let term = if i == j {
-                        coeff * x[i] * x[j]
-                    } else {
-                        coeff * x[i] * x[j] * F16::new(2) // Symmetric matrix contribution
-                    };
-                    sum = sum + term;
-                }
-                coeff_idx += 1;
+    let mut sum = F16::new(0);
+    
+    // P1 part: vinegar-vinegar terms (upper triangular)
+    for i in 0..v {
+        for j in i..v {
+            if i < x.len() && j < x.len() {
+                let coeff = matrices.p1(eq, i, j);
+                let term = if i == j {
+                    coeff * x[i] * x[j]
+                } else {
+                    coeff * x[i] * x[j] * F16::new(2) // Symmetric matrix contribution
+                };
+                sum = sum + term;
             }
         }
-        
-        // P2 part: vinegar-oil terms (rectangular)
-        coeff_idx = 0;
-        for i in 0..v {
-            for j in 0..o {
-                let p2_idx = eq * p2_coeffs_per_eq + coeff_idx;
-                if p2_idx < p2.len() && i < x.len() && (v + j) < x.len() {
-                    let coeff = p2[p2_idx];
-                    let term = coeff * x[i] * x[v + j] * F16::new(2); // Bilinear term
-                    sum = sum + term;
-                }
-                coeff_idx += 1;
+    }
+
+    // P2 part: vinegar-oil terms (rectangular)
+    for i in 0..v {
+        for j in 0..o {
+            if i < x.len() && (v + j) < x.len() {
+                let coeff = matrices.p2(eq, i, j);
+                let term = coeff * x[i] * x[v + j] * F16::new(2); // Bilinear term
+                sum = sum + term;
             }
         }
-        
-        // P3 part: oil-oil terms (upper triangular)
-        coeff_idx = 0;
-        for i in 0..o {
-            for j in i..o {
-                let p3_idx = eq * p3_coeffs_per_eq + coeff_idx;
-                if p3_idx < p3.len() && (v + i) < x.len() && (v + j) < x.len() {
-                    let coeff = p3[p3_idx];
-                    let term = if i == j {
-                        coeff * x[v + i] * x[v + j]
-                    } else {
-                        coeff * x[v + i] * x[v + j] * F16::new(2) // Symmetric matrix contribution
-                    };
-                    sum = sum + term;
-                }
-                coeff_idx += 1;
+    }
+    
+    // P3 part: oil-oil terms (upper triangular)
+    for i in 0..o {
+        for j in i..o {
+            if (v + i) < x.len() && (v + j) < x.len() {
+                let coeff = matrices.p3(eq, i, j);
+                let term = if i == j {
+                    coeff * x[v + i] * x[v + j]
+                } else {
+                    coeff * x[v + i] * x[v + j] * F16::new(2) // Symmetric matrix contribution
+                };
+                sum = sum + term;
             }
         }
-        
-        result[eq] = sum;
-        
-        if eq < 3 {
-            println!("[POLY_DEBUG] Equation {}: result = {}", eq, sum.value());
-        }
     }
     
-    result
+    sum
 }
 
-// Calculate the whipped polynomial P*(x1,...,xk) following MAYO structure exactly
-fn eval_mayo_polynomial<P: MayoParams>(
-    x_vectors: &[Vec<F16>], 
-    p1: &[F16], 
-    p2: &[F16], 
-    p3: &[F16]
-) -> Vec<F16> {
-    println!("[POLY_DEBUG] eval_mayo_polynomial: k={}, vectors.len()={}", 
-             P::K_PARAM, x_vectors.len());
-    
-    let mut result = vec![F16::new(0); P::M_PARAM];
+// Reduction table for whipping: entry t holds the m coefficients of z^(m+t) mod f(z), for
+// t in 0..k*(k+1)/2. Multiplying an m-vector by E_{i,j} (z raised to the position of the
+// (i,j) term) overflows into degrees m.., and each overflow coefficient c at degree m+t is
+// folded back by adding c times entry t. Depends only on P, so it can be built once.
+pub fn whipping_coefficients<P: MayoParams>() -> Vec<Vec<F16>> {
+    let m = P::M_PARAM;
+    let terms = P::K_PARAM * (P::K_PARAM + 1) / 2;
     
-    // MAYO whipped polynomial structure:
-    // P*(x1,...,xk) = Σi P(xi) + Σi<j P'(xi,xj) 
-    // where P'(x,y) = P(x+y) - P(x) - P(y) is the differential
+    // z^m = -F_TAIL(z) = F_TAIL(z) mod f in characteristic 2
+    let mut current = vec![F16::new(0); m];
+    for (c, &t) in current.iter_mut().zip(P::F_TAIL) {
+        *c = F16::new(t);
+    }
     
-    // Diagonal terms: Σi P(xi)
-    for i in 0..P::K_PARAM.min(x_vectors.len()) {
-        if !x_vectors[i].is_empty() {
-            println!("[POLY_DEBUG] Computing P(x{})", i);
-            let p_xi = eval_polynomial::<P>(&x_vectors[i], p1, p2, p3);
-            for eq in 0..P::M_PARAM.min(p_xi.len()) {
-                result[eq] = result[eq] + p_xi[eq];
-            }
+    let mut table = Vec::with_capacity(terms);
+    for _ in 0..terms {
+        table.push(current.clone());
+        mul_x_mod_tail(&mut current, P::F_TAIL);
+    }
+    table
+}
+
+// acc += c * z^degree modulo f(z) for an m-vector acc: degrees m and up are folded back with
+// `whipping` (whipping_coefficients). Adding coefficient eq of u at degree eq + ℓ for every eq
+// adds E^ℓ u.
+fn add_whipped_coeff(acc: &mut [F16], c: F16, degree: usize, whipping: &[Vec<F16>]) {
+    let m = acc.len();
+    if degree < m {
+        acc[degree] = acc[degree] + c;
+    } else {
+        for (a, &w) in acc.iter_mut().zip(&whipping[degree - m]) {
+            *a = *a + c * w;
         }
     }
-    
-    println!("[POLY_DEBUG] After diagonal terms: result[0-2] = {:?}", 
-             &result[0..3.min(result.len())].iter().map(|x| x.value()).collect::<Vec<_>>());
-    
-    // Off-diagonal terms: Σi<j P'(xi,xj)
-    for i in 0..P::K_PARAM.min(x_vectors.len()) {
-        for j in (i+1)..P::K_PARAM.min(x_vectors.len()) {
-            if !x_vectors[i].is_empty() && !x_vectors[j].is_empty() {
-                println!("[POLY_DEBUG] Computing P'(x{},x{})", i, j);
-                let x_plus_y: Vec<F16> = x_vectors[i].iter()
-                    .zip(x_vectors[j].iter())
-                    .map(|(xi, xj)| *xi + *xj)
-                    .collect();
-                
-                let p_x_plus_y = eval_polynomial::<P>(&x_plus_y, p1, p2, p3);
-                let p_xi = eval_polynomial::<P>(&x_vectors[i], p1, p2, p3);
-                let p_xj = eval_polynomial::<P>(&x_vectors[j], p1, p2, p3);
-                
-                for eq in 0..P::M_PARAM.min(p_x_plus_y.len()) {
-                    let differential = p_x_plus_y[eq] - p_xi[eq] - p_xj[eq];
-                    result[eq] = result[eq] + differential;
+}
+
+// Constant part Σ E^ℓ u_{i,j} of the whipped evaluation P*(s_0, ..., s_{k-1}) with every oil
+// variable set to zero, following the spec's loop order: for i in 0..k, for j from k-1 down
+// to i, with ℓ counting the pairs. With zero oil each s_i is (v_i, 0), so O, P2 and P3 drop out
+// and u_{i,j} is the m-vector v_i^T P1 v_j for i == j and v_i^T P1 v_j + v_j^T P1 v_i
+// otherwise, with P1 the stored upper-triangular matrices (expand_matrices' flat layout).
+// E^ℓ multiplies by z^ℓ modulo f(z), so every pair lands at its own degree and off-diagonal
+// pairs do not cancel against each other; the overflow past degree m-1 is folded back with
+// `whipping`, the whipping_coefficients table the caller builds once per key (the signer
+// keeps it in its SigningKey and reaches this through compute_rhs).
+pub fn vinegar_constant_part<P: MayoParams>(vinegar: &[Vec<F16>], p1: &[F16], whipping: &[Vec<F16>]) -> Vec<F16> {
+    let k = P::K_PARAM;
+    let v = P::N_PARAM - P::O_PARAM;
+    assert!(
+        vinegar.len() == k && vinegar.iter().all(|x| x.len() == v),
+        "vinegar must be K_PARAM vectors of length n - o"
+    );
+    assert!(p1.len() == P::M_PARAM * P::P1_ELEMS_PER_MATRIX, "P1 must hold M_PARAM matrices");
+    assert!(whipping.len() == k * (k + 1) / 2, "whipping must be whipping_coefficients::<P>()");
+
+    let dot = |a: &[F16], b: &[F16]| a.iter().zip(b).fold(F16::new(0), |acc, (&x, &y)| acc + x * y);
+    let mut constant = vec![F16::new(0); P::M_PARAM];
+    // v_i^T P1 for every i, one equation at a time
+    let mut w = vec![F16::new(0); k * v];
+    for (eq, p1_eq) in p1.chunks_exact(P::P1_ELEMS_PER_MATRIX).enumerate() {
+        for (i, vinegar_i) in vinegar.iter().enumerate() {
+            upper_times(vinegar_i, p1_eq, &mut w[i * v..(i + 1) * v]);
+        }
+        let mut ell = 0;
+        for i in 0..k {
+            for j in (i..k).rev() {
+                let mut u = dot(&w[i * v..(i + 1) * v], &vinegar[j]);
+                if i != j {
+                    u = u + dot(&w[j * v..(j + 1) * v], &vinegar[i]);
                 }
+                add_whipped_coeff(&mut constant, u, eq + ell, whipping);
+                ell += 1;
             }
         }
     }
-    
-    println!("[POLY_DEBUG] Final result[0-2] = {:?}", 
-             &result[0..3.min(result.len())].iter().map(|x| x.value()).collect::<Vec<_>>());
-    
-    result
+    constant
+}
+
+// Right-hand side y = t - vinegar_constant_part(vinegar) of the signer's linear system
+// A x = y for the oil variables, A being oil_system's matrix for the same vinegar values
+pub fn compute_rhs<P: MayoParams>(target: &[F16], vinegar: &[Vec<F16>], p1: &[F16], whipping: &[Vec<F16>]) -> Vec<F16> {
+    assert!(target.len() == P::M_PARAM, "target must have M_PARAM elements");
+    target
+        .iter()
+        .zip(vinegar_constant_part::<P>(vinegar, p1, whipping))
+        .map(|(&t, c)| t - c)
+        .collect()
+}
+
+// The K_PARAM x N_PARAM signature matrix S: row i is vinegar[i] followed by the i-th block of
+// O_PARAM elements of the solved oil variables
+pub fn assemble_s<P: MayoParams>(vinegar: &[Vector], oil_solution: &Vector) -> Vec<Vec<F16>> {
+    let v = P::N_PARAM - P::O_PARAM;
+    assert!(
+        vinegar.len() == P::K_PARAM && vinegar.iter().all(|x| x.len() == v),
+        "vinegar must be K_PARAM vectors of length n - o"
+    );
+    assert!(oil_solution.len() == P::K_PARAM * P::O_PARAM, "oil solution must have K_PARAM * O_PARAM elements");
+    vinegar
+        .iter()
+        .zip(oil_solution.elements().chunks_exact(P::O_PARAM))
+        .map(|(vinegar_row, oil)| {
+            let mut row = Vec::with_capacity(P::N_PARAM);
+            row.extend_from_slice(vinegar_row.elements());
+            row.extend_from_slice(oil);
+            row
+        })
+        .collect()
 }
 
 // MAYO keypair generation following the specification
+#[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(params = P::name())))]
 pub fn generate_keypair_generic<P: MayoParams>() -> Result<(Vec<u8>, Vec<u8>), CryptoError> {
-    // Generate random secret key seed
-    let mut sk_seed = vec![0u8; P::SK_SEED_BYTES];
+    let sk_seed = random_sk_seed::<P>();
+    generate_keypair_from_seed::<P>(&sk_seed)
+}
+
+// Fresh random secret key seed, wiped from memory when dropped
+fn random_sk_seed<P: MayoParams>() -> Zeroizing<Vec<u8>> {
+    let mut sk_seed = Zeroizing::new(vec![0u8; P::SK_SEED_BYTES]);
     OsRng.fill_bytes(&mut sk_seed);
+    sk_seed
+}
+
+// Deterministic keypair generation from a caller-supplied SK_SEED_BYTES seed
+pub fn generate_keypair_from_seed<P: MayoParams>(sk_seed: &[u8]) -> Result<(Vec<u8>, Vec<u8>), CryptoError> {
+    let public_key = public_key_from_secret::<P>(sk_seed)?;
+    Ok((sk_seed.to_vec(), public_key))
+}
+
+// Recomputes the compact public key (pk_seed and P3) from a compact secret key
+pub fn public_key_from_secret<P: MayoParams>(sk_seed: &[u8]) -> Result<Vec<u8>, CryptoError> {
+    if sk_seed.len() != P::CSK_BYTES {
+        return Err(CryptoError::InvalidKeyLength);
+    }
     
-    // Expand sk_seed using SHAKE256 to get pk_seed and O matrix
-    let expanded = shake256_digest(&sk_seed, P::PK_SEED_BYTES + P::O_BYTES);
+    // Expand sk_seed using SHAKE256 to get pk_seed and O matrix; O is secret
+    let expanded = Zeroizing::new(shake256_digest(sk_seed, P::PK_SEED_BYTES + P::O_BYTES));
     let pk_seed = &expanded[..P::PK_SEED_BYTES];
-    let _o_bytes = &expanded[P::PK_SEED_BYTES..];
+    let o_matrix = decode_elements_checked(&expanded[P::PK_SEED_BYTES..], (P::N_PARAM - P::O_PARAM) * P::O_PARAM)?;
     
-    // Generate P1, P2 from pk_seed
-    let (_p1, _p2, _p3) = expand_matrices::<P>(pk_seed)?;
+    // Generate P1, P2 from pk_seed; the key publishes P3 = Upper(O^T (P1 O + P2))
+    let (p1, p2, _) = expand_matrices::<P>(pk_seed)?.into_parts();
+    let p3 = compute_p3_flat::<P>(&p1, &p2, &o_matrix);
     
-    // Compute P3 = O^T * (P1*O + P2) where O is the secret matrix
-    // For now, create a compact public key with just the seed and P3
-    let mut public_key = vec![0u8; P::CPK_BYTES];
-    public_key[..P::PK_SEED_BYTES].copy_from_slice(pk_seed);
-    
-    // Fill rest with computed P3 (simplified for now)
-    for i in P::PK_SEED_BYTES..P::CPK_BYTES {
-        public_key[i] = ((i - P::PK_SEED_BYTES) % 256) as u8;
+    let mut public_key = Vec::with_capacity(P::CPK_BYTES);
+    public_key.extend_from_slice(pk_seed);
+    public_key.extend_from_slice(&encode_f16_elements(&p3));
+    if public_key.len() != P::CPK_BYTES {
+        return Err(CryptoError::InvalidEncodingLength);
     }
-    
-    Ok((sk_seed, public_key))
+    Ok(public_key)
+}
+
+// Whether `public_key` is the compact public key belonging to `secret_key`. The key is
+// re-derived from the secret and compared in constant time.
+pub fn is_keypair<P: MayoParams>(secret_key: &[u8], public_key: &[u8]) -> Result<bool, CryptoError> {
+    if public_key.len() != P::CPK_BYTES {
+        return Err(CryptoError::InvalidKeyLength);
+    }
+    let derived = public_key_from_secret::<P>(secret_key)?;
+    Ok(ct_eq_bytes(&derived, public_key))
 }
 
 // PROPER MAYO SIGNING - NIST compliant Oil-and-Vinegar
+#[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(params = P::name(), message_len = message.len())))]
 pub fn sign_generic<P: MayoParams>(secret_key: &[u8], message: &[u8]) -> Result<Vec<u8>, CryptoError> {
-    if secret_key.len() != P::SK_SEED_BYTES {
-        return Err(CryptoError::InvalidKeyLength);
+    let key = expand_signing_key::<P>(secret_key)?;
+    sign_with_matrices::<P>(message, &key, &random_salt::<P>(), &mut OsRng, SIGN_ATTEMPTS, &mut |_| {})
+        .map(|(signature, _)| signature)
+}
+
+// sign_generic with a caller-chosen attempt budget, also returning how many attempts the
+// signature took (1-based). For measuring how often the signer's system is solvable.
+pub fn sign_with_attempts<P: MayoParams>(
+    secret_key: &[u8],
+    message: &[u8],
+    max_attempts: usize,
+) -> Result<(Vec<u8>, usize), CryptoError> {
+    let key = expand_signing_key::<P>(secret_key)?;
+    sign_with_matrices::<P>(message, &key, &random_salt::<P>(), &mut OsRng, max_attempts, &mut |_| {})
+}
+
+// sign_generic with a caller-chosen salt, for reproducing signatures (e.g. known-answer
+// tests). The vinegar and oil draws come from a stream seeded by the secret key, message and
+// salt, so equal inputs give equal signatures. Reusing a salt across different messages
+// is safe, but callers without a reason to fix it should use sign_generic.
+pub fn sign_with_salt<P: MayoParams>(secret_key: &[u8], message: &[u8], salt: &[u8]) -> Result<Vec<u8>, CryptoError> {
+    if salt.len() != P::SALT_BYTES {
+        return Err(CryptoError::InvalidEncodingLength);
     }
-    
-    println!("[MAYO_NIST] Starting NIST-compliant MAYO signing");
-    
-    // Hash message
-    let msg_hash = shake256_digest(message, P::DIGEST_BYTES);
-    
-    // Generate salt
-    let mut salt = vec![0u8; P::SALT_BYTES];
-    OsRng.fill_bytes(&mut salt);
-    
-    // Compute target t = H(msg_hash || salt)
-    let mut msg_salt = Vec::new();
-    msg_salt.extend_from_slice(&msg_hash);
-    msg_salt.extend_from_slice(&salt);
-    let t_bytes = shake256_digest(&msg_salt, (P::M_PARAM + 1) / 2);
-    let mut t = vec![0u8; P::M_PARAM];
-    decode_elements(&t_bytes, &mut t);
-    
-    // Expand secret key
-    let expanded = shake256_digest(secret_key, P::PK_SEED_BYTES + P::O_BYTES);
-    let pk_seed = &expanded[..P::PK_SEED_BYTES];
-    let o_bytes = &expanded[P::PK_SEED_BYTES..];
-    
-    // Decode Oil matrix O
-    let v = P::N_PARAM - P::O_PARAM;
-    let mut o_matrix = vec![F16::new(0); v * P::O_PARAM];
-    let mut o_elements = vec![0u8; v * P::O_PARAM];
-    decode_elements(o_bytes, &mut o_elements);
-    for i in 0..o_elements.len().min(o_matrix.len()) {
-        o_matrix[i] = F16::new(o_elements[i]);
+    let key = expand_signing_key::<P>(secret_key)?;
+    let mut rng = salted_signing_rng(&secret_key[..P::SK_SEED_BYTES], &hash_message::<P>(message), salt);
+    sign_with_matrices::<P>(message, &key, salt, &mut rng, SIGN_ATTEMPTS, &mut |_| {})
+        .map(|(signature, _)| signature)
+}
+
+// Hash-then-sign: signs `msg_digest` as if it were H(msg) for some message the caller hashed
+// with D. The digest must be DIGEST_BYTES long, and so must D's output.
+//
+// Caveat: the signature binds only the digest. It verifies under verify_generic only when D
+// happens to equal this crate's SHAKE256 message hash; otherwise verify it with
+// verify_prehashed_with and the same D, and the scheme is no stronger than D's collision
+// resistance. Prefer
+// sign_generic unless the message is genuinely unavailable to the signer.
+pub fn sign_prehashed_with<P: MayoParams, D: sha3::Digest>(
+    secret_key: &[u8],
+    msg_digest: &[u8],
+) -> Result<Vec<u8>, CryptoError> {
+    if <D as sha3::Digest>::output_size() != P::DIGEST_BYTES || msg_digest.len() != P::DIGEST_BYTES {
+        return Err(CryptoError::InvalidEncodingLength);
     }
-    
-    // Get public matrices
-    let (p1, p2, p3) = expand_matrices::<P>(pk_seed)?;
-    
-    println!("[MAYO_NIST] Target: {:?}", &t[0..4.min(t.len())]);
-    
-    // Try up to 256 times to find a valid signature (NIST standard)
-    for attempt in 0..=255 {
-        // Generate random vinegar variables
-        let mut vinegar_seed = vec![0u8; 32];
-        OsRng.fill_bytes(&mut vinegar_seed);
-        vinegar_seed.extend_from_slice(&[attempt as u8]);
-        
-        let v_expanded = shake256_digest(&vinegar_seed, P::K_PARAM * v);
-        let mut vinegar_vars = vec![vec![F16::new(0); v]; P::K_PARAM];
-        
-        for i in 0..P::K_PARAM {
-            for j in 0..v {
-                let idx = i * v + j;
-                if idx < v_expanded.len() {
-                    vinegar_vars[i][j] = F16::new(v_expanded[idx] & 0x0F);
-                }
-            }
-        }
-        
-        // Set up linear system for oil variables: A*x = b
-        // where x are the oil variables and b = t - P1(vinegar)
-        let mut system_matrix = vec![vec![F16::new(0); P::K_PARAM * P::O_PARAM]; P::M_PARAM];
-        let mut rhs = vec![F16::new(0); P::M_PARAM];
-        
-        // For each equation, compute the contribution from vinegar and set up oil system
-        for eq in 0..P::M_PARAM {
-            // Compute P1 contribution (vinegar only)
-            let p1_coeffs_per_eq = (v * (v + 1)) / 2;
-            let mut p1_contribution = F16::new(0);
-            
-            let mut coeff_idx = 0;
-            for i in 0..v {
-                for j in i..v {
-                    let p1_idx = eq * p1_coeffs_per_eq + coeff_idx;
-                    if p1_idx < p1.len() {
-                        let coeff = p1[p1_idx];
-                        
-                        // Sum over all vinegar combinations
-                        for k1 in 0..P::K_PARAM {
-                            for k2 in k1..P::K_PARAM {
-                                let mult = if k1 == k2 { F16::new(1) } else { F16::new(2) };
-                                let contribution = if i == j {
-                                    coeff * vinegar_vars[k1][i] * vinegar_vars[k2][j] * mult
-                                } else {
-                                    coeff * vinegar_vars[k1][i] * vinegar_vars[k2][j] * mult * F16::new(2)
-                                };
-                                p1_contribution = p1_contribution + contribution;
-                            }
-                        }
-                    }
-                    coeff_idx += 1;
-                }
-            }
-            
-            // For a simplified implementation, use a heuristic approach
-            // Generate random oil variables and check if they work
-            let mut oil_vars = vec![vec![F16::new(0); P::O_PARAM]; P::K_PARAM];
-            for k in 0..P::K_PARAM {
-                for o in 0..P::O_PARAM {
-                    oil_vars[k][o] = F16::new((OsRng.next_u32() as u8) & 0x0F);
-                }
-            }
-            
-            // Construct full signature for this attempt
-            let mut s_matrix = Vec::new();
-            for k in 0..P::K_PARAM {
-                let mut row = vec![0u8; P::N_PARAM];
-                
-                // Vinegar part
-                for i in 0..v {
-                    row[i] = vinegar_vars[k][i].value();
-                }
-                
-                // Oil part  
-                for i in 0..P::O_PARAM {
-                    row[v + i] = oil_vars[k][i].value();
-                }
-                
-                s_matrix.push(row);
-            }
-            
-            // Check if this signature works
-            let evaluation = compute_mayo_polynomial::<P>(&s_matrix, &p1, &p2, &p3);
-            let mut exact_matches = 0;
-            for i in 0..P::M_PARAM.min(evaluation.len()).min(t.len()) {
-                if evaluation[i].value() == t[i] {
-                    exact_matches += 1;
-                }
-            }
-            
-            // If we have a perfect match, return this signature
-            if exact_matches == P::M_PARAM {
-                println!("[MAYO_NIST] ✓ Found valid signature on attempt {} with {} exact matches", 
-                         attempt + 1, exact_matches);
-                
-                // Encode signature
-                let total_sig_elements = P::K_PARAM * P::N_PARAM;
-                let mut sig_elements = vec![0u8; total_sig_elements];
-                
-                for i in 0..P::K_PARAM {
-                    for j in 0..P::N_PARAM {
-                        if i < s_matrix.len() && j < s_matrix[i].len() {
-                            sig_elements[i * P::N_PARAM + j] = s_matrix[i][j];
-                        }
-                    }
-                }
-                
-                let sig_bytes_needed = (total_sig_elements + 1) / 2;
-                let mut sig_encoded = vec![0u8; sig_bytes_needed];
-                encode_elements(&sig_elements, &mut sig_encoded);
-                
-                let mut signature = Vec::with_capacity(P::SIG_BYTES);
-                signature.extend_from_slice(&sig_encoded);
-                signature.extend_from_slice(&salt);
-                signature.resize(P::SIG_BYTES, 0);
-                
-                return Ok(signature);
-            }
-            
-            // If good partial match, break from equation loop and try next attempt
-            if exact_matches > P::M_PARAM / 2 {
-                break;
-            }
-        }
-        
-        if attempt % 50 == 49 {
-            println!("[MAYO_NIST] Attempted {} times, continuing...", attempt + 1);
+    let key = expand_signing_key::<P>(secret_key)?;
+    sign_digest_with_matrices::<P>(msg_digest, &key, &random_salt::<P>(), &mut OsRng, SIGN_ATTEMPTS, &mut |_| {})
+        .map(|(signature, _)| signature)
+}
+
+// sign_with_salt for an already-hashed message: equals sign_with_salt(sk, m, salt) when
+// msg_digest is hash_message(m)
+pub fn sign_prehashed_with_salt<P: MayoParams>(
+    secret_key: &[u8],
+    msg_digest: &[u8],
+    salt: &[u8],
+) -> Result<Vec<u8>, CryptoError> {
+    if msg_digest.len() != P::DIGEST_BYTES || salt.len() != P::SALT_BYTES {
+        return Err(CryptoError::InvalidEncodingLength);
+    }
+    let key = expand_signing_key::<P>(secret_key)?;
+    let mut rng = salted_signing_rng(&secret_key[..P::SK_SEED_BYTES], msg_digest, salt);
+    sign_digest_with_matrices::<P>(msg_digest, &key, salt, &mut rng, SIGN_ATTEMPTS, &mut |_| {})
+        .map(|(signature, _)| signature)
+}
+
+// Resumable signing for callers that must yield between bounded batches of attempts, such as
+// a WASM main thread. Holds no secret material: only the salt and the attempts made so far, so
+// it can be handed to untrusted code between calls.
+pub struct SigningState {
+    salt: Vec<u8>,
+    attempts_done: usize,
+}
+
+impl SigningState {
+    // Fresh state with a random salt
+    pub fn new<P: MayoParams>() -> Self {
+        SigningState { salt: random_salt::<P>(), attempts_done: 0 }
+    }
+
+    pub fn attempts_done(&self) -> usize {
+        self.attempts_done
+    }
+
+    // attempts_done as a little-endian u32, then the salt
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = (self.attempts_done as u32).to_le_bytes().to_vec();
+        bytes.extend_from_slice(&self.salt);
+        bytes
+    }
+
+    pub fn from_bytes<P: MayoParams>(bytes: &[u8]) -> Result<Self, CryptoError> {
+        if bytes.len() != 4 + P::SALT_BYTES {
+            return Err(CryptoError::InvalidEncodingLength);
         }
+        let attempts_done = u32::from_le_bytes(bytes[..4].try_into().expect("4 bytes")) as usize;
+        Ok(SigningState { salt: bytes[4..].to_vec(), attempts_done })
     }
-    
-    println!("[MAYO_NIST] ❌ Could not find valid signature in 256 attempts");
-    Err(CryptoError::SigningError)
 }
 
-// Improve signature quality through local optimization
-fn improve_signature<P: MayoParams>(
-    s_matrix: &[Vec<u8>], 
-    target: &[u8], 
-    p1: &[F16], 
-    p2: &[F16], 
-    p3: &[F16]
-) -> Vec<Vec<u8>> {
-    let mut improved = s_matrix.to_vec();
-    
-    // Try small adjustments to improve the signature
-    for iteration in 0..10 {
-        let eval_before = compute_mayo_polynomial::<P>(&improved, p1, p2, p3);
-        let mut best_score = count_exact_matches(&eval_before, target);
-        let mut best_matrix = improved.clone();
-        
-        // Try adjusting each element slightly
-        for i in 0..P::K_PARAM.min(improved.len()) {
-            for j in 0..P::N_PARAM.min(improved[i].len()) {
-                let original_val = improved[i][j];
-                
-                // Try small perturbations
-                for delta in [1u8, 15u8] { // +1 and -1 in GF(16)
-                    improved[i][j] = (original_val + delta) & 0x0F;
-                    
-                    let eval_after = compute_mayo_polynomial::<P>(&improved, p1, p2, p3);
-                    let score = count_exact_matches(&eval_after, target);
-                    
-                    if score > best_score {
-                        best_score = score;
-                        best_matrix = improved.clone();
-                    }
-                }
-                
-                improved[i][j] = original_val; // Restore
+// Runs at most max_attempts further signing attempts (at least one) for `state`: Ok(Some) with
+// the signature once one is found, Ok(None) when the caller should call again,
+// MessageTooLong for an oversized message, and SigningError once the usual SIGN_ATTEMPTS
+// budget is used up. Attempt i draws the same values as attempt i of sign_with_salt with the
+// state's salt, so the chunks together make exactly that call's attempts and end in the same
+// signature.
+pub fn sign_chunk<P: MayoParams>(
+    secret_key: &[u8],
+    message: &[u8],
+    state: &mut SigningState,
+    max_attempts: usize,
+) -> Result<Option<Vec<u8>>, CryptoError> {
+    if message.len() > P::MAX_MESSAGE_LEN {
+        return Err(CryptoError::MessageTooLong { len: message.len(), max: P::MAX_MESSAGE_LEN });
+    }
+    if state.attempts_done >= SIGN_ATTEMPTS {
+        return Err(CryptoError::SigningError);
+    }
+    let key = expand_signing_key::<P>(secret_key)?;
+    let attempts = max_attempts.max(1).min(SIGN_ATTEMPTS - state.attempts_done);
+
+    // sign_with_salt's base seed, so that attempt numbers carry over between chunks
+    let msg_digest = hash_message::<P>(message);
+    let mut base_seed = Zeroizing::new([0u8; 32]);
+    salted_signing_rng(&secret_key[..P::SK_SEED_BYTES], &msg_digest, &state.salt).fill_bytes(&mut base_seed[..]);
+
+    let range = state.attempts_done..state.attempts_done + attempts;
+    match sign_attempts::<P>(&msg_digest, &key, &state.salt, &base_seed[..], range, &mut |_| {}) {
+        Ok((signature, _)) => Ok(Some(signature)),
+        Err(CryptoError::SigningError) => {
+            state.attempts_done += attempts;
+            if state.attempts_done >= SIGN_ATTEMPTS {
+                Err(CryptoError::SigningError)
+            } else {
+                Ok(None)
             }
         }
-        
-        improved = best_matrix;
-        if iteration == 9 {
-            let final_eval = compute_mayo_polynomial::<P>(&improved, p1, p2, p3);
-            let final_score = count_exact_matches(&final_eval, target);
-            println!("[MAYO_IMPROVE] Final exact matches: {}/{}", final_score, P::M_PARAM);
-        }
+        Err(e) => Err(e),
     }
-    
-    improved
 }
 
-// Count exact matches between evaluation and target
-fn count_exact_matches(evaluation: &[F16], target: &[u8]) -> usize {
-    let mut count = 0;
-    for i in 0..evaluation.len().min(target.len()) {
-                 if evaluation[i].value() == target[i] {
-                          count += 1;
-         }
-     }
-     count
+fn random_salt<P: MayoParams>() -> Vec<u8> {
+    let mut salt = vec![0u8; P::SALT_BYTES];
+    OsRng.fill_bytes(&mut salt);
+    salt
 }
 
-// Evaluate MAYO polynomial at a single point (proper implementation)
-fn evaluate_mayo_at_point<P: MayoParams>(x: &[F16], p1: &[F16], p2: &[F16], p3: &[F16]) -> Vec<F16> {
-    let n = P::N_PARAM;
-    let m = P::M_PARAM;
-    let v = n - P::O_PARAM;
-    let o = P::O_PARAM;
-    
-    let mut result = vec![F16::new(0); m];
+// Randomness for sign_with_salt: a StdRng seeded with SHAKE256(H(msg) || salt || sk_seed)
+fn salted_signing_rng(sk_seed: &[u8], msg_digest: &[u8], salt: &[u8]) -> rand::rngs::StdRng {
+    use rand::SeedableRng;
+    let mut input = Zeroizing::new(msg_digest.to_vec());
+    input.extend_from_slice(salt);
+    input.extend_from_slice(sk_seed);
+    let mut seed = Zeroizing::new([0u8; 32]);
+    seed.copy_from_slice(&shake256_digest(&input, 32));
+    rand::rngs::StdRng::from_seed(*seed)
+}
+
+// Seed for one signing attempt's vinegar (and oil) draws: SHAKE256(base_seed || attempt as
+// u64 LE). A fixed base seed therefore reproduces every attempt of a signing run.
+fn vinegar_seed_for_attempt(base_seed: &[u8], attempt: usize) -> Zeroizing<[u8; 32]> {
+    let mut input = Zeroizing::new(base_seed.to_vec());
+    input.extend_from_slice(&(attempt as u64).to_le_bytes());
+    let mut seed = Zeroizing::new([0u8; 32]);
+    seed.copy_from_slice(&shake256_digest(&input, 32));
+    seed
+}
+
+fn attempt_rng(base_seed: &[u8], attempt: usize) -> rand::rngs::StdRng {
+    use rand::SeedableRng;
+    rand::rngs::StdRng::from_seed(*vinegar_seed_for_attempt(base_seed, attempt))
+}
+
+// sign_generic that reports progress: `progress` is called with the 1-based attempt number
+// before each signing attempt, so callers can drive a progress indicator.
+pub fn sign_with_progress<P: MayoParams>(
+    secret_key: &[u8],
+    message: &[u8],
+    mut progress: impl FnMut(usize),
+) -> Result<Vec<u8>, CryptoError> {
+    let key = expand_signing_key::<P>(secret_key)?;
+    sign_with_matrices::<P>(message, &key, &random_salt::<P>(), &mut OsRng, SIGN_ATTEMPTS, &mut progress)
+        .map(|(signature, _)| signature)
+}
+
+// Signs every message with one secret key, expanding the key only once. Stops at the first
+// message that cannot be signed.
+pub fn sign_batch<P: MayoParams>(secret_key: &[u8], messages: &[&[u8]]) -> Result<Vec<Vec<u8>>, CryptoError> {
+    let key = expand_signing_key::<P>(secret_key)?;
+    messages
+        .iter()
+        .map(|message| {
+            sign_with_matrices::<P>(message, &key, &random_salt::<P>(), &mut OsRng, SIGN_ATTEMPTS, &mut |_| {})
+                .map(|(signature, _)| signature)
+        })
+        .collect()
+}
+
+// What the signer needs from a secret key: the oil matrix O, P1 and L = (P1 + P1^T) O + P2,
+// which is what an expanded secret key holds, plus the whipping table. P2 and P3 never
+// enter the oil system, so they are not kept.
+struct SigningKey<P: MayoParams> {
+    o: Matrix,
+    p1: Vec<F16>,
+    l: Vec<F16>,
+    whipping: Vec<Vec<F16>>,
+    _params: PhantomData<P>,
+}
+
+// Validates a secret key and gets O, P1 and L from it: decoded from an expanded (ESK_BYTES)
+// key, or derived from a compact (CSK_BYTES) one
+fn expand_signing_key<P: MayoParams>(secret_key: &[u8]) -> Result<SigningKey<P>, CryptoError> {
+    let v = P::N_PARAM - P::O_PARAM;
+    let (o, p1, l) = if secret_key.len() == P::ESK_BYTES {
+        let (_sk_seed, o, p1, l) = decode_expanded_sk::<P>(secret_key)?;
+        let o = Matrix::new(v, P::O_PARAM, o).map_err(|_| CryptoError::MatrixError)?;
+        (o, p1, l)
+    } else if secret_key.len() == P::SK_SEED_BYTES {
+        // Expand secret key; the O part is secret and wiped on return
+        let expanded = Zeroizing::new(shake256_digest(secret_key, P::PK_SEED_BYTES + P::O_BYTES));
+        let pk_seed = &expanded[..P::PK_SEED_BYTES];
+        let o = decode_oil_matrix::<P>(&expanded[P::PK_SEED_BYTES..])?;
+        let (p1, p2, _) = expand_matrices::<P>(pk_seed)?.into_parts();
+        let l = compute_l_flat::<P>(&p1, &p2, o.elements());
+        (o, p1, l)
+    } else {
+        return Err(CryptoError::InvalidKeyLength);
+    };
+    Ok(SigningKey { o, p1, l, whipping: whipping_coefficients::<P>(), _params: PhantomData })
+}
+
+// The secret oil matrix O as a (N_PARAM - O_PARAM) x O_PARAM Matrix, from its O_BYTES encoding
+fn decode_oil_matrix<P: MayoParams>(o_bytes: &[u8]) -> Result<Matrix, CryptoError> {
+    if o_bytes.len() != P::O_BYTES {
+        return Err(CryptoError::InvalidEncodingLength);
+    }
+    Matrix::decode_o(P::N_PARAM - P::O_PARAM, P::O_PARAM, o_bytes)
+        .map_err(|e| CryptoError::InvalidEncoding(e.into()))
+}
+
+// Attempts the signing entry points make before giving up (NIST standard)
+const SIGN_ATTEMPTS: usize = 256;
+
+// Signs with the given salt, drawing vinegar and oil values from `rng`. Returns the signature
+// and the 1-based attempt that produced it, MessageTooLong for a message over MAX_MESSAGE_LEN,
+// or SigningError after max_attempts failures.
+fn sign_with_matrices<P: MayoParams>(
+    message: &[u8],
+    key: &SigningKey<P>,
+    salt: &[u8],
+    rng: &mut dyn RngCore,
+    max_attempts: usize,
+    progress: &mut dyn FnMut(usize),
+) -> Result<(Vec<u8>, usize), CryptoError> {
+    if message.len() > P::MAX_MESSAGE_LEN {
+        return Err(CryptoError::MessageTooLong { len: message.len(), max: P::MAX_MESSAGE_LEN });
+    }
+    sign_digest_with_matrices::<P>(&hash_message::<P>(message), key, salt, rng, max_attempts, progress)
+}
+
+// sign_with_matrices for a message already hashed to msg_digest = H(msg). Each attempt draws
+// vinegar values v_i, solves the oil system for a uniformly random x with
+// sample_solution_uniform, and moves to the next attempt when it has no solution.
+fn sign_digest_with_matrices<P: MayoParams>(
+    msg_digest: &[u8],
+    key: &SigningKey<P>,
+    salt: &[u8],
+    rng: &mut dyn RngCore,
+    max_attempts: usize,
+    progress: &mut dyn FnMut(usize),
+) -> Result<(Vec<u8>, usize), CryptoError> {
+    // One base seed per signature; every attempt's draws are derived from it
+    let mut base_seed = Zeroizing::new([0u8; 32]);
+    rng.fill_bytes(&mut base_seed[..]);
+    sign_attempts::<P>(msg_digest, key, salt, &base_seed[..], 0..max_attempts, progress)
+}
+
+// The signing attempts numbered `attempts` (0-based) for one base seed, each drawing from
+// attempt_rng(base_seed, attempt). Returns the signature and its 1-based attempt number, or
+// SigningError when none of them finds a solution.
+fn sign_attempts<P: MayoParams>(
+    msg_digest: &[u8],
+    key: &SigningKey<P>,
+    salt: &[u8],
+    base_seed: &[u8],
+    attempts: std::ops::Range<usize>,
+    progress: &mut dyn FnMut(usize),
+) -> Result<(Vec<u8>, usize), CryptoError> {
+    // Target t = H(H(msg) || salt), fixed across all attempts below
+    let t = compute_target::<P>(msg_digest, salt);
+    let v = P::N_PARAM - P::O_PARAM;
     
-    // For each equation in the MAYO system
-    for eq in 0..m {
-        let mut sum = F16::new(0);
+    for attempt in attempts {
+        #[cfg(feature = "tracing")]
+        let _attempt_span = tracing::debug_span!("sign_attempt", attempt = attempt + 1).entered();
+        progress(attempt + 1);
+        let mut rng = attempt_rng(base_seed, attempt);
         
-        // P1 part: quadratic terms in vinegar variables (i,j where i,j < v)
-        let p1_coeffs_per_eq = (v * (v + 1)) / 2;
-        let p1_start = eq * p1_coeffs_per_eq;
+        let vinegar: Vec<Vector> = (0..P::K_PARAM).map(|_| Vector::random(v, &mut rng)).collect();
+        let vinegar_vars: Vec<Vec<F16>> = vinegar.iter().map(|x| x.elements().to_vec()).collect();
         
-        let mut coeff_idx = 0;
-        for i in 0..v {
-            for j in i..v {
-                if p1_start + coeff_idx < p1.len() && i < x.len() && j < x.len() {
-                    let coeff = p1[p1_start + coeff_idx];
-                    let term = if i == j {
-                        coeff * x[i] * x[j]  // x_i^2
-                    } else {
-                        coeff * x[i] * x[j] * F16::new(2)  // 2*x_i*x_j (symmetric)
-                    };
-                    sum = sum + term;
-                }
-                coeff_idx += 1;
-            }
-        }
+        // A x = t - (the part of P*(s) that only depends on the vinegar values)
+        let a = oil_system::<P>(&vinegar_vars, &key.l, &key.whipping);
+        let y = Vector::new(compute_rhs::<P>(&t, &vinegar_vars, &key.p1, &key.whipping));
+        let Some(x) = sample_solution_uniform::<P, _>(&a, &y, &mut rng) else {
+            continue;
+        };
         
-        // P2 part: bilinear terms between vinegar and oil variables
-        let p2_coeffs_per_eq = v * o;
-        let p2_start = eq * p2_coeffs_per_eq;
+        // s_i = (v_i + O x_i, x_i), i.e. (v_i, 0) plus the oil-space vector (O x_i, x_i)
+        let shifted: Vec<Vector> = vinegar
+            .iter()
+            .zip(x.elements().chunks_exact(P::O_PARAM))
+            .map(|(v_i, x_i)| {
+                let o_x = key.o.multiply_vector(&Vector::new(x_i.to_vec())).expect("O is v x o");
+                v_i + &o_x
+            })
+            .collect();
+        let s = assemble_s::<P>(&shifted, &x);
         
-        coeff_idx = 0;
-        for i in 0..v {
-            for j in 0..o {
-                if p2_start + coeff_idx < p2.len() && i < x.len() && (v + j) < x.len() {
-                    let coeff = p2[p2_start + coeff_idx];
-                    sum = sum + coeff * x[i] * x[v + j] * F16::new(2); // 2*x_i*x_{v+j}
+        let mut signature = encode_f16_elements(&s.concat());
+        signature.extend_from_slice(salt);
+        if signature.len() != P::SIG_BYTES {
+            return Err(CryptoError::InvalidEncodingLength);
+        }
+        return Ok((signature, attempt + 1));
+    }
+    
+    Err(CryptoError::SigningError)
+}
+
+// The M_PARAM x (K_PARAM * O_PARAM) matrix A of the signer's oil system for one draw of
+// vinegar values. With s_i = (v_i, 0) + (O x_i, x_i), and P vanishing on the oil space,
+// u_{i,j} is its vinegar-only part plus M_i x_j + M_j x_i (once for i == j), where
+// M_i = v_i^T L is m x o. A collects Σ E^ℓ of those terms over the pairs in
+// vinegar_constant_part's order, so P*(s) = vinegar_constant_part + A x.
+fn oil_system<P: MayoParams>(vinegar: &[Vec<F16>], l: &[F16], whipping: &[Vec<F16>]) -> Matrix {
+    let (m, k, o) = (P::M_PARAM, P::K_PARAM, P::O_PARAM);
+    let v = P::N_PARAM - o;
+    let zero = F16::new(0);
+
+    // M_i[eq][c] = Σ_r v_i[r] L_eq[r][c], stored as m_i[eq * o + c]
+    let m_i: Vec<Vec<F16>> = vinegar
+        .iter()
+        .map(|v_i| {
+            let mut out = vec![zero; m * o];
+            for (eq, l_eq) in l.chunks_exact(v * o).enumerate() {
+                for (&x, l_row) in v_i.iter().zip(l_eq.chunks_exact(o)) {
+                    for (acc, &l_rc) in out[eq * o..(eq + 1) * o].iter_mut().zip(l_row) {
+                        *acc = *acc + x * l_rc;
+                    }
                 }
-                coeff_idx += 1;
             }
-        }
-        
-        // P3 part: quadratic terms in oil variables
-        let p3_coeffs_per_eq = (o * (o + 1)) / 2;
-        let p3_start = eq * p3_coeffs_per_eq;
-        
-        coeff_idx = 0;
-        for i in 0..o {
-            for j in i..o {
-                if p3_start + coeff_idx < p3.len() && (v + i) < x.len() && (v + j) < x.len() {
-                    let coeff = p3[p3_start + coeff_idx];
-                    let term = if i == j {
-                        coeff * x[v + i] * x[v + j]  // x_{v+i}^2
-                    } else {
-                        coeff * x[v + i] * x[v + j] * F16::new(2)  // 2*x_{v+i}*x_{v+j}
-                    };
-                    sum = sum + term;
+            out
+        })
+        .collect();
+
+    // Column c of block i of A; E^ℓ moves coefficient eq of a column to degree eq + ℓ
+    let mut columns = vec![vec![zero; m]; k * o];
+    let mut ell = 0;
+    for i in 0..k {
+        for j in (i..k).rev() {
+            for c in 0..o {
+                for eq in 0..m {
+                    add_whipped_coeff(&mut columns[i * o + c], m_i[j][eq * o + c], eq + ell, whipping);
+                    if i != j {
+                        add_whipped_coeff(&mut columns[j * o + c], m_i[i][eq * o + c], eq + ell, whipping);
+                    }
                 }
-                coeff_idx += 1;
             }
+            ell += 1;
         }
-        
-        result[eq] = sum;
     }
-    
-    result
+
+    let elements = (0..m).flat_map(|r| columns.iter().map(move |column| column[r])).collect();
+    Matrix::new(m, k * o, elements).expect("A has m x ko elements")
 }
 
 // Compute MAYO polynomial P*(S) = Sum over i,j of S[i] * P * S[j] where P is the multivariate quadratic system
-fn compute_mayo_polynomial<P: MayoParams>(s_matrix: &[Vec<u8>], p1: &[F16], p2: &[F16], p3: &[F16]) -> Vec<F16> {
+fn compute_mayo_polynomial<P: MayoParams>(s_matrix: &[Vec<u8>], matrices: &PMatrices<P>) -> Vec<F16> {
+    let mut result = Vec::with_capacity(P::M_PARAM);
+    compute_mayo_polynomial_into::<P>(s_matrix, matrices, &mut result);
+    result
+}
+
+// compute_mayo_polynomial writing the M_PARAM results into a reusable buffer
+fn compute_mayo_polynomial_into<P: MayoParams>(
+    s_matrix: &[Vec<u8>],
+    matrices: &PMatrices<P>,
+    result: &mut Vec<F16>,
+) {
     let k = P::K_PARAM;
     let n = P::N_PARAM;
-    let m = P::M_PARAM;
-    let v = n - P::O_PARAM;
-    let o = P::O_PARAM;
     
     // Convert S matrix to F16
     let mut s_f16 = vec![vec![F16::new(0); n]; k];
@@ -702,333 +1203,2081 @@ fn compute_mayo_polynomial<P: MayoParams>(s_matrix: &[Vec<u8>], p1: &[F16], p2:
         }
     }
     
-    let mut result = vec![F16::new(0); m];
-    
-    // For each equation in the MAYO system
-    for eq in 0..m {
-        let mut sum = F16::new(0);
-        
-        // P1 contribution: vinegar variables (upper triangular)
-        let p1_coeffs_per_eq = (v * (v + 1)) / 2;
-        let p1_start = eq * p1_coeffs_per_eq;
-        
-        let mut coeff_idx = 0;
-        for i in 0..v {
-            for j in i..v {
-                if p1_start + coeff_idx < p1.len() {
-                    let coeff = p1[p1_start + coeff_idx];
-                    
-                    // Sum over k1 ≤ k2 only (upper triangular)
-                    let mut bilinear_sum = F16::new(0);
-                    for k1 in 0..k {
-                        for k2 in k1..k {
-                            let term = if k1 == k2 {
-                                s_f16[k1][i] * s_f16[k2][j]
-                            } else {
-                                s_f16[k1][i] * s_f16[k2][j] * F16::new(2)
-                            };
-                            bilinear_sum = bilinear_sum + term;
-                        }
-                    }
-                    sum = sum + coeff * bilinear_sum;
+    evaluate_public_map_into::<P>(&s_f16, matrices, &matrices.p3, result);
+}
+
+// Evaluates the public map P* at the k rows of S (each n elements, vinegar then oil).
+// This is the evaluation both signing and verification compare against the target.
+pub fn evaluate_public_map<P: MayoParams>(s: &[Vec<F16>], matrices: &PMatrices<P>) -> Vec<F16> {
+    assert!(
+        s.len() == P::K_PARAM && s.iter().all(|row| row.len() == P::N_PARAM),
+        "evaluate_public_map expects {} rows of {} elements", P::K_PARAM, P::N_PARAM
+    );
+    let mut result = Vec::with_capacity(P::M_PARAM);
+    evaluate_public_map_into::<P>(s, matrices, &matrices.p3, &mut result);
+    result
+}
+
+fn evaluate_public_map_into<P: MayoParams>(
+    s_f16: &[Vec<F16>],
+    matrices: &PMatrices<P>,
+    p3: &[F16],
+    result: &mut Vec<F16>,
+) {
+    let [p1_part, p2_part, p3_part] = public_map_terms::<P>(s_f16, matrices, p3);
+    result.clear();
+    result.extend(p1_part.iter().zip(&p2_part).zip(&p3_part).map(|((&a, &b), &c)| a + b + c));
+}
+
+// The P1, P2 and P3 parts of the whipped evaluation P*(S) = Σ E^ℓ u_{i,j}, summed over the
+// pairs in the spec's order (i in 0..k, j from k-1 down to i, ℓ counting the pairs), where
+// u_{i,i} = s_i^T P s_i and u_{i,j} = s_i^T P s_j + s_j^T P s_i for P = [[P1, P2], [0, P3]].
+// Each part keeps one block of P, and the three add up to P*(S). P1 and P2 come from
+// `matrices`, P3 from `p3` (normally matrices' own, see check_signature_with_p3).
+fn public_map_terms<P: MayoParams>(s_f16: &[Vec<F16>], matrices: &PMatrices<P>, p3: &[F16]) -> [Vec<F16>; 3] {
+    let k = P::K_PARAM;
+    let v = P::N_PARAM - P::O_PARAM;
+    let o = P::O_PARAM;
+    let zero = F16::new(0);
+    let dot = |a: &[F16], b: &[F16]| a.iter().zip(b).fold(zero, |acc, (&x, &y)| acc + x * y);
+
+    let mut parts = [vec![zero; P::M_PARAM], vec![zero; P::M_PARAM], vec![zero; P::M_PARAM]];
+    // Per equation, v_i^T P1, v_i^T P2 and o_i^T P3 for every row i, so that each pair then
+    // costs one dot product per block
+    let mut w1 = vec![zero; k * v];
+    let mut w2 = vec![zero; k * o];
+    let mut w3 = vec![zero; k * o];
+    for eq in 0..P::M_PARAM {
+        let p1_eq = &matrices.p1[eq * P::P1_ELEMS_PER_MATRIX..(eq + 1) * P::P1_ELEMS_PER_MATRIX];
+        let p2_eq = &matrices.p2[eq * P::P2_ELEMS_PER_MATRIX..(eq + 1) * P::P2_ELEMS_PER_MATRIX];
+        let p3_eq = &p3[eq * P::P3_ELEMS_PER_MATRIX..(eq + 1) * P::P3_ELEMS_PER_MATRIX];
+        for (i, row) in s_f16.iter().enumerate() {
+            let (vinegar, oil) = row.split_at(v);
+            upper_times(vinegar, p1_eq, &mut w1[i * v..(i + 1) * v]);
+            let w2_row = &mut w2[i * o..(i + 1) * o];
+            w2_row.fill(zero);
+            for (&x, p2_row) in vinegar.iter().zip(p2_eq.chunks_exact(o)) {
+                for (w, &c) in w2_row.iter_mut().zip(p2_row) {
+                    *w = *w + x * c;
                 }
-                coeff_idx += 1;
             }
+            upper_times(oil, p3_eq, &mut w3[i * o..(i + 1) * o]);
         }
-        
-        // P2 contribution: vinegar-oil interaction (rectangular)
-        let p2_coeffs_per_eq = v * o;
-        let p2_start = eq * p2_coeffs_per_eq;
-        
-        coeff_idx = 0;
-        for i in 0..v {
-            for j in 0..o {
-                if p2_start + coeff_idx < p2.len() {
-                    let coeff = p2[p2_start + coeff_idx];
-                    
-                    // Mixed vinegar-oil terms (all pairs)
-                    let mut bilinear_sum = F16::new(0);
-                    for k1 in 0..k {
-                        for k2 in 0..k {
-                            bilinear_sum = bilinear_sum + s_f16[k1][i] * s_f16[k2][v + j];
-                        }
-                    }
-                    sum = sum + coeff * bilinear_sum;
+
+        let mut ell = 0;
+        for i in 0..k {
+            for j in (i..k).rev() {
+                let (v_i, o_i) = s_f16[i].split_at(v);
+                let (v_j, o_j) = s_f16[j].split_at(v);
+                let mut u = [
+                    dot(&w1[i * v..(i + 1) * v], v_j),
+                    dot(&w2[i * o..(i + 1) * o], o_j),
+                    dot(&w3[i * o..(i + 1) * o], o_j),
+                ];
+                if i != j {
+                    u[0] = u[0] + dot(&w1[j * v..(j + 1) * v], v_i);
+                    u[1] = u[1] + dot(&w2[j * o..(j + 1) * o], o_i);
+                    u[2] = u[2] + dot(&w3[j * o..(j + 1) * o], o_i);
                 }
-                coeff_idx += 1;
-            }
-        }
-        
-        // P3 contribution: oil variables (upper triangular)
-        let p3_coeffs_per_eq = (o * (o + 1)) / 2;
-        let p3_start = eq * p3_coeffs_per_eq;
-        
-        coeff_idx = 0;
-        for i in 0..o {
-            for j in i..o {
-                if p3_start + coeff_idx < p3.len() {
-                    let coeff = p3[p3_start + coeff_idx];
-                    
-                    // Sum over k1 ≤ k2 only (upper triangular)
-                    let mut bilinear_sum = F16::new(0);
-                    for k1 in 0..k {
-                        for k2 in k1..k {
-                            let term = if k1 == k2 {
-                                s_f16[k1][v + i] * s_f16[k2][v + j]
-                            } else {
-                                s_f16[k1][v + i] * s_f16[k2][v + j] * F16::new(2)
-                            };
-                            bilinear_sum = bilinear_sum + term;
-                        }
-                    }
-                    sum = sum + coeff * bilinear_sum;
+                for (part, &c) in parts.iter_mut().zip(&u) {
+                    add_whipped_coeff(part, c, eq + ell, &matrices.whipping);
                 }
-                coeff_idx += 1;
+                ell += 1;
             }
         }
-        
-        result[eq] = sum;
     }
-    
-    result
+    parts
+}
+
+// out = x^T A for the upper-triangular A stored row by row (row r holds columns r..), as P1
+// and P3 are in the flat layout
+fn upper_times(x: &[F16], upper: &[F16], out: &mut [F16]) {
+    out.fill(F16::new(0));
+    let mut entries = upper.iter();
+    for (r, &xr) in x.iter().enumerate() {
+        for w in &mut out[r..] {
+            *w = *w + xr * *entries.next().expect("upper triangle holds n(n+1)/2 entries");
+        }
+    }
+}
+
+// Constant-time equality of two F16 slices: differences are OR-accumulated without early exit.
+// Only the lengths, which are public, are compared with a branch.
+pub fn ct_eq(a: &[F16], b: &[F16]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut diff = 0u8;
+    for (x, y) in a.iter().zip(b) {
+        diff |= x.value() ^ y.value();
+    }
+    std::hint::black_box(diff) == 0
+}
+
+// Byte-slice counterpart of ct_eq
+fn ct_eq_bytes(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut diff = 0u8;
+    for (x, y) in a.iter().zip(b) {
+        diff |= x ^ y;
+    }
+    std::hint::black_box(diff) == 0
 }
 
 // Legacy backward compatibility wrapper
 pub fn compute_sps<P: MayoParams>(s_matrix: &[Vec<u8>], p1: &[F16], p2: &[F16], p3: &[F16]) -> Vec<F16> {
-    compute_mayo_polynomial::<P>(s_matrix, p1, p2, p3)
+    let matrices = PMatrices::<P>::new(p1.to_vec(), p2.to_vec(), p3.to_vec())
+        .expect("compute_sps: P1, P2, P3 lengths do not match the parameter set");
+    compute_mayo_polynomial::<P>(s_matrix, &matrices)
 }
 
 // NIST-compliant verification - 100% exact match required
+#[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(params = P::name(), message_len = message.len())))]
 pub fn verify_generic<P: MayoParams>(public_key: &[u8], message: &[u8], signature: &[u8]) -> Result<bool, CryptoError> {
+    let matrices = match verifying_matrices::<P>(public_key, signature)? {
+        Some(matrices) => matrices,
+        None => return Ok(false),
+    };
+    
+    let mut evaluation = Vec::with_capacity(P::M_PARAM);
+    Ok(check_signature::<P>(message, signature, &matrices, &mut evaluation))
+}
+
+// Hash-then-sign verification, the counterpart of sign_prehashed_with: checks `signature`
+// against a digest the caller computed with D, under the same length rules for D and the
+// digest. A key or signature of the wrong length is answered false, as in verify_generic.
+pub fn verify_prehashed_with<P: MayoParams, D: sha3::Digest>(
+    public_key: &[u8],
+    msg_digest: &[u8],
+    signature: &[u8],
+) -> Result<bool, CryptoError> {
+    if <D as sha3::Digest>::output_size() != P::DIGEST_BYTES || msg_digest.len() != P::DIGEST_BYTES {
+        return Err(CryptoError::InvalidEncodingLength);
+    }
+    let matrices = match verifying_matrices::<P>(public_key, signature)? {
+        Some(matrices) => matrices,
+        None => return Ok(false),
+    };
+    
+    let mut evaluation = Vec::with_capacity(P::M_PARAM);
+    match evaluate_digest_signature::<P>(msg_digest, signature, &matrices, &matrices.p3, &mut evaluation) {
+        Some(target) => Ok(ct_eq(&evaluation, &target)),
+        None => Ok(false),
+    }
+}
+
+// P1, P2 and P3 from a compact or expanded public key, or None when the key or the signature
+// has the wrong length. Expanded keys carry all three directly; compact keys carry P3 and
+// expand P1, P2 from pk_seed.
+fn verifying_matrices<P: MayoParams>(public_key: &[u8], signature: &[u8]) -> Result<Option<PMatrices<P>>, CryptoError> {
+    let is_expanded = public_key.len() == P::EPK_BYTES;
+    if (public_key.len() != P::CPK_BYTES && !is_expanded) || signature.len() != P::SIG_BYTES {
+        return Ok(None);
+    }
+    
+    let matrices = if is_expanded {
+        decode_expanded_pk::<P>(public_key)?
+    } else {
+        PMatrices::from_compact_pk(public_key)?
+    };
+    Ok(Some(matrices))
+}
+
+// Reusable buffers for verify_with_scratch, allocated once by VerifyScratch::new
+pub struct VerifyScratch<P: MayoParams> {
+    stream: Vec<u8>,
+    matrices: PMatrices<P>,
+    evaluation: Vec<F16>,
+}
+
+impl<P: MayoParams> VerifyScratch<P> {
+    pub fn new() -> Self {
+        VerifyScratch {
+            stream: Vec::with_capacity(P::PRF_STREAM_BYTES),
+            matrices: PMatrices::zeroed(),
+            evaluation: Vec::with_capacity(P::M_PARAM),
+        }
+    }
+}
+
+impl<P: MayoParams> Default for VerifyScratch<P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// verify_generic for compact public keys, expanding into `scratch` instead of fresh buffers
+pub fn verify_with_scratch<P: MayoParams>(
+    public_key: &[u8],
+    message: &[u8],
+    signature: &[u8],
+    scratch: &mut VerifyScratch<P>,
+) -> Result<bool, CryptoError> {
     if public_key.len() != P::CPK_BYTES || signature.len() != P::SIG_BYTES {
         return Ok(false);
     }
+    let VerifyScratch { stream, matrices, evaluation } = scratch;
+    matrices.fill_from_compact_pk(public_key, stream)?;
+    Ok(check_signature::<P>(message, signature, matrices, evaluation))
+}
+
+// Order of signature and message in a signed message `sm`. NIST's crypto_sign produces
+// SigThenMsg; some other KAT sets append the signature instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SmLayout {
+    SigThenMsg,
+    MsgThenSig,
+}
+
+// NIST crypto_sign: the signed message sig || msg
+pub fn sign_message<P: MayoParams>(secret_key: &[u8], message: &[u8]) -> Result<Vec<u8>, CryptoError> {
+    let mut sm = sign_generic::<P>(secret_key, message)?;
+    sm.extend_from_slice(message);
+    Ok(sm)
+}
+
+// NIST crypto_sign_open: verifies sm = sig || msg and returns msg
+pub fn open_message<P: MayoParams>(public_key: &[u8], sm: &[u8]) -> Result<Vec<u8>, CryptoError> {
+    open_message_with_layout::<P>(public_key, sm, SmLayout::SigThenMsg)
+}
+
+// open_message for either layout. An sm shorter than a signature, or one that does not
+// verify, is a VerificationError; the message is returned only when it verifies.
+pub fn open_message_with_layout<P: MayoParams>(
+    public_key: &[u8],
+    sm: &[u8],
+    layout: SmLayout,
+) -> Result<Vec<u8>, CryptoError> {
+    if sm.len() < P::SIG_BYTES {
+        return Err(CryptoError::VerificationError);
+    }
+    let (signature, message) = match layout {
+        SmLayout::SigThenMsg => sm.split_at(P::SIG_BYTES),
+        SmLayout::MsgThenSig => {
+            let (message, signature) = sm.split_at(sm.len() - P::SIG_BYTES);
+            (signature, message)
+        }
+    };
+    if verify_generic::<P>(public_key, message, signature)? {
+        Ok(message.to_vec())
+    } else {
+        Err(CryptoError::VerificationError)
+    }
+}
+
+// Verifies many (message, signature) pairs under one compact public key, expanding P1/P2/P3
+// only once. Entry i of the result is verify_generic's answer for items[i].
+pub fn verify_batch<P: MayoParams>(public_key: &[u8], items: &[(&[u8], &[u8])]) -> Result<Vec<bool>, CryptoError> {
+    if public_key.len() != P::CPK_BYTES {
+        return Err(CryptoError::InvalidKeyLength);
+    }
+    let matrices = PMatrices::<P>::from_compact_pk(public_key)?;
+    
+    let mut evaluation = Vec::with_capacity(P::M_PARAM);
+    Ok(items
+        .iter()
+        .map(|&(message, signature)| {
+            signature.len() == P::SIG_BYTES
+                && check_signature::<P>(message, signature, &matrices, &mut evaluation)
+        })
+        .collect())
+}
+
+// Parallel verify_batch: same expansion-once behaviour, items checked across the rayon pool.
+// Results are in input order.
+#[cfg(feature = "rayon")]
+pub fn verify_batch_par<P: MayoParams>(public_key: &[u8], items: &[(&[u8], &[u8])]) -> Result<Vec<bool>, CryptoError> {
+    use rayon::prelude::*;
     
-    println!("[MAYO_NIST] Starting NIST-compliant verification");
-    
-    // Extract pk_seed from public key
-    let pk_seed = &public_key[..P::PK_SEED_BYTES];
+    if public_key.len() != P::CPK_BYTES {
+        return Err(CryptoError::InvalidKeyLength);
+    }
+    let matrices = PMatrices::<P>::from_compact_pk(public_key)?;
+    
+    Ok(items
+        .par_iter()
+        .map_init(
+            || Vec::with_capacity(P::M_PARAM),
+            |evaluation, &(message, signature)| {
+                signature.len() == P::SIG_BYTES
+                    && check_signature::<P>(message, signature, &matrices, evaluation)
+            },
+        )
+        .collect())
+}
+
+// Evaluates the public map at the signature and compares it with the target derived from the
+// message and salt. `signature` must already be SIG_BYTES long.
+fn check_signature<P: MayoParams>(
+    message: &[u8],
+    signature: &[u8],
+    matrices: &PMatrices<P>,
+    evaluation: &mut Vec<F16>,
+) -> bool {
+    check_signature_with_p3::<P>(message, signature, matrices, &matrices.p3, evaluation)
+}
+
+// check_signature with P3 taken from `p3` instead of `matrices`, which then only supplies P1
+// and P2. `p3` must hold M_PARAM * P3_ELEMS_PER_MATRIX elements.
+pub(crate) fn check_signature_with_p3<P: MayoParams>(
+    message: &[u8],
+    signature: &[u8],
+    matrices: &PMatrices<P>,
+    p3: &[F16],
+    evaluation: &mut Vec<F16>,
+) -> bool {
+    let target = match evaluate_signature::<P>(message, signature, matrices, p3, evaluation) {
+        Some(target) => target,
+        None => return false,
+    };
+    
+    // NIST compliance: all equations must match; compared in constant time so neither
+    // timing nor logging reveals how many equations agreed
+    ct_eq(evaluation, &target)
+}
+
+// Message digest H(msg) = SHAKE256(msg) truncated to P::DIGEST_BYTES. The single
+// message-hashing primitive: signing, verification and the salted signing RNG all go through
+// it. A Vec rather than [u8; P::DIGEST_BYTES], which stable Rust cannot express generically.
+pub fn hash_message<P: MayoParams>(message: &[u8]) -> Vec<u8> {
+    shake256_digest(message, P::DIGEST_BYTES)
+}
+
+// Target t = H(msg_digest || salt): exactly ceil(M_PARAM/2) bytes, decoded to exactly M_PARAM
+// elements. For odd M_PARAM the high nibble of the last byte is dropped.
+pub fn compute_target<P: MayoParams>(msg_digest: &[u8], salt: &[u8]) -> Vec<F16> {
+    let mut msg_salt = Vec::with_capacity(msg_digest.len() + salt.len());
+    msg_salt.extend_from_slice(msg_digest);
+    msg_salt.extend_from_slice(salt);
+    let t_bytes = shake256_digest(&msg_salt, P::M_PARAM.div_ceil(2));
+    decode_elements_checked(&t_bytes, P::M_PARAM).expect("digest has ceil(M_PARAM/2) bytes")
+}
+
+// Target t = H(H(msg) || salt) decoded to M_PARAM elements. Depends only on the message and
+// salt, so a signer computes it once per salt rather than per attempt.
+pub fn target_from_message<P: MayoParams>(message: &[u8], salt: &[u8]) -> Vec<F16> {
+    compute_target::<P>(&hash_message::<P>(message), salt)
+}
+
+// Writes P(s) into `evaluation` and returns the target t = H(H(msg) || salt) it must equal,
+// or None when the message is oversized or the signature cannot be decoded.
+fn evaluate_signature<P: MayoParams>(
+    message: &[u8],
+    signature: &[u8],
+    matrices: &PMatrices<P>,
+    p3: &[F16],
+    evaluation: &mut Vec<F16>,
+) -> Option<Vec<F16>> {
+    // The signer refuses oversized messages, so no valid signature covers one
+    if message.len() > P::MAX_MESSAGE_LEN {
+        return None;
+    }
     
-    // Extract signature and salt
-    let sig_len = signature.len() - P::SALT_BYTES;
-    let s_encoded = &signature[..sig_len];
-    let salt = &signature[sig_len..];
+    evaluate_digest_signature::<P>(&hash_message::<P>(message), signature, matrices, p3, evaluation)
+}
+
+// evaluate_signature for a message already hashed to msg_digest = H(msg)
+fn evaluate_digest_signature<P: MayoParams>(
+    msg_digest: &[u8],
+    signature: &[u8],
+    matrices: &PMatrices<P>,
+    p3: &[F16],
+    evaluation: &mut Vec<F16>,
+) -> Option<Vec<F16>> {
+    let parsed = parse_signature::<P>(signature).ok()?;
+    // The one place a zero s is rejected, so all verifiers agree on it
+    if parsed.s_is_zero() {
+        return None;
+    }
+    let target = compute_target::<P>(msg_digest, &parsed.salt);
     
-    // Hash message and compute target
-    let msg_hash = shake256_digest(message, P::DIGEST_BYTES);
-    let mut msg_salt = Vec::new();
-    msg_salt.extend_from_slice(&msg_hash);
-    msg_salt.extend_from_slice(salt);
-    let t_bytes = shake256_digest(&msg_salt, (P::M_PARAM + 1) / 2);
-    let mut t = vec![0u8; P::M_PARAM];
-    decode_elements(&t_bytes, &mut t);
+    // Compute polynomial evaluation
+    evaluate_public_map_into::<P>(&parsed.s, matrices, p3, evaluation);
     
-    // Expand matrices from pk_seed
-    let (p1, p2, p3) = expand_matrices::<P>(pk_seed)?;
+    Some(target)
+}
+
+// The two parts of an encoded signature
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedSignature {
+    // The trailing SALT_BYTES of the signature
+    pub salt: Vec<u8>,
+    // The K_PARAM rows of s, N_PARAM elements each (vinegar then oil)
+    pub s: Vec<Vec<F16>>,
+}
+
+impl ParsedSignature {
+    // An all-zero s evaluates to zero under every public map, so it would verify whenever the
+    // target happens to be zero. Every verification entry point reaches evaluate_signature,
+    // which treats it as a signature that does not verify (false, never an error).
+    pub fn s_is_zero(&self) -> bool {
+        self.s.iter().flatten().all(|&x| x == F16::new(0))
+    }
+}
+
+// Splits a signature into salt and s without verifying anything about them
+pub fn parse_signature<P: MayoParams>(signature: &[u8]) -> Result<ParsedSignature, CryptoError> {
+    if signature.len() != P::SIG_BYTES {
+        return Err(CryptoError::InvalidEncodingLength);
+    }
+    let s_vector = Vector::decode_vec_at(P::K_PARAM * P::N_PARAM, signature, 0)
+        .map_err(|e| CryptoError::InvalidEncoding(e.into()))?;
+    Ok(ParsedSignature {
+        salt: signature[P::SIG_BYTES - P::SALT_BYTES..].to_vec(),
+        s: s_vector.elements().chunks(P::N_PARAM).map(|row| row.to_vec()).collect(),
+    })
+}
+
+// Diagnostic counterpart of verify_generic: entry i is true when equation i of P(s) matches
+// the target. A signature is valid exactly when all M_PARAM entries are true. Unlike
+// verification this is not constant time and must not be used to accept signatures.
+pub fn verify_equations<P: MayoParams>(public_key: &[u8], message: &[u8], signature: &[u8]) -> Result<Vec<bool>, CryptoError> {
+    if public_key.len() != P::CPK_BYTES {
+        return Err(CryptoError::InvalidKeyLength);
+    }
+    if signature.len() != P::SIG_BYTES {
+        return Err(CryptoError::InvalidEncodingLength);
+    }
     
-    // Decode signature
-    let total_sig_elements = P::K_PARAM * P::N_PARAM;
-    let mut s_elements = vec![0u8; total_sig_elements];
-    decode_elements(s_encoded, &mut s_elements);
+    let matrices = PMatrices::<P>::from_compact_pk(public_key)?;
+    let mut evaluation = Vec::with_capacity(P::M_PARAM);
+    let target = evaluate_signature::<P>(message, signature, &matrices, &matrices.p3, &mut evaluation)
+        .ok_or(CryptoError::VerificationError)?;
     
-    // Reconstruct S matrix
-    let mut s_matrix = Vec::new();
-    for i in 0..P::K_PARAM {
-        let mut row = vec![0u8; P::N_PARAM];
-        for j in 0..P::N_PARAM {
-            let idx = i * P::N_PARAM + j;
-            if idx < s_elements.len() {
-                row[j] = s_elements[idx];
-            }
-        }
-        s_matrix.push(row);
+    Ok(evaluation.iter().zip(&target).map(|(e, t)| e == t).collect())
+}
+
+// Human-readable breakdown of a verification: for every equation, the P1, P2 and P3
+// contributions to P*(s), their total, the target and whether they match. Built on
+// public_map_terms, so it cannot drift from the evaluation verify_generic performs.
+#[cfg(feature = "debug-dump")]
+pub fn dump_signing_state<P: MayoParams>(public_key: &[u8], message: &[u8], signature: &[u8]) -> String {
+    use std::fmt::Write;
+    
+    let mut out = String::new();
+    let _ = writeln!(out, "--- {} signing state ---", P::name());
+    if public_key.len() != P::CPK_BYTES && public_key.len() != P::EPK_BYTES {
+        let _ = writeln!(out, "public key has {} bytes, expected {} or {}", public_key.len(), P::CPK_BYTES, P::EPK_BYTES);
+        return out;
+    }
+    if signature.len() != P::SIG_BYTES {
+        let _ = writeln!(out, "signature has {} bytes, expected {}", signature.len(), P::SIG_BYTES);
+        return out;
     }
     
-    // Compute polynomial evaluation
-    let mayo_result = compute_mayo_polynomial::<P>(&s_matrix, &p1, &p2, &p3);
+    let matrices = if public_key.len() == P::EPK_BYTES {
+        decode_expanded_pk::<P>(public_key)
+    } else {
+        let _ = writeln!(out, "pk_seed: {}", hex::encode(&public_key[..P::PK_SEED_BYTES]));
+        PMatrices::from_compact_pk(public_key)
+    };
+    let matrices = match matrices {
+        Ok(matrices) => matrices,
+        Err(e) => {
+            let _ = writeln!(out, "could not expand public key: {}", e);
+            return out;
+        }
+    };
     
-    // NIST compliance: check exact match for ALL equations
-    let mut exact_matches = 0;
-    for i in 0..P::M_PARAM.min(mayo_result.len()).min(t.len()) {
-        if mayo_result[i].value() == t[i] {
-            exact_matches += 1;
+    let ParsedSignature { salt, s: s_rows } = match parse_signature::<P>(signature) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            let _ = writeln!(out, "could not decode s: {}", e);
+            return out;
         }
+    };
+    let _ = writeln!(out, "salt: {}", hex::encode(&salt));
+    let target = target_from_message::<P>(message, &salt);
+    
+    let [p1_part, p2_part, p3_part] = public_map_terms::<P>(&s_rows, &matrices, &matrices.p3);
+    let mut totals = Vec::with_capacity(P::M_PARAM);
+    for (eq, t) in target.iter().enumerate() {
+        let (p1_term, p2_term, p3_term) = (p1_part[eq], p2_part[eq], p3_part[eq]);
+        let total = p1_term + p2_term + p3_term;
+        let ok = total == *t;
+        totals.push(total);
+        let _ = writeln!(
+            out,
+            "eq {:3}: P1={:2} P2={:2} P3={:2} total={:2} target={:2} {}",
+            eq, p1_term.value(), p2_term.value(), p3_term.value(), total.value(), t.value(),
+            if ok { "ok" } else { "MISMATCH" }
+        );
+    }
+    let matches = count_exact_matches_packed(&totals, &target);
+    let _ = writeln!(out, "{}/{} equations match", matches, P::M_PARAM);
+    out
+}
+
+// The matrices a secret seed expands to, one "<name>: <hex>" line each for P1, P2, P3 and O.
+// Each hex string is the nibble encoding of the flat coefficients (low nibble first), so the
+// output can be diffed line by line against the same dump from the C implementation.
+// Contains the secret O: for debugging with throwaway seeds only.
+#[cfg(feature = "debug-dump")]
+pub fn dump_matrices_hex<P: MayoParams>(sk_seed: &[u8]) -> String {
+    use std::fmt::Write;
+    
+    let mut out = String::new();
+    if sk_seed.len() != P::SK_SEED_BYTES {
+        let _ = writeln!(out, "sk_seed has {} bytes, expected {}", sk_seed.len(), P::SK_SEED_BYTES);
+        return out;
     }
     
-    let is_valid = exact_matches == P::M_PARAM;
+    let expanded = Zeroizing::new(shake256_digest(sk_seed, P::PK_SEED_BYTES + P::O_BYTES));
+    let (p1, p2, p3) = match expand_matrices::<P>(&expanded[..P::PK_SEED_BYTES]) {
+        Ok(matrices) => matrices.into_parts(),
+        Err(e) => {
+            let _ = writeln!(out, "could not expand pk_seed: {}", e);
+            return out;
+        }
+    };
+    let o = match decode_elements_checked(&expanded[P::PK_SEED_BYTES..], (P::N_PARAM - P::O_PARAM) * P::O_PARAM) {
+        Ok(o) => o,
+        Err(e) => {
+            let _ = writeln!(out, "could not decode O: {}", e);
+            return out;
+        }
+    };
     
-    if is_valid {
-        println!("[MAYO_NIST] ✓ SIGNATURE VALID - Perfect NIST compliance: {}/{}", exact_matches, P::M_PARAM);
-    } else {
-        println!("[MAYO_NIST] ❌ SIGNATURE INVALID - Only {} matches out of {} required", exact_matches, P::M_PARAM);
+    for (name, elements) in [("P1", &p1), ("P2", &p2), ("P3", &p3), ("O", &o)] {
+        let _ = writeln!(out, "{}: {}", name, hex::encode(encode_f16_elements(elements)));
     }
-    
-    Ok(is_valid)
+    out
 }
 
-// Debug test function with corrected implementation
-pub fn test_basic_crypto_operations<P: MayoParams>() -> Result<(), CryptoError> {
-    println!("[DEBUG] Testing corrected MAYO crypto operations for {}", std::any::type_name::<P>());
-    
-    // Test key generation
+// Keygen, matrix expansion, the public map and a sign/verify round trip for P, for the unit
+// tests. Signing gets 16 attempts, which the standard parameter sets need only one of.
+#[cfg(test)]
+pub(crate) fn test_basic_crypto_operations<P: MayoParams>() -> Result<(), CryptoError> {
     let (secret_key, public_key) = generate_keypair_generic::<P>()?;
-    println!("[DEBUG] Generated keys: SK={} bytes, PK={} bytes", secret_key.len(), public_key.len());
     
-    // Test matrix expansion
     let expanded = shake256_digest(&secret_key, P::PK_SEED_BYTES + P::O_BYTES);
     let pk_seed = &expanded[..P::PK_SEED_BYTES];
-    let (p1, p2, p3) = expand_matrices::<P>(pk_seed)?;
-    println!("[DEBUG] Matrix expansion: P1={}, P2={}, P3={} coefficients", p1.len(), p2.len(), p3.len());
+    let (p1, p2, p3) = expand_matrices::<P>(pk_seed)?.into_parts();
     
-    // Test S*P*S^T computation with a simple test vector
-    let mut test_s_matrix = Vec::new();
-    for i in 0..P::K_PARAM {
-        let mut row = vec![0u8; P::N_PARAM];
-        for j in 0..P::N_PARAM {
-            row[j] = ((i + j) % 16) as u8; // Simple test pattern
+    // S*P*S^T on a simple test pattern
+    let mut test_s_matrix = vec![vec![0u8; P::N_PARAM]; P::K_PARAM];
+    for (i, row) in test_s_matrix.iter_mut().enumerate() {
+        for (j, x) in row.iter_mut().enumerate() {
+            *x = ((i + j) % 16) as u8;
         }
-        test_s_matrix.push(row);
+    }
+    if compute_sps::<P>(&test_s_matrix, &p1, &p2, &p3).len() != P::M_PARAM {
+        return Err(CryptoError::MatrixError);
     }
     
-    let sps_result = compute_sps::<P>(&test_s_matrix, &p1, &p2, &p3);
-    println!("[DEBUG] S*P*S^T computation: {} results", sps_result.len());
-    
-    // Test signing with limited attempts (quick test)
     let message = b"test message";
-    println!("[DEBUG] Attempting signing with 16 attempts...");
-    
-    let signing_result = sign_with_limited_attempts::<P>(&secret_key, message, 16);
-    match signing_result {
-        Ok(signature) => {
-            println!("[DEBUG] ✅ Signing succeeded! Signature: {} bytes", signature.len());
-            
-            // Test verification
-            match verify_generic::<P>(&public_key, message, &signature) {
-                Ok(true) => {
-                    println!("[DEBUG] ✅ Verification PASSED - Implementation is working correctly!");
-                    Ok(())
-                }
-                Ok(false) => {
-                    println!("[DEBUG] ❌ Verification FAILED - Polynomial evaluation mismatch");
-                    Err(CryptoError::VerificationError)
-                }
-                Err(e) => {
-                    println!("[DEBUG] ❌ Verification ERROR: {}", e);
-                    Err(e)
-                }
-            }
-        }
-        Err(_) => {
-            println!("[DEBUG] ⚠️  Signing failed in 16 attempts - this is expected for the current implementation");
-            println!("[DEBUG] ✅ Core algorithms (key generation, matrix expansion, S*P*S^T) are working correctly");
-            println!("[DEBUG] ✅ Implementation has correct mathematical structure");
-            Ok(())
-        }
+    let (signature, _) = sign_with_attempts::<P>(&secret_key, message, 16)?;
+    if verify_generic::<P>(&public_key, message, &signature)? {
+        Ok(())
+    } else {
+        Err(CryptoError::VerificationError)
     }
 }
 
-// Limited attempt signing for testing
-fn sign_with_limited_attempts<P: MayoParams>(secret_key: &[u8], message: &[u8], max_attempts: usize) -> Result<Vec<u8>, CryptoError> {
-    if secret_key.len() != P::SK_SEED_BYTES {
+// Signs, then re-derives the public key from csk and verifies the signature before releasing it,
+// so a fault injected during signing surfaces as SigningError instead of a bad signature.
+pub fn sign_checked<P: MayoParams>(secret_key: &[u8], message: &[u8]) -> Result<Vec<u8>, CryptoError> {
+    if secret_key.len() != P::CSK_BYTES {
         return Err(CryptoError::InvalidKeyLength);
     }
-    
-    // Hash message
-    let msg_hash = shake256_digest(message, P::DIGEST_BYTES);
-    
-    // Generate salt
-    let mut salt = vec![0u8; P::SALT_BYTES];
-    OsRng.fill_bytes(&mut salt);
-    
-    // Compute target t = H(msg_hash || salt)
-    let mut msg_salt = Vec::new();
-    msg_salt.extend_from_slice(&msg_hash);
-    msg_salt.extend_from_slice(&salt);
-    let t_bytes = shake256_digest(&msg_salt, (P::M_PARAM + 1) / 2);
-    let mut t = vec![0u8; P::M_PARAM];
-    decode_elements(&t_bytes, &mut t);
-    
-    // Expand secret key to get matrices
-    let expanded = shake256_digest(secret_key, P::PK_SEED_BYTES + P::O_BYTES);
-    let pk_seed = &expanded[..P::PK_SEED_BYTES];
-    
-    let (p1, p2, p3) = expand_matrices::<P>(pk_seed)?;
-    
-    // Try to find a signature with limited attempts
-    for attempt in 0..max_attempts {
-        // Generate k random vectors of length n as S matrix (k x n)
-        let mut s_matrix = Vec::new();
-        for _ in 0..P::K_PARAM {
-            let mut row = vec![0u8; P::N_PARAM];
-            for j in 0..P::N_PARAM {
-                row[j] = (OsRng.next_u32() as u8) & 0x0F;
-            }
-            s_matrix.push(row);
-        }
-        
-        // Compute S*P*S^T using the correct MAYO structure
-        let sps_result = compute_sps::<P>(&s_matrix, &p1, &p2, &p3);
-        
-        // Extract result
-        let mut evaluation = vec![0u8; P::M_PARAM];
-        for i in 0..P::M_PARAM.min(sps_result.len()) {
-            evaluation[i] = sps_result[i].value();
-        }
-        
-        // Check if it matches the target exactly
-        let mut matches = 0;
-        for i in 0..P::M_PARAM.min(evaluation.len()).min(t.len()) {
-            if evaluation[i] == t[i] {
-                matches += 1;
-            }
-        }
-        
-        if matches == P::M_PARAM {
-            // Encode the signature
-            let total_sig_elements = P::K_PARAM * P::N_PARAM;
-            let mut sig_elements = vec![0u8; total_sig_elements];
-            
-            for i in 0..P::K_PARAM {
-                for j in 0..P::N_PARAM {
-                    if i < s_matrix.len() && j < s_matrix[i].len() {
-                        sig_elements[i * P::N_PARAM + j] = s_matrix[i][j];
-                    }
-                }
-            }
-            
-            let sig_bytes_needed = (total_sig_elements + 1) / 2;
-            let mut sig_encoded = vec![0u8; sig_bytes_needed];
-            encode_elements(&sig_elements, &mut sig_encoded);
-            
-            let mut signature = Vec::with_capacity(P::SIG_BYTES);
-            signature.extend_from_slice(&sig_encoded);
-            signature.extend_from_slice(&salt);
-            signature.resize(P::SIG_BYTES, 0);
-            
-            return Ok(signature);
-        }
-        
-        if attempt == max_attempts - 1 {
-            println!("[DEBUG] Best match: {} out of {} equations", matches, P::M_PARAM);
+    let signature = sign_generic::<P>(secret_key, message)?;
+
+    let epk = expand_pk::<P>(&public_key_from_secret::<P>(secret_key)?)?;
+    match verify_generic::<P>(&epk, message, &signature) {
+        Ok(true) => Ok(signature),
+        _ => Err(CryptoError::SigningError),
+    }
+}
+
+// Known-answer-free startup check: keygen, sign a fixed message, verify it, and confirm
+// that signatures with one flipped bit in s or in the salt are rejected. Exercises the
+// signer, so it only passes in builds where capabilities().sign is true.
+pub fn mayo_self_test<P: MayoParams>() -> Result<(), CryptoError> {
+    const SELF_TEST_MESSAGE: &[u8] = b"MAYO self-test message";
+
+    let (secret_key, public_key) = generate_keypair_generic::<P>()?;
+    if secret_key.len() != P::CSK_BYTES || public_key.len() != P::CPK_BYTES {
+        return Err(CryptoError::KeyGenerationError);
+    }
+
+    let signature = sign_generic::<P>(&secret_key, SELF_TEST_MESSAGE)?;
+    if signature.len() != P::SIG_BYTES {
+        return Err(CryptoError::SigningError);
+    }
+    if !verify_generic::<P>(&public_key, SELF_TEST_MESSAGE, &signature)? {
+        return Err(CryptoError::VerificationError);
+    }
+
+    for position in [0, P::SIG_BYTES - 1] {
+        let mut tampered = signature.clone();
+        tampered[position] ^= 0x01;
+        if verify_generic::<P>(&public_key, SELF_TEST_MESSAGE, &tampered)? {
+            return Err(CryptoError::VerificationError);
         }
     }
-    
-    Err(CryptoError::SigningError)
+    Ok(())
 }
 
 // Wrapper functions for backward compatibility (MAYO-1)
+#[cfg(feature = "mayo1")]
 pub fn generate_keypair() -> Result<(Vec<u8>, Vec<u8>), CryptoError> {
     generate_keypair_generic::<Mayo1>()
 }
 
+#[cfg(feature = "mayo1")]
 pub fn sign(secret_key: &[u8], message: &[u8]) -> Result<Vec<u8>, CryptoError> {
     sign_generic::<Mayo1>(secret_key, message)
 }
 
+#[cfg(feature = "mayo1")]
 pub fn verify(public_key: &[u8], message: &[u8], signature: &[u8]) -> Result<bool, CryptoError> {
     verify_generic::<Mayo1>(public_key, message, signature)
 }
 
  
  
- 
\ No newline at end of file
+ 
+#[cfg(all(test, feature = "mayo1"))]
+mod tests {
+    use super::*;
+    use crate::test_support::{test_rng, SignMayo, TinyMayo};
+
+    fn f16_seq(len: usize) -> Vec<F16> {
+        (0..len).map(|i| F16::new((i * 7 + 3) as u8)).collect()
+    }
+
+    // Mayo1 with SHAKE256 expansion instead of AES-128-CTR
+    struct Mayo1Shake;
+
+    macro_rules! delegate_to_mayo1 {
+        ($($name:ident: $ty:ty),* $(,)?) => { $(const $name: $ty = Mayo1::$name;)* };
+    }
+
+    impl MayoParams for Mayo1Shake {
+        delegate_to_mayo1! {
+            M_PARAM: usize, N_PARAM: usize, O_PARAM: usize, K_PARAM: usize, KO_PARAM: usize, F_TAIL: &'static [u8],
+            P1_ELEMS_PER_MATRIX: usize, P2_ELEMS_PER_MATRIX: usize, P3_ELEMS_PER_MATRIX: usize,
+            SALT_BYTES: usize, DIGEST_BYTES: usize, SK_SEED_BYTES: usize, PK_SEED_BYTES: usize,
+            O_BYTES: usize, P1_BYTES: usize, P2_BYTES: usize, P3_BYTES: usize,
+            CSK_BYTES: usize, CPK_BYTES: usize, SIG_BYTES: usize,
+            R_BYTES: usize, V_BYTES: usize, O_ELTS: usize, L_BYTES: usize, ESK_BYTES: usize, EPK_BYTES: usize,
+            P1_MAT_ROWS: usize, P1_MAT_COLS: usize, P1_IS_TRIANGULAR: bool,
+            P2_MAT_ROWS: usize, P2_MAT_COLS: usize, P2_IS_TRIANGULAR: bool,
+            P3_MAT_ROWS: usize, P3_MAT_COLS: usize, P3_IS_TRIANGULAR: bool,
+            L_MAT_ROWS: usize, L_MAT_COLS: usize, L_IS_TRIANGULAR: bool,
+        }
+        type Prf = Shake256Prf;
+
+        fn name() -> &'static str { "MAYO-1 (SHAKE)" }
+        fn security_level() -> usize { 1 }
+    }
+
+    // MAYO-1 with a small message bound so the oversized path can be tested cheaply
+    struct Mayo1ShortMessages;
+
+    impl MayoParams for Mayo1ShortMessages {
+        delegate_to_mayo1! {
+            M_PARAM: usize, N_PARAM: usize, O_PARAM: usize, K_PARAM: usize, KO_PARAM: usize, F_TAIL: &'static [u8],
+            P1_ELEMS_PER_MATRIX: usize, P2_ELEMS_PER_MATRIX: usize, P3_ELEMS_PER_MATRIX: usize,
+            SALT_BYTES: usize, DIGEST_BYTES: usize, SK_SEED_BYTES: usize, PK_SEED_BYTES: usize,
+            O_BYTES: usize, P1_BYTES: usize, P2_BYTES: usize, P3_BYTES: usize,
+            CSK_BYTES: usize, CPK_BYTES: usize, SIG_BYTES: usize,
+            R_BYTES: usize, V_BYTES: usize, O_ELTS: usize, L_BYTES: usize, ESK_BYTES: usize, EPK_BYTES: usize,
+            P1_MAT_ROWS: usize, P1_MAT_COLS: usize, P1_IS_TRIANGULAR: bool,
+            P2_MAT_ROWS: usize, P2_MAT_COLS: usize, P2_IS_TRIANGULAR: bool,
+            P3_MAT_ROWS: usize, P3_MAT_COLS: usize, P3_IS_TRIANGULAR: bool,
+            L_MAT_ROWS: usize, L_MAT_COLS: usize, L_IS_TRIANGULAR: bool,
+        }
+        const MAX_MESSAGE_LEN: usize = 32;
+        type Prf = <Mayo1 as MayoParams>::Prf;
+
+        fn name() -> &'static str { "MAYO-1 (short messages)" }
+        fn security_level() -> usize { 1 }
+    }
+
+    #[test]
+    fn test_custom_params_keygen() {
+        assert_eq!(TinyMayo::name(), "TinyMayo");
+        assert_eq!(TinyMayo::P1_ELEMS_PER_MATRIX, 136);
+        assert_eq!(TinyMayo::CPK_BYTES, 16 + 10 * 16 / 2);
+
+        let (sk, pk) = generate_keypair_generic::<TinyMayo>().unwrap();
+        assert_eq!(sk.len(), TinyMayo::CSK_BYTES);
+        assert_eq!(pk.len(), TinyMayo::CPK_BYTES);
+
+        let (p1, p2, p3) = expand_matrices::<TinyMayo>(&pk[..TinyMayo::PK_SEED_BYTES]).unwrap().into_parts();
+        assert_eq!(p1.len(), TinyMayo::M_PARAM * TinyMayo::P1_ELEMS_PER_MATRIX);
+        assert_eq!(p2.len(), TinyMayo::M_PARAM * TinyMayo::P2_ELEMS_PER_MATRIX);
+        assert_eq!(p3.len(), TinyMayo::M_PARAM * TinyMayo::P3_ELEMS_PER_MATRIX);
+        assert_eq!(expand_pk::<TinyMayo>(&pk).unwrap().len(), TinyMayo::EPK_BYTES);
+    }
+
+    #[test]
+    fn test_shake256_prf_matches_shake256_digest() {
+        for len in [0, 1, 32, 200] {
+            assert_eq!(Shake256Prf::expand(b"seed", len), shake256_digest(b"seed", len));
+        }
+    }
+
+    #[test]
+    fn test_expand_prf_choice_changes_stream() {
+        let seed = [0x42u8; 16];
+        assert_ne!(
+            <Mayo1 as MayoParams>::Prf::expand(&seed, 64),
+            <Mayo1Shake as MayoParams>::Prf::expand(&seed, 64)
+        );
+
+        let (aes_p1, _, _) = expand_matrices::<Mayo1>(&seed).unwrap().into_parts();
+        let (shake_p1, _, _) = expand_matrices::<Mayo1Shake>(&seed).unwrap().into_parts();
+        assert_eq!(aes_p1.len(), shake_p1.len());
+        assert_ne!(aes_p1, shake_p1);
+
+        assert!(matches!(expand_matrices::<Mayo1>(&seed[..15]), Err(CryptoError::InvalidKeyLength)));
+    }
+
+    // Counts bytes allocated on the current thread so tests can check for reallocation
+    struct CountingAllocator;
+
+    thread_local! {
+        static ALLOCATED_BYTES: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+    }
+
+    fn count_allocation(size: usize) {
+        let _ = ALLOCATED_BYTES.try_with(|b| b.set(b.get() + size));
+    }
+
+    unsafe impl std::alloc::GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: std::alloc::Layout) -> *mut u8 {
+            count_allocation(layout.size());
+            std::alloc::System.alloc(layout)
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: std::alloc::Layout) {
+            std::alloc::System.dealloc(ptr, layout)
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: std::alloc::Layout, new_size: usize) -> *mut u8 {
+            count_allocation(new_size);
+            std::alloc::System.realloc(ptr, layout, new_size)
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    fn allocated_bytes() -> usize {
+        ALLOCATED_BYTES.with(|b| b.get())
+    }
+
+    #[test]
+    fn test_verify_with_scratch_matches_verify_generic() {
+        let (sk, pk) = generate_keypair_generic::<Mayo1>().unwrap();
+        let message = b"scratch verify";
+        let signature = sign_generic::<Mayo1>(&sk, message).unwrap();
+        let mut tampered = signature.clone();
+        tampered[0] ^= 1;
+        let mut scratch = VerifyScratch::<Mayo1>::new();
+
+        assert!(verify_generic::<Mayo1>(&pk, message, &signature).unwrap());
+        assert!(verify_with_scratch(&pk, message, &signature, &mut scratch).unwrap());
+        assert!(!verify_generic::<Mayo1>(&pk, message, &tampered).unwrap());
+        assert!(!verify_with_scratch(&pk, message, &tampered, &mut scratch).unwrap());
+
+        let buffers = (scratch.stream.as_ptr(), scratch.matrices.p1.as_ptr(), scratch.matrices.p2.as_ptr(), scratch.matrices.p3.as_ptr());
+        let before = allocated_bytes();
+        assert!(verify_with_scratch(&pk, message, &signature, &mut scratch).unwrap());
+        let allocated = allocated_bytes() - before;
+
+        // Only small per-call temporaries remain; the matrix buffers alone are ~290 KB
+        assert!(allocated < 16 * 1024, "verify_with_scratch allocated {} bytes", allocated);
+        assert_eq!(
+            buffers,
+            (scratch.stream.as_ptr(), scratch.matrices.p1.as_ptr(), scratch.matrices.p2.as_ptr(), scratch.matrices.p3.as_ptr())
+        );
+        assert_eq!(scratch.matrices.p1, expand_matrices::<Mayo1>(&pk[..Mayo1::PK_SEED_BYTES]).unwrap().as_slices().0);
+    }
+
+    #[test]
+    fn test_ct_eq_matches_naive_equality() {
+        let a = f16_seq(78);
+        let mut b = a.clone();
+        assert_eq!(ct_eq(&a, &b), a == b);
+        assert!(ct_eq(&a, &b));
+
+        for idx in [0, 39, 77] {
+            b[idx] = b[idx] + F16::new(1);
+            assert_eq!(ct_eq(&a, &b), a == b);
+            assert!(!ct_eq(&a, &b));
+            b[idx] = a[idx];
+        }
+
+        assert!(!ct_eq(&a, &a[..77]));
+        assert!(ct_eq(&[], &[]));
+    }
+
+    #[test]
+    fn test_public_key_from_secret_matches_keygen() {
+        let (sk, pk) = generate_keypair_generic::<Mayo1>().unwrap();
+        assert_eq!(public_key_from_secret::<Mayo1>(&sk).unwrap(), pk);
+
+        let seed: Vec<u8> = (0..Mayo1::SK_SEED_BYTES as u8).collect();
+        let (csk, cpk) = generate_keypair_from_seed::<Mayo1>(&seed).unwrap();
+        assert_eq!(csk, seed);
+        assert_eq!(public_key_from_secret::<Mayo1>(&csk).unwrap(), cpk);
+        assert_eq!(generate_keypair_from_seed::<Mayo1>(&seed).unwrap().1, cpk);
+
+        assert!(matches!(public_key_from_secret::<Mayo1>(&seed[1..]), Err(CryptoError::InvalidKeyLength)));
+    }
+
+    #[test]
+    fn test_sign_checked_rejects_bad_key_length() {
+        let sk = vec![0u8; Mayo1::CSK_BYTES - 1];
+        assert!(matches!(sign_checked::<Mayo1>(&sk, b"msg"), Err(CryptoError::InvalidKeyLength)));
+    }
+
+    #[test]
+    fn test_sign_checked_signatures_verify() {
+        let (sk, pk) = generate_keypair_generic::<Mayo1>().unwrap();
+        let message = b"fault check";
+        for _ in 0..3 {
+            let signature = sign_checked::<Mayo1>(&sk, message).unwrap();
+            assert_eq!(signature.len(), sign_generic::<Mayo1>(&sk, message).unwrap().len());
+            assert!(verify_generic::<Mayo1>(&pk, message, &signature).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_self_test_mayo1() {
+        mayo_self_test::<Mayo1>().unwrap();
+    }
+
+    #[test]
+    #[cfg(feature = "mayo2")]
+    fn test_self_test_mayo2() {
+        mayo_self_test::<crate::params::Mayo2>().unwrap();
+    }
+
+    #[test]
+    #[cfg(feature = "mayo3")]
+    fn test_self_test_mayo3() {
+        mayo_self_test::<crate::params::Mayo3>().unwrap();
+    }
+
+    #[test]
+    #[cfg(feature = "mayo5")]
+    fn test_self_test_mayo5() {
+        mayo_self_test::<crate::params::Mayo5>().unwrap();
+    }
+
+    #[test]
+    fn test_self_test_small_set() {
+        mayo_self_test::<SignMayo>().unwrap();
+    }
+
+    #[test]
+    fn test_tampered_signatures_rejected() {
+        let (sk, pk) = generate_keypair_from_seed::<SignMayo>(&[5u8; 24]).unwrap();
+        let signature = sign_generic::<SignMayo>(&sk, b"tamper").unwrap();
+        assert!(verify_generic::<SignMayo>(&pk, b"tamper", &signature).unwrap());
+
+        // Every bit of s and of the salt matters
+        for position in 0..SignMayo::SIG_BYTES {
+            for bit in [0x01u8, 0x10, 0x80] {
+                let mut tampered = signature.clone();
+                tampered[position] ^= bit;
+                assert!(!verify_generic::<SignMayo>(&pk, b"tamper", &tampered).unwrap(), "byte {} bit {:#x}", position, bit);
+            }
+        }
+        assert!(!verify_generic::<SignMayo>(&pk, b"tamperer", &signature).unwrap());
+    }
+
+    #[test]
+    fn test_symmetric_index_row_major_upper() {
+        // 3x3 upper triangle in row-major order: (0,0) (0,1) (0,2) (1,1) (1,2) (2,2)
+        let n = 3;
+        let mut expected = 0;
+        for i in 0..n {
+            for j in i..n {
+                assert_eq!(symmetric_index(i, j, n), expected);
+                assert_eq!(symmetric_index(j, i, n), expected);
+                expected += 1;
+            }
+        }
+    }
+
+    #[test]
+    fn test_p_coeff_symmetric() {
+        let n = 5;
+        let per_eq = n * (n + 1) / 2;
+        let p = f16_seq(3 * per_eq);
+        for eq in 0..3 {
+            for i in 0..n {
+                for j in 0..n {
+                    assert_eq!(p_coeff(&p, eq, i, j, n), p_coeff(&p, eq, j, i, n));
+                }
+            }
+        }
+        // Second equation starts right after the first equation's coefficients
+        assert_eq!(p_coeff(&p, 1, 0, 0, n), p[per_eq]);
+        assert_eq!(p_coeff(&p, 1, n - 1, n - 1, n), p[2 * per_eq - 1]);
+    }
+
+    #[test]
+    fn test_decode_elements_checked() {
+        let decoded = decode_elements_checked(&[0x21, 0x03], 3).unwrap();
+        assert_eq!(decoded, vec![F16::new(1), F16::new(2), F16::new(3)]);
+
+        let decoded = decode_elements_checked(&[0x21, 0x43], 4).unwrap();
+        assert_eq!(decoded, vec![F16::new(1), F16::new(2), F16::new(3), F16::new(4)]);
+    }
+
+    #[test]
+    fn test_decode_elements_checked_rejects_short_input() {
+        let count = Mayo1::O_ELTS;
+        let bytes = vec![0xAB; count.div_ceil(2) - 1];
+        assert!(matches!(
+            decode_elements_checked(&bytes, count),
+            Err(CryptoError::InvalidEncodingLength)
+        ));
+        // Extra trailing bytes are rejected as well
+        assert!(decode_elements_checked(&[0x21, 0x03, 0x00], 3).is_err());
+    }
+
+    #[test]
+    fn test_expand_pk_matches_expand_matrices() {
+        let (_sk, pk) = generate_keypair_generic::<Mayo1>().unwrap();
+        let epk = expand_pk::<Mayo1>(&pk).unwrap();
+        assert_eq!(epk.len(), Mayo1::EPK_BYTES);
+
+        // P1 and P2 come from pk_seed, P3 from the key
+        let (p1, p2, _) = expand_matrices::<Mayo1>(&pk[..Mayo1::PK_SEED_BYTES]).unwrap().into_parts();
+        let p3 = decode_elements_checked(&pk[Mayo1::PK_SEED_BYTES..], Mayo1::M_PARAM * Mayo1::P3_ELEMS_PER_MATRIX).unwrap();
+        let decoded = decode_expanded_pk::<Mayo1>(&epk).unwrap();
+        assert_eq!(decoded.as_slices(), (&p1[..], &p2[..], &p3[..]));
+
+        assert!(matches!(expand_pk::<Mayo1>(&pk[1..]), Err(CryptoError::InvalidKeyLength)));
+    }
+
+    #[test]
+    fn test_expanded_to_compact_pk_roundtrip() {
+        // expand_pk takes P3 from pk_seed, so a compact key carrying that same P3 round-trips
+        let pk_seed = [0x3Cu8; 16];
+        let (_, _, p3) = expand_matrices::<TinyMayo>(&pk_seed).unwrap().into_parts();
+        let mut cpk = pk_seed.to_vec();
+        cpk.extend_from_slice(&encode_f16_elements(&p3));
+        assert_eq!(cpk.len(), TinyMayo::CPK_BYTES);
+
+        let epk = compact_to_expanded_pk::<TinyMayo>(&cpk).unwrap();
+        assert_eq!(epk, expand_pk::<TinyMayo>(&cpk).unwrap());
+        assert_eq!(expanded_to_compact_pk::<TinyMayo>(&pk_seed, &epk).unwrap(), cpk);
+
+        assert!(matches!(
+            expanded_to_compact_pk::<TinyMayo>(&[0x3Du8; 16], &epk),
+            Err(CryptoError::InvalidEncoding(_))
+        ));
+        assert!(matches!(
+            expanded_to_compact_pk::<TinyMayo>(&pk_seed, &epk[1..]),
+            Err(CryptoError::InvalidKeyLength)
+        ));
+    }
+
+    // x^T P x for each equation, with P = [[P1, P2], [0, P3]] and P1, P3 upper triangular as
+    // stored. Written out from the spec rather than through the crate's evaluators.
+    fn spec_public_map<P: MayoParams>(x: &[F16], matrices: &PMatrices<P>) -> Vec<F16> {
+        let v = P::N_PARAM - P::O_PARAM;
+        let o = P::O_PARAM;
+        (0..P::M_PARAM)
+            .map(|eq| {
+                let mut sum = F16::new(0);
+                for i in 0..v {
+                    for j in i..v {
+                        sum = sum + matrices.p1(eq, i, j) * x[i] * x[j];
+                    }
+                    for j in 0..o {
+                        sum = sum + matrices.p2(eq, i, j) * x[i] * x[v + j];
+                    }
+                }
+                for i in 0..o {
+                    for j in i..o {
+                        sum = sum + matrices.p3(eq, i, j) * x[v + i] * x[v + j];
+                    }
+                }
+                sum
+            })
+            .collect()
+    }
+
+    // The public map vanishes on the oil space: for random r, x = [O·r ; r] must evaluate to
+    // zero in every equation, using P1 and P2 from pk_seed and P3 from the public key
+    fn test_oil_space_vanishes<P: MayoParams>() {
+        let mut rng = test_rng();
+        let sk_seed = vec![0x37u8; P::SK_SEED_BYTES];
+        let (_sk, pk) = generate_keypair_from_seed::<P>(&sk_seed).unwrap();
+
+        let expanded = shake256_digest(&sk_seed, P::PK_SEED_BYTES + P::O_BYTES);
+        let o = decode_oil_matrix::<P>(&expanded[P::PK_SEED_BYTES..]).unwrap();
+        let (p1, p2, _) = expand_matrices::<P>(&pk[..P::PK_SEED_BYTES]).unwrap().into_parts();
+        let p3 = decode_elements_checked(&pk[P::PK_SEED_BYTES..], P::M_PARAM * P::P3_ELEMS_PER_MATRIX).unwrap();
+        let matrices = PMatrices::<P>::new(p1, p2, p3).unwrap();
+
+        for _ in 0..4 {
+            let r = Vector::random(P::O_PARAM, &mut rng);
+            let x = o.multiply_vector(&r).unwrap().concat(&r);
+            let evaluation = spec_public_map::<P>(x.elements(), &matrices);
+            let nonzero: Vec<usize> = (0..P::M_PARAM).filter(|&eq| evaluation[eq] != F16::new(0)).collect();
+            assert!(nonzero.is_empty(), "{}: P(O·r, r) is nonzero in equations {:?}", P::name(), nonzero);
+        }
+
+        // So does the whipped map on k oil-space vectors: every u_{i,j} is zero
+        let s: Vec<Vec<F16>> = (0..P::K_PARAM)
+            .map(|_| {
+                let r = Vector::random(P::O_PARAM, &mut rng);
+                o.multiply_vector(&r).unwrap().concat(&r).elements().to_vec()
+            })
+            .collect();
+        assert!(evaluate_public_map::<P>(&s, &matrices).iter().all(|&x| x == F16::new(0)), "{}", P::name());
+    }
+
+    // P*(S) from the spec's definition: Σ E^ℓ u_{i,j} over the pairs in the spec's order, with
+    // u_{i,j} = P(s_i + s_j) - P(s_i) - P(s_j) off the diagonal, and E^ℓ applied as multiplying
+    // by z^ℓ and reducing modulo f(z) at the end
+    fn spec_whipped_map<P: MayoParams>(s: &[Vec<F16>], matrices: &PMatrices<P>) -> Vec<F16> {
+        let (m, k) = (P::M_PARAM, P::K_PARAM);
+        let mut y = vec![F16::new(0); m + k * (k + 1) / 2];
+        let mut ell = 0;
+        for i in 0..k {
+            for j in (i..k).rev() {
+                let u = if i == j {
+                    spec_public_map::<P>(&s[i], matrices)
+                } else {
+                    let sum: Vec<F16> = s[i].iter().zip(&s[j]).map(|(&a, &b)| a + b).collect();
+                    let (p_sum, p_i, p_j) = (
+                        spec_public_map::<P>(&sum, matrices),
+                        spec_public_map::<P>(&s[i], matrices),
+                        spec_public_map::<P>(&s[j], matrices),
+                    );
+                    (0..m).map(|eq| p_sum[eq] - p_i[eq] - p_j[eq]).collect()
+                };
+                for (eq, &c) in u.iter().enumerate() {
+                    y[eq + ell] = y[eq + ell] + c;
+                }
+                ell += 1;
+            }
+        }
+        crate::poly::reduce_by_tail(&mut y, m, P::F_TAIL);
+        y.truncate(m);
+        y
+    }
+
+    fn check_whipped_evaluation<P: MayoParams>() {
+        let mut rng = test_rng();
+        let matrices = PMatrices::<P>::from_seed(&vec![0x5Du8; P::PK_SEED_BYTES]).unwrap();
+        let s: Vec<Vec<F16>> = (0..P::K_PARAM)
+            .map(|_| Vector::random(P::N_PARAM, &mut rng).elements().to_vec())
+            .collect();
+        assert_eq!(evaluate_public_map::<P>(&s, &matrices), spec_whipped_map::<P>(&s, &matrices), "{}", P::name());
+    }
+
+    #[test]
+    fn test_evaluation_is_whipped_per_spec() {
+        check_whipped_evaluation::<TinyMayo>();
+        check_whipped_evaluation::<Mayo1>();
+    }
+
+    #[test]
+    fn test_oil_space_vanishes_tiny() {
+        test_oil_space_vanishes::<TinyMayo>();
+    }
+
+    #[test]
+    fn test_oil_space_vanishes_standard_sets() {
+        test_oil_space_vanishes::<Mayo1>();
+        #[cfg(feature = "mayo2")]
+        test_oil_space_vanishes::<crate::params::Mayo2>();
+        #[cfg(feature = "mayo3")]
+        test_oil_space_vanishes::<crate::params::Mayo3>();
+        #[cfg(feature = "mayo5")]
+        test_oil_space_vanishes::<crate::params::Mayo5>();
+    }
+
+    #[test]
+    fn test_open_message_round_trips_both_layouts() {
+        let (sk, pk) = generate_keypair_generic::<SignMayo>().unwrap();
+        let message = b"signed message layout";
+        let sm = sign_message::<SignMayo>(&sk, message).unwrap();
+        assert_eq!(sm.len(), SignMayo::SIG_BYTES + message.len());
+        assert_eq!(&sm[SignMayo::SIG_BYTES..], message);
+        assert_eq!(open_message::<SignMayo>(&pk, &sm).unwrap(), message);
+        assert_eq!(open_message_with_layout::<SignMayo>(&pk, &sm, SmLayout::SigThenMsg).unwrap(), message);
+
+        let (signature, _) = sm.split_at(SignMayo::SIG_BYTES);
+        let reordered = [&message[..], signature].concat();
+        assert_eq!(open_message_with_layout::<SignMayo>(&pk, &reordered, SmLayout::MsgThenSig).unwrap(), message);
+        // The same bytes read with the other layout do not verify
+        assert!(open_message_with_layout::<SignMayo>(&pk, &reordered, SmLayout::SigThenMsg).is_err());
+    }
+
+    #[test]
+    fn test_open_message_rejects_short_and_invalid_sm() {
+        let (_sk, pk) = generate_keypair_generic::<TinyMayo>().unwrap();
+        let short = vec![0x11u8; TinyMayo::SIG_BYTES - 1];
+        for layout in [SmLayout::SigThenMsg, SmLayout::MsgThenSig] {
+            assert!(matches!(
+                open_message_with_layout::<TinyMayo>(&pk, &short, layout),
+                Err(CryptoError::VerificationError)
+            ));
+            let filler = [vec![0x11u8; TinyMayo::SIG_BYTES], b"message".to_vec()].concat();
+            assert!(open_message_with_layout::<TinyMayo>(&pk, &filler, layout).is_err());
+        }
+    }
+
+    #[test]
+    fn test_compute_l_flat_matches_direct_sum() {
+        let mut rng = test_rng();
+        let (p1, p2, _p3) = expand_matrices::<TinyMayo>(&[3u8; 16]).unwrap().into_parts();
+        let v = TinyMayo::N_PARAM - TinyMayo::O_PARAM;
+        let o = TinyMayo::O_PARAM;
+        let o_matrix = Vector::random(v * o, &mut rng).elements().to_vec();
+
+        // L[eq][i][c] = P2[eq][i][c] + Σ_{j != i} P1sym[eq][i][j] * O[j][c]
+        let mut expected = p2.clone();
+        for eq in 0..TinyMayo::M_PARAM {
+            for i in 0..v {
+                for j in (0..v).filter(|&j| j != i) {
+                    for c in 0..o {
+                        let idx = eq * v * o + i * o + c;
+                        expected[idx] = expected[idx] + p_coeff(&p1, eq, i, j, v) * o_matrix[j * o + c];
+                    }
+                }
+            }
+        }
+        assert_eq!(compute_l_flat::<TinyMayo>(&p1, &p2, &o_matrix), expected);
+    }
+
+    #[test]
+    fn test_expand_sk_layout() {
+        let (sk, _pk) = generate_keypair_generic::<Mayo1>().unwrap();
+        let esk = expand_sk::<Mayo1>(&sk).unwrap();
+        assert_eq!(esk.len(), Mayo1::ESK_BYTES);
+        assert_eq!(&esk[..Mayo1::SK_SEED_BYTES], sk.as_slice());
+
+        let expanded = shake256_digest(&sk, Mayo1::PK_SEED_BYTES + Mayo1::O_BYTES);
+        let o_start = Mayo1::SK_SEED_BYTES;
+        assert_eq!(&esk[o_start..o_start + Mayo1::O_BYTES], &expanded[Mayo1::PK_SEED_BYTES..]);
+    }
+
+    #[test]
+    fn test_verify_accepts_expanded_public_key() {
+        let (_sk, pk) = generate_keypair_generic::<Mayo1>().unwrap();
+        let epk = expand_pk::<Mayo1>(&pk).unwrap();
+        let forged = vec![0x5Au8; Mayo1::SIG_BYTES];
+        let message = b"expanded key message";
+
+        let compact_result = verify_generic::<Mayo1>(&pk, message, &forged).unwrap();
+        let expanded_result = verify_generic::<Mayo1>(&epk, message, &forged).unwrap();
+        assert_eq!(compact_result, expanded_result);
+        assert!(!expanded_result);
+    }
+
+    #[test]
+    fn test_sign_rejects_unknown_secret_key_length() {
+        let sk = vec![0u8; Mayo1::SK_SEED_BYTES + 1];
+        assert!(matches!(sign_generic::<Mayo1>(&sk, b"msg"), Err(CryptoError::InvalidKeyLength)));
+    }
+
+    #[test]
+    fn test_sign_with_expanded_sk_verifies_under_both_pk_forms() {
+        let (sk, pk) = generate_keypair_generic::<Mayo1>().unwrap();
+        let esk = expand_sk::<Mayo1>(&sk).unwrap();
+        let epk = expand_pk::<Mayo1>(&pk).unwrap();
+        let message = b"expanded key message";
+
+        let signature = sign_generic::<Mayo1>(&esk, message).unwrap();
+        assert!(verify_generic::<Mayo1>(&pk, message, &signature).unwrap());
+        assert!(verify_generic::<Mayo1>(&epk, message, &signature).unwrap());
+    }
+
+    #[test]
+    fn test_sign_with_expanded_sk_uses_its_matrices() {
+        let (sk, _pk) = generate_keypair_from_seed::<SignMayo>(&[1u8; 24]).unwrap();
+        let (other_sk, other_pk) = generate_keypair_from_seed::<SignMayo>(&[2u8; 24]).unwrap();
+        let esk = expand_sk::<SignMayo>(&sk).unwrap();
+        let key = expand_signing_key::<SignMayo>(&esk).unwrap();
+        let from_seed = expand_signing_key::<SignMayo>(&sk).unwrap();
+        assert_eq!((&key.o, &key.p1, &key.l), (&from_seed.o, &from_seed.p1, &from_seed.l));
+
+        // sk's seed with other_sk's O, P1 and L signs for other_pk: nothing is re-derived
+        let (_, o, p1, l) = decode_expanded_sk::<SignMayo>(&expand_sk::<SignMayo>(&other_sk).unwrap()).unwrap();
+        let spliced = encode_expanded_sk::<SignMayo>(&sk, &o, &p1, &l).unwrap();
+        let signature = sign_generic::<SignMayo>(&spliced, b"spliced").unwrap();
+        assert!(verify_generic::<SignMayo>(&other_pk, b"spliced", &signature).unwrap());
+    }
+
+    #[test]
+    fn test_p_coeff_out_of_range_is_zero() {
+        let p = f16_seq(6); // one 3x3 triangular matrix
+        assert_eq!(p_coeff(&p, 1, 0, 0, 3), F16::new(0));
+    }
+
+    #[test]
+    fn test_message_length_bound() {
+        assert_eq!(Mayo1::MAX_MESSAGE_LEN, crate::params::DEFAULT_MAX_MESSAGE_LEN);
+
+        let (sk, pk) = generate_keypair_generic::<Mayo1ShortMessages>().unwrap();
+        let too_long = vec![0x5Au8; Mayo1ShortMessages::MAX_MESSAGE_LEN + 1];
+        assert!(matches!(
+            sign_generic::<Mayo1ShortMessages>(&sk, &too_long),
+            Err(CryptoError::MessageTooLong { len: 33, max: 32 })
+        ));
+
+        // Oversized messages never verify, and empty ones are simply checked as usual
+        let signature = vec![0x11u8; Mayo1ShortMessages::SIG_BYTES];
+        assert!(!verify_generic::<Mayo1ShortMessages>(&pk, &too_long, &signature).unwrap());
+        assert!(!verify_generic::<Mayo1ShortMessages>(&pk, b"", &signature).unwrap());
+    }
+
+    #[test]
+    fn test_sign_empty_message() {
+        let (sk, pk) = generate_keypair_generic::<Mayo1>().unwrap();
+        let signature = sign_generic::<Mayo1>(&sk, b"").unwrap();
+        assert!(verify_generic::<Mayo1>(&pk, b"", &signature).unwrap());
+    }
+
+    #[test]
+    fn test_verify_equations_agrees_with_verify() {
+        let (_sk, pk) = generate_keypair_generic::<Mayo1>().unwrap();
+        let mut signature = vec![0u8; Mayo1::SIG_BYTES];
+        test_rng().fill_bytes(&mut signature);
+
+        let matches = verify_equations::<Mayo1>(&pk, b"diagnostics", &signature).unwrap();
+        assert_eq!(matches.len(), Mayo1::M_PARAM);
+        let verified = verify_generic::<Mayo1>(&pk, b"diagnostics", &signature).unwrap();
+        assert_eq!(matches.iter().all(|&m| m), verified);
+
+        assert!(matches!(
+            verify_equations::<Mayo1>(&pk[1..], b"diagnostics", &signature),
+            Err(CryptoError::InvalidKeyLength)
+        ));
+        assert!(matches!(
+            verify_equations::<Mayo1>(&pk, b"diagnostics", &signature[1..]),
+            Err(CryptoError::InvalidEncodingLength)
+        ));
+    }
+
+    #[test]
+    fn test_verify_equations_all_true_for_valid_signature() {
+        let (sk, pk) = generate_keypair_generic::<Mayo1>().unwrap();
+        let signature = sign_generic::<Mayo1>(&sk, b"diagnostics").unwrap();
+        let matches = verify_equations::<Mayo1>(&pk, b"diagnostics", &signature).unwrap();
+        assert_eq!(matches, vec![true; Mayo1::M_PARAM]);
+    }
+
+    // z^e mod f(z) by schoolbook long division, independent of the incremental table
+    fn reduce_monomial(e: usize, m: usize, f_tail: &[u8]) -> Vec<F16> {
+        let mut poly = vec![F16::new(0); e + 1];
+        poly[e] = F16::new(1);
+        for deg in (m..=e).rev() {
+            let lead = poly[deg];
+            poly[deg] = F16::new(0);
+            for (i, &t) in f_tail.iter().enumerate() {
+                poly[deg - m + i] = poly[deg - m + i] + lead * F16::new(t);
+            }
+        }
+        poly.truncate(m);
+        poly
+    }
+
+    fn check_whipping_table<P: MayoParams>() {
+        let table = whipping_coefficients::<P>();
+        assert_eq!(table.len(), P::K_PARAM * (P::K_PARAM + 1) / 2);
+        // z^m itself reduces to the tail
+        let tail: Vec<u8> = table[0][..P::F_TAIL.len()].iter().map(|c| c.value()).collect();
+        assert_eq!(tail, P::F_TAIL, "{}", P::name());
+        assert!(table[0][P::F_TAIL.len()..].iter().all(|&c| c == F16::new(0)));
+        for (t, row) in table.iter().enumerate() {
+            assert_eq!(row, &reduce_monomial(P::M_PARAM + t, P::M_PARAM, P::F_TAIL), "{} z^(m+{})", P::name(), t);
+        }
+    }
+
+    #[test]
+    fn test_whipping_coefficients_reduce_monomials() {
+        check_whipping_table::<Mayo1>();
+        #[cfg(feature = "mayo2")]
+        check_whipping_table::<crate::params::Mayo2>();
+        #[cfg(feature = "mayo3")]
+        check_whipping_table::<crate::params::Mayo3>();
+        #[cfg(feature = "mayo5")]
+        check_whipping_table::<crate::params::Mayo5>();
+        // A custom set with its own tail
+        check_whipping_table::<TinyMayo>();
+    }
+
+    // Random signatures plus one of the wrong length, over a few messages
+    fn batch_items(count: usize) -> Vec<(Vec<u8>, Vec<u8>)> {
+        let mut rng = test_rng();
+        let mut items: Vec<(Vec<u8>, Vec<u8>)> = (0..count)
+            .map(|i| {
+                let mut signature = vec![0u8; Mayo1::SIG_BYTES];
+                rng.fill_bytes(&mut signature);
+                (format!("message {}", i).into_bytes(), signature)
+            })
+            .collect();
+        items.push((b"short".to_vec(), vec![0u8; Mayo1::SIG_BYTES - 1]));
+        items
+    }
+
+    #[test]
+    fn test_verify_batch_matches_verify_generic() {
+        let (_sk, pk) = generate_keypair_generic::<Mayo1>().unwrap();
+        let owned = batch_items(3);
+        let items: Vec<(&[u8], &[u8])> = owned.iter().map(|(m, s)| (m.as_slice(), s.as_slice())).collect();
+
+        let results = verify_batch::<Mayo1>(&pk, &items).unwrap();
+        assert_eq!(results.len(), items.len());
+        for (&(message, signature), &result) in items.iter().zip(&results) {
+            assert_eq!(result, verify_generic::<Mayo1>(&pk, message, signature).unwrap());
+        }
+        assert!(matches!(verify_batch::<Mayo1>(&pk[1..], &items), Err(CryptoError::InvalidKeyLength)));
+    }
+
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn test_verify_batch_par_matches_serial() {
+        let (_sk, pk) = generate_keypair_generic::<Mayo1>().unwrap();
+        let owned = batch_items(8);
+        let items: Vec<(&[u8], &[u8])> = owned.iter().map(|(m, s)| (m.as_slice(), s.as_slice())).collect();
+
+        assert_eq!(
+            verify_batch_par::<Mayo1>(&pk, &items).unwrap(),
+            verify_batch::<Mayo1>(&pk, &items).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_target_from_message() {
+        let salt = [0x11u8; 24];
+        let t = target_from_message::<Mayo1>(b"target", &salt);
+        assert_eq!(t.len(), Mayo1::M_PARAM);
+        assert!(t.iter().all(|x| x.value() < 16));
+        assert_eq!(t, target_from_message::<Mayo1>(b"target", &salt));
+        assert_ne!(t, target_from_message::<Mayo1>(b"target", &[0x12u8; 24]));
+        assert_ne!(t, target_from_message::<Mayo1>(b"other", &salt));
+
+        // Matches the nibble decoding of H(H(msg) || salt)
+        let mut msg_salt = hash_message::<Mayo1>(b"target");
+        msg_salt.extend_from_slice(&salt);
+        let t_bytes = shake256_digest(&msg_salt, Mayo1::M_PARAM / 2);
+        assert_eq!(t[0].value(), t_bytes[0] & 0x0F);
+        assert_eq!(t[1].value(), t_bytes[0] >> 4);
+    }
+
+    crate::mayo_params! { name: OddMayo, n: 20, m: 15, o: 4, k: 3, f_tail: [1, 0, 1, 8] }
+
+    #[test]
+    fn test_validate_public_key() {
+        let (_, pk) = generate_keypair_generic::<TinyMayo>().unwrap();
+        assert!(validate_public_key::<TinyMayo>(&pk).is_ok());
+        assert!(matches!(
+            validate_public_key::<TinyMayo>(&pk[..pk.len() - 1]),
+            Err(CryptoError::InvalidKeyLength)
+        ));
+    }
+
+    // 3 equations of 3 P3 elements each: the packed P3 ends in a padding nibble
+    crate::mayo_params! { name: OddP3Mayo, n: 6, m: 3, o: 2, k: 2, f_tail: [1, 1] }
+
+    #[test]
+    fn test_validate_public_key_rejects_padding() {
+        let (_, mut pk) = generate_keypair_generic::<OddP3Mayo>().unwrap();
+        assert_eq!(pk.len(), 16 + 5);
+        assert!(validate_public_key::<OddP3Mayo>(&pk).is_ok());
+        *pk.last_mut().unwrap() |= 0x10;
+        assert!(matches!(validate_public_key::<OddP3Mayo>(&pk), Err(CryptoError::InvalidEncoding(_))));
+    }
+
+    // v = 2, m = 4, k = 2 and f(z) = z^4 + z + 2, small enough to work by hand
+    crate::mayo_params! { name: RhsMayo, n: 3, m: 4, o: 1, k: 2, f_tail: [2, 1, 0, 0] }
+
+    #[test]
+    fn test_compute_rhs_hand_example() {
+        // Equation 0: P1 = [[0, 1], [., 0]]; equation 3: P1 = [[1, 0], [., 0]]; others zero
+        let mut p1 = vec![F16::new(0); 4 * 3];
+        p1[1] = F16::new(1);
+        p1[3 * 3] = F16::new(1);
+        let matrices = PMatrices::<RhsMayo>::new(p1, vec![F16::new(0); 4 * 2], vec![F16::new(0); 4]).unwrap();
+        let vinegar = vec![vec![F16::new(1), F16::new(0)], vec![F16::new(0), F16::new(1)]];
+        let target: Vec<F16> = [0, 0, 5, 0].into_iter().map(F16::new).collect();
+
+        // ℓ = 0, pair (0, 1): the off-diagonal P1 entry gives v0[0]*v1[1] = 1 in equation 0,
+        // which survives at degree 0 rather than cancelling.
+        // ℓ = 1, pair (0, 0): the diagonal entry of equation 3 gives v0[0]^2 = 1 at degree 3 + 1,
+        // and z^4 = z + 2 folds it to 2 + z.
+        // ℓ = 2, pair (1, 1): both entries miss v1, so nothing.
+        let y = compute_rhs::<RhsMayo>(&target, &vinegar, matrices.as_slices().0, matrices.whipping());
+        assert_eq!(y.iter().map(|x| x.value()).collect::<Vec<_>>(), vec![1 ^ 2, 1, 5, 0]);
+    }
+
+    #[test]
+    fn test_assemble_s() {
+        let mut rng = test_rng();
+        let v = TinyMayo::N_PARAM - TinyMayo::O_PARAM;
+        let vinegar: Vec<Vector> = (0..TinyMayo::K_PARAM).map(|_| Vector::random(v, &mut rng)).collect();
+        let oil = Vector::random(TinyMayo::K_PARAM * TinyMayo::O_PARAM, &mut rng);
+
+        let s = assemble_s::<TinyMayo>(&vinegar, &oil);
+        assert_eq!(s.len(), TinyMayo::K_PARAM);
+        for (i, row) in s.iter().enumerate() {
+            assert_eq!(row.len(), TinyMayo::N_PARAM);
+            assert_eq!(&row[..v], vinegar[i].elements());
+            let block = &oil.elements()[i * TinyMayo::O_PARAM..(i + 1) * TinyMayo::O_PARAM];
+            assert_eq!(&row[v..], block);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "oil solution")]
+    fn test_assemble_s_rejects_short_oil_solution() {
+        let v = TinyMayo::N_PARAM - TinyMayo::O_PARAM;
+        let vinegar = vec![Vector::new(vec![F16::new(0); v]); TinyMayo::K_PARAM];
+        assemble_s::<TinyMayo>(&vinegar, &Vector::new(vec![F16::new(0); TinyMayo::O_PARAM]));
+    }
+
+    #[test]
+    fn test_vinegar_constant_part() {
+        let mut rng = test_rng();
+        let matrices = PMatrices::<RhsMayo>::new(
+            Vector::random(4 * 3, &mut rng).elements().to_vec(),
+            Vector::random(4 * 2, &mut rng).elements().to_vec(),
+            Vector::random(4, &mut rng).elements().to_vec(),
+        )
+        .unwrap();
+        let (p1, whipping) = (matrices.as_slices().0, matrices.whipping());
+
+        let zero = vec![vec![F16::new(0); 2]; 2];
+        assert_eq!(vinegar_constant_part::<RhsMayo>(&zero, p1, whipping), vec![F16::new(0); 4]);
+
+        // compute_rhs subtracts exactly this constant from the target
+        let vinegar = vec![vec![F16::new(3), F16::new(7)], vec![F16::new(9), F16::new(1)]];
+        let target: Vec<F16> = [4, 0, 11, 2].into_iter().map(F16::new).collect();
+        let constant = vinegar_constant_part::<RhsMayo>(&vinegar, p1, whipping);
+        let expected: Vec<F16> = target.iter().zip(&constant).map(|(&t, &c)| t - c).collect();
+        assert_eq!(compute_rhs::<RhsMayo>(&target, &vinegar, p1, whipping), expected);
+    }
+
+    // The constant part is the whipped public map at s_i = (v_i, 0)
+    #[test]
+    fn test_vinegar_constant_part_matches_public_map() {
+        let mut rng = test_rng();
+        let v = TinyMayo::N_PARAM - TinyMayo::O_PARAM;
+        let matrices = PMatrices::<TinyMayo>::from_seed(&[6u8; 16]).unwrap();
+        let vinegar: Vec<Vec<F16>> = (0..TinyMayo::K_PARAM).map(|_| Vector::random(v, &mut rng).elements().to_vec()).collect();
+        let s: Vec<Vec<F16>> = vinegar
+            .iter()
+            .map(|x| x.iter().copied().chain(std::iter::repeat_n(F16::new(0), TinyMayo::O_PARAM)).collect())
+            .collect();
+        assert_eq!(
+            vinegar_constant_part::<TinyMayo>(&vinegar, matrices.as_slices().0, matrices.whipping()),
+            evaluate_public_map::<TinyMayo>(&s, &matrices)
+        );
+    }
+
+    #[test]
+    fn test_hash_message_length_tracks_params() {
+        fn check<P: MayoParams>() {
+            let digest = hash_message::<P>(b"digest");
+            assert_eq!(digest.len(), P::DIGEST_BYTES, "{}", P::name());
+            assert_eq!(digest, shake256_digest(b"digest", P::DIGEST_BYTES));
+        }
+        check::<Mayo1>();
+        #[cfg(feature = "mayo2")]
+        check::<crate::params::Mayo2>();
+        #[cfg(feature = "mayo3")]
+        check::<crate::params::Mayo3>();
+        #[cfg(feature = "mayo5")]
+        check::<crate::params::Mayo5>();
+
+        crate::mayo_params! {
+            name: WideDigest, n: 20, m: 16, o: 4, k: 3, f_tail: [1, 0, 1, 8],
+            salt_bytes: 24, digest_bytes: 48, sk_seed_bytes: 24, pk_seed_bytes: 16
+        }
+        check::<WideDigest>();
+    }
+
+    #[test]
+    fn test_all_zero_signature_rejected() {
+        let (_, pk) = generate_keypair_from_seed::<TinyMayo>(&[8u8; 24]).unwrap();
+        let zero = vec![0u8; TinyMayo::SIG_BYTES];
+        // A zero s with a nonzero salt is rejected too
+        let mut zero_s = zero.clone();
+        zero_s[TinyMayo::SIG_BYTES - 1] = 0xAA;
+
+        // Every verifier answers false rather than an error
+        let mut scratch = VerifyScratch::<TinyMayo>::new();
+        let typed_pk = crate::keys::PublicKey::<TinyMayo>::from_bytes(&pk).unwrap();
+        let expanded = crate::keys::ExpandedPublicKey::from_compact(&typed_pk).unwrap();
+        for signature in [&zero, &zero_s] {
+            assert!(!verify_generic::<TinyMayo>(&pk, b"", signature).unwrap());
+            assert!(!verify_with_scratch::<TinyMayo>(&pk, b"", signature, &mut scratch).unwrap());
+            let typed = crate::keys::Signature::<TinyMayo>::from_bytes(signature).unwrap();
+            assert!(!expanded.verify(b"", &typed).unwrap());
+            assert!(!expanded.verify_with_p3(b"", &typed, expanded.p3()).unwrap());
+        }
+        assert_eq!(verify_batch::<TinyMayo>(&pk, &[(b"", &zero), (b"", &zero_s)]).unwrap(), vec![false, false]);
+        #[cfg(feature = "rayon")]
+        assert_eq!(verify_batch_par::<TinyMayo>(&pk, &[(b"", &zero), (b"", &zero_s)]).unwrap(), vec![false, false]);
+        assert!(parse_signature::<TinyMayo>(&zero).unwrap().s_is_zero());
+    }
+
+    #[test]
+    fn test_sign_chunk_matches_sign_with_salt() {
+        let (sk, pk) = generate_keypair_from_seed::<SignMayo>(&[4u8; 24]).unwrap();
+        let salt = [9u8; SignMayo::SALT_BYTES];
+        let mut state_bytes = 0u32.to_le_bytes().to_vec();
+        state_bytes.extend_from_slice(&salt);
+        let mut state = SigningState::from_bytes::<SignMayo>(&state_bytes).unwrap();
+        let mut calls = 0;
+        let signature = loop {
+            // Round-trip the state through bytes as a WASM caller would
+            let bytes = state.to_bytes();
+            assert_eq!(bytes.len(), 4 + SignMayo::SALT_BYTES);
+            state = SigningState::from_bytes::<SignMayo>(&bytes).unwrap();
+            calls += 1;
+            // One attempt per chunk, so a signature that takes several attempts spans chunks
+            match sign_chunk::<SignMayo>(&sk, b"chunked", &mut state, 1).unwrap() {
+                Some(signature) => break signature,
+                None => assert_eq!(state.attempts_done(), calls),
+            }
+        };
+        assert!(verify_generic::<SignMayo>(&pk, b"chunked", &signature).unwrap());
+        assert_eq!(signature, sign_with_salt::<SignMayo>(&sk, b"chunked", &salt).unwrap());
+    }
+
+    #[test]
+    fn test_sign_chunk_stops_at_budget() {
+        let (sk, _) = generate_keypair_from_seed::<SignMayo>(&[4u8; 24]).unwrap();
+        let mut state_bytes = (SIGN_ATTEMPTS as u32).to_le_bytes().to_vec();
+        state_bytes.extend_from_slice(&[9u8; SignMayo::SALT_BYTES]);
+        let mut state = SigningState::from_bytes::<SignMayo>(&state_bytes).unwrap();
+        assert!(matches!(sign_chunk::<SignMayo>(&sk, b"chunked", &mut state, 1), Err(CryptoError::SigningError)));
+        assert!(SigningState::from_bytes::<SignMayo>(&[0u8; 5]).is_err());
+    }
+
+    #[test]
+    fn test_compute_target_odd_m() {
+        let digest = hash_message::<OddMayo>(b"odd");
+        let salt = [0x22u8; 24];
+        let t = compute_target::<OddMayo>(&digest, &salt);
+        assert_eq!(t.len(), 15);
+        assert_eq!(t, target_from_message::<OddMayo>(b"odd", &salt));
+
+        // 8 bytes are drawn; the last element is the low nibble of the eighth
+        let mut msg_salt = digest.clone();
+        msg_salt.extend_from_slice(&salt);
+        let t_bytes = shake256_digest(&msg_salt, 8);
+        assert_eq!(t[14].value(), t_bytes[7] & 0x0F);
+        assert_eq!(t[13].value(), t_bytes[6] >> 4);
+    }
+
+    #[test]
+    fn test_evaluate_public_map_matches_compute_mayo_polynomial() {
+        let matrices = PMatrices::<TinyMayo>::from_seed(&[5u8; 16]).unwrap();
+        let s: Vec<Vec<F16>> = (0..TinyMayo::K_PARAM)
+            .map(|i| (0..TinyMayo::N_PARAM).map(|j| F16::new(((3 * i + 7 * j) % 16) as u8)).collect())
+            .collect();
+        let s_bytes: Vec<Vec<u8>> = s.iter().map(|row| row.iter().map(|x| x.value()).collect()).collect();
+
+        let evaluation = evaluate_public_map::<TinyMayo>(&s, &matrices);
+        assert_eq!(evaluation.len(), TinyMayo::M_PARAM);
+        assert_eq!(evaluation, compute_mayo_polynomial::<TinyMayo>(&s_bytes, &matrices));
+
+        // The map is homogeneous quadratic, so S = 0 evaluates to 0
+        let zero = vec![vec![F16::new(0); TinyMayo::N_PARAM]; TinyMayo::K_PARAM];
+        assert!(evaluate_public_map::<TinyMayo>(&zero, &matrices).iter().all(|x| x.value() == 0));
+    }
+
+    #[test]
+    #[cfg(feature = "debug-dump")]
+    fn test_dump_signing_state() {
+        let (_sk, pk) = generate_keypair_generic::<TinyMayo>().unwrap();
+        let mut signature = vec![0u8; TinyMayo::SIG_BYTES];
+        test_rng().fill_bytes(&mut signature);
+
+        let dump = dump_signing_state::<TinyMayo>(&pk, b"dump", &signature);
+        assert_eq!(dump.lines().filter(|l| l.starts_with("eq ")).count(), TinyMayo::M_PARAM);
+        let matches = verify_equations::<TinyMayo>(&pk, b"dump", &signature).unwrap();
+        let summary = format!("{}/{} equations match", matches.iter().filter(|&&m| m).count(), TinyMayo::M_PARAM);
+        assert!(dump.ends_with(&format!("{}\n", summary)));
+
+        assert!(dump_signing_state::<TinyMayo>(&pk[1..], b"dump", &signature).contains("public key has"));
+    }
+
+    #[test]
+    fn test_sample_solution_uniform_varies_free_variables() {
+        let mut rng = test_rng();
+        let (m, ko) = (TinyMayo::M_PARAM, TinyMayo::K_PARAM * TinyMayo::O_PARAM);
+        // Repeating column ko-3 in the last two columns leaves those two variables free
+        let random = Matrix::random(m, ko, &mut rng);
+        let mut a = random.clone();
+        for r in 0..m {
+            let repeated = random.get(r, ko - 3).unwrap();
+            a.set(r, ko - 2, repeated).unwrap();
+            a.set(r, ko - 1, repeated).unwrap();
+        }
+        let y = a.multiply_vector(&Vector::random(ko, &mut rng)).unwrap();
+
+        let mut seen = [[false; 16]; 2];
+        for _ in 0..400 {
+            let x = sample_solution_uniform::<TinyMayo, _>(&a, &y, &mut rng).expect("system is consistent");
+            assert_eq!(a.multiply_vector(&x).unwrap(), y);
+            for (free, seen) in [ko - 2, ko - 1].into_iter().zip(seen.iter_mut()) {
+                seen[x.elements()[free].value() as usize] = true;
+            }
+        }
+        // With the free variables fixed to zero each position would only ever show one value
+        assert!(seen.iter().all(|values| values.iter().all(|&v| v)), "free variables missed values: {:?}", seen);
+    }
+
+    #[test]
+    fn test_sample_solution_uniform_inconsistent_system() {
+        let mut rng = test_rng();
+        let (m, ko) = (TinyMayo::M_PARAM, TinyMayo::K_PARAM * TinyMayo::O_PARAM);
+        let a = Matrix::zero(m, ko);
+        let mut y = vec![F16::new(0); m];
+        y[0] = F16::new(1);
+        assert!(sample_solution_uniform::<TinyMayo, _>(&a, &Vector::new(y), &mut rng).is_none());
+    }
+
+    // For s_i = (v_i + O x_i, x_i), P*(s) is affine in x: vinegar_constant_part plus A x
+    fn check_oil_system<P: MayoParams>() {
+        let mut rng = test_rng();
+        let (sk, pk) = generate_keypair_from_seed::<P>(&vec![0x29u8; P::SK_SEED_BYTES]).unwrap();
+        let key = expand_signing_key::<P>(&sk).unwrap();
+        let matrices = PMatrices::<P>::from_compact_pk(&pk).unwrap();
+        let v = P::N_PARAM - P::O_PARAM;
+
+        let vinegar: Vec<Vector> = (0..P::K_PARAM).map(|_| Vector::random(v, &mut rng)).collect();
+        let vinegar_vars: Vec<Vec<F16>> = vinegar.iter().map(|x| x.elements().to_vec()).collect();
+        let a = oil_system::<P>(&vinegar_vars, &key.l, &key.whipping);
+        assert_eq!((a.rows(), a.cols()), (P::M_PARAM, P::K_PARAM * P::O_PARAM));
+
+        let x = Vector::random(P::K_PARAM * P::O_PARAM, &mut rng);
+        let shifted: Vec<Vector> = vinegar
+            .iter()
+            .zip(x.elements().chunks_exact(P::O_PARAM))
+            .map(|(v_i, x_i)| v_i + &key.o.multiply_vector(&Vector::new(x_i.to_vec())).unwrap())
+            .collect();
+        let s = assemble_s::<P>(&shifted, &x);
+
+        let constant = vinegar_constant_part::<P>(&vinegar_vars, &key.p1, &key.whipping);
+        let linear = a.multiply_vector(&x).unwrap();
+        let expected: Vec<F16> = constant.iter().zip(linear.elements()).map(|(&c, &l)| c + l).collect();
+        assert_eq!(evaluate_public_map::<P>(&s, &matrices), expected, "{}", P::name());
+    }
+
+    #[test]
+    fn test_oil_system_linearizes_public_map() {
+        check_oil_system::<TinyMayo>();
+        check_oil_system::<SignMayo>();
+        check_oil_system::<Mayo1>();
+    }
+
+    #[test]
+    fn test_sample_solution_uniform_wrong_dimensions() {
+        let mut rng = test_rng();
+        let (m, ko) = (TinyMayo::M_PARAM, TinyMayo::K_PARAM * TinyMayo::O_PARAM);
+        let y = Vector::new(vec![F16::new(0); m]);
+        assert!(sample_solution_uniform::<TinyMayo, _>(&Matrix::zero(m, ko + 1), &y, &mut rng).is_none());
+        assert!(sample_solution_uniform::<TinyMayo, _>(&Matrix::zero(m - 1, ko), &y, &mut rng).is_none());
+        let short = Vector::new(vec![F16::new(0); m - 1]);
+        assert!(sample_solution_uniform::<TinyMayo, _>(&Matrix::zero(m, ko), &short, &mut rng).is_none());
+    }
+
+    #[test]
+    fn test_lu_solve_solves_system() {
+        let mut rng = test_rng();
+        let mut solved = 0;
+        while solved < 5 {
+            let a = Matrix::random(8, 8, &mut rng);
+            let b = Vector::random(8, &mut rng);
+            let (l, u, perm) = match a.lu_decompose() {
+                Ok(factors) => factors,
+                Err(_) => continue, // singular draw
+            };
+            let x = Matrix::lu_solve(&l, &u, &perm, &b);
+            assert_eq!(a.multiply_vector(&x).unwrap(), b);
+            solved += 1;
+        }
+    }
+
+    #[test]
+    fn test_is_keypair() {
+        let (sk, pk) = generate_keypair_generic::<Mayo1>().unwrap();
+        let (other_sk, other_pk) = generate_keypair_generic::<Mayo1>().unwrap();
+        assert!(is_keypair::<Mayo1>(&sk, &pk).unwrap());
+        assert!(is_keypair::<Mayo1>(&other_sk, &other_pk).unwrap());
+        assert!(!is_keypair::<Mayo1>(&sk, &other_pk).unwrap());
+        assert!(!is_keypair::<Mayo1>(&other_sk, &pk).unwrap());
+
+        assert!(matches!(is_keypair::<Mayo1>(&sk, &pk[1..]), Err(CryptoError::InvalidKeyLength)));
+        assert!(matches!(is_keypair::<Mayo1>(&sk[1..], &pk), Err(CryptoError::InvalidKeyLength)));
+    }
+
+    #[test]
+    fn test_sign_with_progress_reports_attempts() {
+        let (sk, _pk) = generate_keypair_generic::<TinyMayo>().unwrap();
+        let mut attempts = Vec::new();
+        let result = sign_with_progress::<TinyMayo>(&sk, b"progress", |attempt| attempts.push(attempt));
+
+        // Attempts are reported in order from 1, up to the full budget on failure
+        assert!(!attempts.is_empty());
+        assert!(attempts.iter().enumerate().all(|(i, &a)| a == i + 1));
+        if result.is_err() {
+            assert_eq!(attempts.len(), 256);
+        }
+    }
+
+    #[test]
+    fn test_sk_seed_buffer_is_zeroizing() {
+        // Type-checked: the seed keygen draws must be a Zeroizing wrapper
+        let sk_seed: Zeroizing<Vec<u8>> = random_sk_seed::<Mayo1>();
+        assert_eq!(sk_seed.len(), Mayo1::SK_SEED_BYTES);
+    }
+
+    #[test]
+    fn test_error_source() {
+        let io = CryptoError::from(std::io::Error::new(std::io::ErrorKind::UnexpectedEof, "short read"));
+        assert_eq!(io.to_string(), "I/O error");
+        assert_eq!(io.source().unwrap().to_string(), "short read");
+
+        assert!(CryptoError::InvalidKeyLength.source().is_none());
+    }
+
+    #[test]
+    fn test_pmatrices_accessors_match_flat_layout() {
+        let matrices = expand_matrices::<TinyMayo>(&[9u8; 16]).unwrap();
+        let (p1, p2, p3) = matrices.as_slices();
+        let v = TinyMayo::N_PARAM - TinyMayo::O_PARAM;
+        let o = TinyMayo::O_PARAM;
+        let (p1_per_eq, p3_per_eq) = (v * (v + 1) / 2, o * (o + 1) / 2);
+
+        for eq in 0..TinyMayo::M_PARAM {
+            for i in 0..v {
+                for j in i..v {
+                    let expected = p1[eq * p1_per_eq + symmetric_index(i, j, v)];
+                    assert_eq!(matrices.p1(eq, i, j), expected);
+                    assert_eq!(matrices.p1(eq, j, i), expected);
+                }
+                for j in 0..o {
+                    assert_eq!(matrices.p2(eq, i, j), p2[eq * v * o + i * o + j]);
+                }
+            }
+            for i in 0..o {
+                for j in i..o {
+                    let expected = p3[eq * p3_per_eq + symmetric_index(i, j, o)];
+                    assert_eq!(matrices.p3(eq, i, j), expected);
+                    assert_eq!(matrices.p3(eq, j, i), expected);
+                }
+            }
+        }
+        let (q1, q2, q3) = PMatrices::<TinyMayo>::from_seed(&[9u8; 16]).unwrap().into_parts();
+        assert_eq!((q1.as_slice(), q2.as_slice(), q3.as_slice()), (p1, p2, p3));
+    }
+
+    #[test]
+    fn test_pmatrices_rejects_wrong_lengths() {
+        let (p1, p2, mut p3) = expand_matrices::<TinyMayo>(&[9u8; 16]).unwrap().into_parts();
+        p3.pop();
+        assert!(matches!(PMatrices::<TinyMayo>::new(p1, p2, p3), Err(CryptoError::MatrixError)));
+    }
+
+    #[test]
+    #[should_panic(expected = "P2 index")]
+    fn test_pmatrices_p2_rejects_oil_index_out_of_range() {
+        let matrices = PMatrices::<TinyMayo>::from_seed(&[9u8; 16]).unwrap();
+        // (0, 0, o) would land inside the flat data, on P2[0][1][0]
+        let _ = matrices.p2(0, 0, TinyMayo::O_PARAM);
+    }
+
+    #[test]
+    fn test_validate_expansion() {
+        let (p1, p2, mut p3) = expand_matrices::<TinyMayo>(&[4u8; 16]).unwrap().into_parts();
+        assert!(validate_expansion::<TinyMayo>(&p1, &p2, &p3).is_ok());
+
+        p3.push(F16::new(1));
+        assert!(matches!(validate_expansion::<TinyMayo>(&p1, &p2, &p3), Err(CryptoError::MatrixError)));
+        assert!(matches!(validate_expansion::<TinyMayo>(&p1, &p2[1..], &p3[1..]), Err(CryptoError::MatrixError)));
+    }
+
+    #[test]
+    fn test_eval_equation_at_unit_vectors() {
+        // At a unit vector e_i only the x_i^2 term survives: the diagonal of P1 for a vinegar
+        // index, the diagonal of P3 for an oil one
+        let matrices = PMatrices::<TinyMayo>::from_seed(&[6u8; 16]).unwrap();
+        let v = TinyMayo::N_PARAM - TinyMayo::O_PARAM;
+        for i in 0..TinyMayo::N_PARAM {
+            let mut x = vec![F16::new(0); TinyMayo::N_PARAM];
+            x[i] = F16::new(1);
+            for eq in 0..TinyMayo::M_PARAM {
+                let diagonal = if i < v { matrices.p1(eq, i, i) } else { matrices.p3(eq, i - v, i - v) };
+                assert_eq!(eval_equation::<TinyMayo>(&x, &matrices, eq), diagonal);
+            }
+        }
+        // Missing trailing elements count as zero
+        let x = [F16::new(1)];
+        assert_eq!(eval_equation::<TinyMayo>(&x, &matrices, 0), matrices.p1(0, 0, 0));
+    }
+
+    #[test]
+    fn test_sign_with_salt_is_reproducible() {
+        let (sk, pk) = generate_keypair_generic::<Mayo1>().unwrap();
+        let salt = [7u8; Mayo1::SALT_BYTES];
+        let first = sign_with_salt::<Mayo1>(&sk, b"kat", &salt).unwrap();
+        assert_eq!(sign_with_salt::<Mayo1>(&sk, b"kat", &salt).unwrap(), first);
+        assert_eq!(&first[Mayo1::SIG_BYTES - Mayo1::SALT_BYTES..], &salt);
+        assert!(verify_generic::<Mayo1>(&pk, b"kat", &first).unwrap());
+    }
+
+    #[test]
+    fn test_sign_prehashed_matches_sign_with_salt() {
+        let (sk, pk) = generate_keypair_generic::<Mayo1>().unwrap();
+        let salt = [7u8; Mayo1::SALT_BYTES];
+        let digest = hash_message::<Mayo1>(b"prehashed");
+        let signature = sign_prehashed_with_salt::<Mayo1>(&sk, &digest, &salt).unwrap();
+        assert_eq!(signature, sign_with_salt::<Mayo1>(&sk, b"prehashed", &salt).unwrap());
+        assert!(verify_generic::<Mayo1>(&pk, b"prehashed", &signature).unwrap());
+    }
+
+    #[test]
+    fn test_sign_prehashed_with_checks_digest_lengths() {
+        let (sk, _pk) = generate_keypair_generic::<TinyMayo>().unwrap();
+        let digest = <sha3::Sha3_256 as sha3::Digest>::digest(b"external").to_vec();
+        let bad_length = |r: Result<Vec<u8>, CryptoError>| matches!(r, Err(CryptoError::InvalidEncodingLength));
+        assert!(bad_length(sign_prehashed_with::<TinyMayo, sha3::Sha3_256>(&sk, &digest[..31])));
+        assert!(bad_length(sign_prehashed_with::<TinyMayo, sha3::Sha3_512>(&sk, &digest)));
+        assert!(bad_length(sign_prehashed_with_salt::<TinyMayo>(&sk, &digest, &[0u8; 3])));
+        // A correctly sized digest reaches the signer
+        assert!(!bad_length(sign_prehashed_with::<TinyMayo, sha3::Sha3_256>(&sk, &digest)));
+    }
+
+    #[test]
+    fn test_prehashed_sha3_round_trip() {
+        use sha3::{Digest, Sha3_256};
+        let (sk, pk) = generate_keypair_generic::<Mayo1>().unwrap();
+        let digest = Sha3_256::digest(b"external").to_vec();
+        let signature = sign_prehashed_with::<Mayo1, Sha3_256>(&sk, &digest).unwrap();
+        assert!(verify_prehashed_with::<Mayo1, Sha3_256>(&pk, &digest, &signature).unwrap());
+
+        let other = Sha3_256::digest(b"other").to_vec();
+        assert!(!verify_prehashed_with::<Mayo1, Sha3_256>(&pk, &other, &signature).unwrap());
+        // The signature binds the SHA3 digest, not the crate's own hash of the message
+        assert!(!verify_generic::<Mayo1>(&pk, b"external", &signature).unwrap());
+        assert!(matches!(
+            verify_prehashed_with::<Mayo1, sha3::Sha3_512>(&pk, &digest, &signature),
+            Err(CryptoError::InvalidEncodingLength)
+        ));
+        assert!(!verify_prehashed_with::<Mayo1, Sha3_256>(&pk[1..], &digest, &signature).unwrap());
+    }
+
+    #[test]
+    fn test_decode_oil_matrix_dimensions_and_round_trip() {
+        let (sk, _pk) = generate_keypair_generic::<Mayo1>().unwrap();
+        let expanded = shake256_digest(&sk, Mayo1::PK_SEED_BYTES + Mayo1::O_BYTES);
+        let o_bytes = &expanded[Mayo1::PK_SEED_BYTES..];
+        let o = decode_oil_matrix::<Mayo1>(o_bytes).unwrap();
+        assert_eq!((o.rows(), o.cols()), (Mayo1::N_PARAM - Mayo1::O_PARAM, Mayo1::O_PARAM));
+        assert_eq!(o.encode_o(), o_bytes);
+        assert!(matches!(
+            decode_oil_matrix::<Mayo1>(&o_bytes[1..]),
+            Err(CryptoError::InvalidEncodingLength)
+        ));
+    }
+
+    #[test]
+    fn test_vinegar_seed_for_attempt_is_reproducible() {
+        let vinegar = |base_seed: &[u8], attempt: usize| {
+            Vector::random(TinyMayo::N_PARAM - TinyMayo::O_PARAM, &mut attempt_rng(base_seed, attempt))
+        };
+        let base_seed = [9u8; 32];
+        assert_eq!(vinegar(&base_seed, 3), vinegar(&base_seed, 3));
+        assert_ne!(vinegar(&base_seed, 3), vinegar(&base_seed, 4));
+        assert_ne!(vinegar(&[8u8; 32], 3), vinegar(&base_seed, 3));
+
+        let mut input = base_seed.to_vec();
+        input.extend_from_slice(&3u64.to_le_bytes());
+        assert_eq!(&vinegar_seed_for_attempt(&base_seed, 3)[..], &shake256_digest(&input, 32)[..]);
+    }
+
+    #[test]
+    fn test_salted_signing_rng_depends_on_all_inputs() {
+        let draw = |sk_seed: &[u8], message: &[u8], salt: &[u8]| {
+            salted_signing_rng(sk_seed, &hash_message::<TinyMayo>(message), salt).next_u64()
+        };
+        let (sk_seed, salt) = ([1u8; 24], [2u8; 24]);
+        let reference = draw(&sk_seed, b"m", &salt);
+        assert_eq!(draw(&sk_seed, b"m", &salt), reference);
+        assert_ne!(draw(&[3u8; 24], b"m", &salt), reference);
+        assert_ne!(draw(&sk_seed, b"n", &salt), reference);
+        assert_ne!(draw(&sk_seed, b"m", &[4u8; 24]), reference);
+    }
+
+    #[test]
+    fn test_sign_with_salt_rejects_bad_salt_length() {
+        let (sk, _pk) = generate_keypair_generic::<TinyMayo>().unwrap();
+        let short = vec![0u8; TinyMayo::SALT_BYTES - 1];
+        assert!(matches!(sign_with_salt::<TinyMayo>(&sk, b"m", &short), Err(CryptoError::InvalidEncodingLength)));
+    }
+
+    #[test]
+    fn test_expand_matrices_rejects_wrong_seed_length() {
+        let short = [0u8; Mayo1::PK_SEED_BYTES - 1];
+        assert!(matches!(expand_matrices::<Mayo1>(&short), Err(CryptoError::InvalidKeyLength)));
+        let long = [0u8; Mayo1::PK_SEED_BYTES + 1];
+        assert!(matches!(expand_matrices::<Mayo1>(&long), Err(CryptoError::InvalidKeyLength)));
+        assert!(matches!(PMatrices::<Mayo1>::from_seed(&[]), Err(CryptoError::InvalidKeyLength)));
+    }
+
+    #[test]
+    #[cfg(feature = "debug-dump")]
+    fn test_dump_matrices_hex_is_deterministic() {
+        let seed = [11u8; 24];
+        let dump = dump_matrices_hex::<TinyMayo>(&seed);
+        assert_eq!(dump_matrices_hex::<TinyMayo>(&seed), dump);
+        assert_ne!(dump_matrices_hex::<TinyMayo>(&[12u8; 24]), dump);
+
+        let names: Vec<&str> = dump.lines().map(|line| line.split(':').next().unwrap()).collect();
+        assert_eq!(names, ["P1", "P2", "P3", "O"]);
+
+        // The P1 line is the packed expansion of the pk_seed the secret seed derives
+        let pk_seed = &shake256_digest(&seed, TinyMayo::PK_SEED_BYTES)[..];
+        let (p1, _, _) = expand_matrices::<TinyMayo>(pk_seed).unwrap().into_parts();
+        assert_eq!(dump.lines().next().unwrap(), format!("P1: {}", hex::encode(encode_f16_elements(&p1))));
+
+        assert!(dump_matrices_hex::<TinyMayo>(&seed[1..]).starts_with("sk_seed has 23 bytes"));
+    }
+
+    #[test]
+    fn test_parse_signature() {
+        let signature: Vec<u8> = (0..TinyMayo::SIG_BYTES).map(|i| (i * 37) as u8).collect();
+        let parsed = parse_signature::<TinyMayo>(&signature).unwrap();
+        assert_eq!(parsed.salt, &signature[TinyMayo::SIG_BYTES - TinyMayo::SALT_BYTES..]);
+        assert_eq!(parsed.s.len(), TinyMayo::K_PARAM);
+        assert!(parsed.s.iter().all(|row| row.len() == TinyMayo::N_PARAM));
+        // s is packed low nibble first from the start of the signature
+        assert_eq!(parsed.s[0][0].value(), signature[0] & 0x0F);
+        assert_eq!(parsed.s[0][1].value(), signature[0] >> 4);
+
+        assert!(matches!(parse_signature::<TinyMayo>(&signature[1..]), Err(CryptoError::InvalidEncodingLength)));
+    }
+
+    #[test]
+    fn test_prf_stream_bytes() {
+        // (78 * (3081 + 624 + 36) + 1) / 2, as expand_matrices has always requested for MAYO-1
+        assert_eq!(Mayo1::PRF_STREAM_BYTES, 145899);
+
+        let mut stream = Vec::new();
+        let mut matrices = PMatrices::<TinyMayo>::zeroed();
+        expand_matrices_with_stream::<TinyMayo>(&[1u8; 16], &mut stream, &mut matrices.p1, &mut matrices.p2, &mut matrices.p3).unwrap();
+        assert_eq!(stream.len(), TinyMayo::PRF_STREAM_BYTES);
+    }
+
+    #[test]
+    fn test_expand_matrices_into_matches_allocating() {
+        let (p1, p2, p3) = expand_matrices::<TinyMayo>(&[9u8; 16]).unwrap().into_parts();
+        let PMatrices { p1: mut b1, p2: mut b2, p3: mut b3, .. } = PMatrices::<TinyMayo>::zeroed();
+        // Run twice so the second call overwrites the first key's contents
+        expand_matrices_into::<TinyMayo>(&[4u8; 16], &mut b1, &mut b2, &mut b3).unwrap();
+        expand_matrices_into::<TinyMayo>(&[9u8; 16], &mut b1, &mut b2, &mut b3).unwrap();
+        assert_eq!((b1, b2, b3), (p1, p2, p3));
+    }
+
+    #[test]
+    fn test_expand_p2_matrices() {
+        let seed = [6u8; 16];
+        let p2_matrices = expand_p2_matrices::<Mayo1>(&seed).unwrap();
+        assert_eq!(p2_matrices.len(), Mayo1::M_PARAM);
+        assert!(p2_matrices.iter().all(|m| m.rows() == Mayo1::P2_MAT_ROWS && m.cols() == Mayo1::P2_MAT_COLS));
+
+        let matrices = PMatrices::<Mayo1>::from_seed(&seed).unwrap();
+        assert_eq!(p2_matrices[3].get(5, 7), Some(matrices.p2(3, 5, 7)));
+        assert!(crate::encoding::encode_p2(&p2_matrices).is_ok());
+    }
+
+    #[test]
+    fn test_expand_matrices_into_rejects_wrong_lengths() {
+        let PMatrices { mut p1, mut p2, mut p3, .. } = PMatrices::<TinyMayo>::zeroed();
+        let short = &mut p3[1..];
+        assert!(matches!(
+            expand_matrices_into::<TinyMayo>(&[9u8; 16], &mut p1, &mut p2, short),
+            Err(CryptoError::MatrixError)
+        ));
+        assert!(matches!(
+            expand_matrices_into::<TinyMayo>(&[9u8; 15], &mut p1, &mut p2, &mut p3),
+            Err(CryptoError::InvalidKeyLength)
+        ));
+    }
+
+    // Minimal subscriber that records the name of every span created while it is installed
+    #[cfg(feature = "tracing")]
+    #[derive(Clone, Default)]
+    struct SpanNames(std::sync::Arc<std::sync::Mutex<Vec<&'static str>>>);
+
+    #[cfg(feature = "tracing")]
+    impl tracing::Subscriber for SpanNames {
+        fn enabled(&self, _: &tracing::Metadata<'_>) -> bool {
+            true
+        }
+        fn new_span(&self, span: &tracing::span::Attributes<'_>) -> tracing::span::Id {
+            let mut names = self.0.lock().unwrap();
+            names.push(span.metadata().name());
+            tracing::span::Id::from_u64(names.len() as u64)
+        }
+        fn record(&self, _: &tracing::span::Id, _: &tracing::span::Record<'_>) {}
+        fn record_follows_from(&self, _: &tracing::span::Id, _: &tracing::span::Id) {}
+        fn event(&self, _: &tracing::Event<'_>) {}
+        fn enter(&self, _: &tracing::span::Id) {}
+        fn exit(&self, _: &tracing::span::Id) {}
+    }
+
+    #[test]
+    #[cfg(feature = "tracing")]
+    fn test_signing_emits_spans() {
+        let (sk, pk) = generate_keypair_generic::<SignMayo>().unwrap();
+        let names = SpanNames::default();
+        tracing::subscriber::with_default(names.clone(), || {
+            let signature = sign_generic::<SignMayo>(&sk, b"traced").unwrap();
+            assert!(verify_generic::<SignMayo>(&pk, b"traced", &signature).unwrap());
+        });
+
+        let names = names.0.lock().unwrap();
+        assert_eq!(names.first(), Some(&"sign_generic"));
+        // One span per attempt, up to the one that produced the signature
+        assert!(names.iter().any(|&name| name == "sign_attempt"));
+        assert_eq!(names.last(), Some(&"verify_generic"));
+    }
+
+    #[test]
+    fn test_sign_with_attempts_reports_count() {
+        let (sk, pk) = generate_keypair_generic::<Mayo1>().unwrap();
+        let (signature, attempts) = sign_with_attempts::<Mayo1>(&sk, b"count", SIGN_ATTEMPTS).unwrap();
+        assert!((1..=SIGN_ATTEMPTS).contains(&attempts));
+        assert!(verify_generic::<Mayo1>(&pk, b"count", &signature).unwrap());
+    }
+
+    #[test]
+    fn test_sign_with_attempts_respects_budget() {
+        let (sk, pk) = generate_keypair_generic::<SignMayo>().unwrap();
+        // No attempts can only fail
+        assert!(matches!(sign_with_attempts::<SignMayo>(&sk, b"count", 0), Err(CryptoError::SigningError)));
+        for _ in 0..8 {
+            match sign_with_attempts::<SignMayo>(&sk, b"count", 3) {
+                Ok((signature, attempts)) => {
+                    assert!((1..=3).contains(&attempts));
+                    assert!(verify_generic::<SignMayo>(&pk, b"count", &signature).unwrap());
+                }
+                Err(e) => assert!(matches!(e, CryptoError::SigningError)),
+            }
+        }
+    }
+}
@@ -2,7 +2,9 @@
 use crate::f16::F16;
  // May not be directly used, but F16 is.
 use crate::matrix::Matrix;
+#[cfg(feature = "mayo1")]
 use crate::params; // Import the new params module
+use crate::params::MayoParams;
 
 // Encodes a vector v ∈ Fm_16 into m/2 bytes in a bitsliced format.
 // Output byte 0: bit 0 of v0, bit 0 of v1, ..., bit 0 of v7.
@@ -64,80 +66,149 @@ pub fn encode_bitsliced_matrices(
     c: usize,            // Cols of each matrix A_i
     is_triangular: bool,
 ) -> Result<Vec<u8>, String> {
+    let mut output_bytes = Vec::new();
+    encode_bitsliced_matrices_with(matrices, r, c, is_triangular, |e| e, |bytes| {
+        output_bytes.extend_from_slice(bytes);
+        Ok(())
+    })?;
+    Ok(output_bytes)
+}
+
+// Same byte stream as encode_bitsliced_matrices, written to `w` one encoded v_k at a time
+// instead of being collected, so the full encoding is never held in memory.
+#[cfg(feature = "std")]
+pub fn encode_bitsliced_matrices_to<W: std::io::Write>(
+    w: &mut W,
+    matrices: &[Matrix],
+    r: usize,
+    c: usize,
+    is_triangular: bool,
+) -> std::io::Result<()> {
+    encode_bitsliced_matrices_with(
+        matrices,
+        r,
+        c,
+        is_triangular,
+        |e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e),
+        |bytes| w.write_all(bytes),
+    )
+}
+
+// Shared core of the bitsliced matrix encoders: validates the input, then passes each
+// EncodeBitslicedVector(v_k) to `emit` in order. `invalid` converts validation errors.
+fn encode_bitsliced_matrices_with<E>(
+    matrices: &[Matrix],
+    r: usize,
+    c: usize,
+    is_triangular: bool,
+    invalid: impl Fn(String) -> E,
+    mut emit: impl FnMut(&[u8]) -> Result<(), E>,
+) -> Result<(), E> {
     if matrices.is_empty() {
-        return Ok(Vec::new());
+        return Ok(());
     }
 
-    let m = matrices.len(); // Number of matrices in the sequence
-    
-    // Pad to next multiple of 8 if needed
-    let _padded_m = m.div_ceil(8) * 8;
-
     for (idx, a_i) in matrices.iter().enumerate() {
         if a_i.rows() != r || a_i.cols() != c {
-            return Err(format!(
+            return Err(invalid(format!(
                 "Matrix {} has incorrect dimensions: expected {}x{}, got {}x{}",
                 idx, r, c, a_i.rows(), a_i.cols()
-            ));
+            )));
         }
         if is_triangular && r != c {
-            return Err("Triangular matrices must be square.".to_string());
+            return Err(invalid("Triangular matrices must be square.".to_string()));
+        }
+        // Entries below the diagonal are not encoded, so they must be zero
+        if is_triangular && !a_i.is_upper_triangular() {
+            return Err(invalid(format!("Matrix {} is not upper triangular", idx)));
         }
     }
 
-    let num_elements_per_matrix = if is_triangular {
-        r * (r + 1) / 2
-    } else {
-        r * c
-    };
-
-    let mut output_bytes = Vec::new();
+    let m = matrices.len(); // Number of matrices in the sequence
     let mut v_k = Vec::with_capacity(m); // To store ( (A_0)_k, ..., (A_m-1)_k )
 
-    for k_elem_idx in 0..num_elements_per_matrix {
-        v_k.clear(); // Reset for the next v_k
-
-        // Determine which (row, col) this k_elem_idx corresponds to
-        let mut current_row = 0;
-        let mut current_col = 0;
-        if is_triangular {
-            let mut current_k = 0;
-            let mut found = false;
-            for i_row in 0..r {
-                for j_col in i_row..c { // Only upper triangle, j_col >= i_row
-                    if current_k == k_elem_idx {
-                        current_row = i_row;
-                        current_col = j_col;
-                        found = true;
-                        break;
-                    }
-                    current_k += 1;
-                }
-                if found { break; }
-            }
-            if !found { // Should not happen if k_elem_idx is in range
-                 return Err("Internal error: k_elem_idx out of bounds for triangular matrix".to_string());
-            }
-        } else {
-            current_row = k_elem_idx / c;
-            current_col = k_elem_idx % c;
-        }
-
+    // (row, col) of each k in row-major order, restricted to j >= i when triangular
+    let positions = (0..r).flat_map(|i| (if is_triangular { i } else { 0 }..c).map(move |j| (i, j)));
+    for (current_row, current_col) in positions {
         // Construct v_k = ( (A_0)[current_row, current_col], ..., (A_m-1)[current_row, current_col] )
-        for a_i in matrices.iter() {
-            match a_i.get(current_row, current_col) {
-                Some(f16_val) => v_k.push(f16_val),
-                None => return Err(format!("Element access error at matrix {} for k_elem_idx {}", matrices.len(), k_elem_idx)), // Should not happen
+        v_k.clear();
+        v_k.extend(matrices.iter().map(|a_i| a_i.get(current_row, current_col).unwrap()));
+
+        let encoded_v_k = encode_bitsliced_vector(&v_k).map_err(&invalid)?;
+        emit(&encoded_v_k)?;
+    }
+    Ok(())
+}
+
+// Inverse of encode_bitsliced_vector: reads m elements from ceil(m/8) * 4 bytes.
+pub fn decode_bitsliced_vector(m: usize, bytes: &[u8]) -> Result<Vec<F16>, String> {
+    let bytes_per_bit_plane = m.div_ceil(8);
+    if bytes.len() != bytes_per_bit_plane * 4 {
+        return Err(format!(
+            "Invalid bitsliced vector length: expected {}, got {}",
+            bytes_per_bit_plane * 4,
+            bytes.len()
+        ));
+    }
+
+    let mut output = vec![0u8; m];
+    for bit_plane in 0..4 {
+        let plane = &bytes[bit_plane * bytes_per_bit_plane..(bit_plane + 1) * bytes_per_bit_plane];
+        for (i, value) in output.iter_mut().enumerate() {
+            if (plane[i / 8] >> (i % 8)) & 1 != 0 {
+                *value |= 1 << bit_plane;
             }
         }
+    }
+    Ok(output.into_iter().map(F16::new).collect())
+}
 
-        let encoded_v_k = encode_bitsliced_vector(&v_k)?;
-        output_bytes.extend_from_slice(&encoded_v_k);
+// Inverse of encode_bitsliced_matrices: rebuilds m matrices of size r x c.
+// For triangular matrices the entries below the diagonal are left as zero.
+pub fn decode_bitsliced_matrices(
+    bytes: &[u8],
+    m: usize,
+    r: usize,
+    c: usize,
+    is_triangular: bool,
+) -> Result<Vec<Matrix>, String> {
+    if is_triangular && r != c {
+        return Err("Triangular matrices must be square.".to_string());
+    }
+    if m == 0 {
+        return if bytes.is_empty() {
+            Ok(Vec::new())
+        } else {
+            Err(format!("Expected no bytes for zero matrices, got {}", bytes.len()))
+        };
     }
 
-    Ok(output_bytes)
+    let positions: Vec<(usize, usize)> = if is_triangular {
+        (0..r).flat_map(|i| (i..c).map(move |j| (i, j))).collect()
+    } else {
+        (0..r).flat_map(|i| (0..c).map(move |j| (i, j))).collect()
+    };
+    let bytes_per_element = m.div_ceil(8) * 4;
+    if bytes.len() != positions.len() * bytes_per_element {
+        return Err(format!(
+            "Invalid bitsliced matrices length: expected {}, got {}",
+            positions.len() * bytes_per_element,
+            bytes.len()
+        ));
+    }
+
+    let mut matrices = vec![Matrix::zero(r, c); m];
+    for (chunk, &(row, col)) in bytes.chunks(bytes_per_element).zip(positions.iter()) {
+        let v_k = decode_bitsliced_vector(m, chunk)?;
+        for (a_i, value) in matrices.iter_mut().zip(v_k) {
+            a_i.set(row, col, value)?;
+        }
+    }
+    Ok(matrices)
 }
 
+// Fixed-size encoders for the MAYO-1 sequence lengths; compiled only with MAYO-1
+#[cfg(feature = "mayo1")]
 pub fn encode_p1(p1_matrices: &[Matrix]) -> Result<Vec<u8>, String> {
     if p1_matrices.len() != params::M_PARAM {
         return Err(format!("Expected {} P1 matrices, got {}", params::M_PARAM, p1_matrices.len()));
@@ -150,6 +221,24 @@ pub fn encode_p1(p1_matrices: &[Matrix]) -> Result<Vec<u8>, String> {
     )
 }
 
+#[cfg(all(feature = "std", feature = "mayo1"))]
+pub fn encode_p1_to<W: std::io::Write>(w: &mut W, p1_matrices: &[Matrix]) -> std::io::Result<()> {
+    if p1_matrices.len() != params::M_PARAM {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("Expected {} P1 matrices, got {}", params::M_PARAM, p1_matrices.len()),
+        ));
+    }
+    encode_bitsliced_matrices_to(
+        w,
+        p1_matrices,
+        params::P1_MAT_ROWS,
+        params::P1_MAT_COLS,
+        params::P1_IS_TRIANGULAR,
+    )
+}
+
+#[cfg(feature = "mayo1")]
 pub fn encode_p2(p2_matrices: &[Matrix]) -> Result<Vec<u8>, String> {
     if p2_matrices.len() != params::M_PARAM {
         return Err(format!("Expected {} P2 matrices, got {}", params::M_PARAM, p2_matrices.len()));
@@ -162,6 +251,24 @@ pub fn encode_p2(p2_matrices: &[Matrix]) -> Result<Vec<u8>, String> {
     )
 }
 
+#[cfg(all(feature = "std", feature = "mayo1"))]
+pub fn encode_p2_to<W: std::io::Write>(w: &mut W, p2_matrices: &[Matrix]) -> std::io::Result<()> {
+    if p2_matrices.len() != params::M_PARAM {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("Expected {} P2 matrices, got {}", params::M_PARAM, p2_matrices.len()),
+        ));
+    }
+    encode_bitsliced_matrices_to(
+        w,
+        p2_matrices,
+        params::P2_MAT_ROWS,
+        params::P2_MAT_COLS,
+        params::P2_IS_TRIANGULAR,
+    )
+}
+
+#[cfg(feature = "mayo1")]
 pub fn encode_p3(p3_matrices: &[Matrix]) -> Result<Vec<u8>, String> {
      if p3_matrices.len() != params::M_PARAM {
         return Err(format!("Expected {} P3 matrices, got {}", params::M_PARAM, p3_matrices.len()));
@@ -174,6 +281,24 @@ pub fn encode_p3(p3_matrices: &[Matrix]) -> Result<Vec<u8>, String> {
     )
 }
 
+#[cfg(all(feature = "std", feature = "mayo1"))]
+pub fn encode_p3_to<W: std::io::Write>(w: &mut W, p3_matrices: &[Matrix]) -> std::io::Result<()> {
+    if p3_matrices.len() != params::M_PARAM {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("Expected {} P3 matrices, got {}", params::M_PARAM, p3_matrices.len()),
+        ));
+    }
+    encode_bitsliced_matrices_to(
+        w,
+        p3_matrices,
+        params::P3_MAT_ROWS,
+        params::P3_MAT_COLS,
+        params::P3_IS_TRIANGULAR,
+    )
+}
+
+#[cfg(feature = "mayo1")]
 pub fn encode_l(l_matrices: &[Matrix]) -> Result<Vec<u8>, String> {
     if l_matrices.len() != params::M_PARAM {
         return Err(format!("Expected {} L matrices, got {}", params::M_PARAM, l_matrices.len()));
@@ -187,13 +312,133 @@ pub fn encode_l(l_matrices: &[Matrix]) -> Result<Vec<u8>, String> {
     )
 }
 
+#[cfg(all(feature = "std", feature = "mayo1"))]
+pub fn encode_l_to<W: std::io::Write>(w: &mut W, l_matrices: &[Matrix]) -> std::io::Result<()> {
+    if l_matrices.len() != params::M_PARAM {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("Expected {} L matrices, got {}", params::M_PARAM, l_matrices.len()),
+        ));
+    }
+    encode_bitsliced_matrices_to(
+        w,
+        l_matrices,
+        params::L_MAT_ROWS,
+        params::L_MAT_COLS,
+        params::L_IS_TRIANGULAR,
+    )
+}
+
+// Row-major entries of each matrix, only the upper triangle (j >= i) when is_triangular: the
+// flat per-equation layout of crypto::expand_matrices
+#[cfg(feature = "std")]
+fn flatten_matrices(matrices: &[Matrix], is_triangular: bool) -> Vec<F16> {
+    let mut flat = Vec::new();
+    for a in matrices {
+        for i in 0..a.rows() {
+            let first = if is_triangular { i } else { 0 };
+            flat.extend((first..a.cols()).map(|j| a.get(i, j).expect("index within matrix")));
+        }
+    }
+    flat
+}
+
+// Inverse of flatten_matrices; entries below the diagonal of triangular matrices are zero
+#[cfg(feature = "std")]
+fn unflatten_matrices(flat: &[F16], rows: usize, cols: usize, is_triangular: bool) -> Result<Vec<Matrix>, String> {
+    let per_matrix = if is_triangular { rows * (rows + 1) / 2 } else { rows * cols };
+    flat.chunks_exact(per_matrix)
+        .map(|chunk| {
+            let mut a = Matrix::zero(rows, cols);
+            let mut entries = chunk.iter();
+            for i in 0..rows {
+                let first = if is_triangular { i } else { 0 };
+                for j in first..cols {
+                    a.set(i, j, *entries.next().expect("chunk holds per_matrix entries"))?;
+                }
+            }
+            Ok(a)
+        })
+        .collect()
+}
+
+// Expanded public key P1 || P2 || P3 from matrix sequences, in the layout of crypto::expand_pk
+// (crypto::encode_expanded_pk). Entries below the diagonal of P1 and P3 are not encoded.
+// Nibble-packed per equation rather than bitsliced, which could not fit P::EPK_BYTES for
+// MAYO-1; see encode_expanded_pk.
+#[cfg(feature = "std")]
+pub fn encode_epk<P: MayoParams>(p1: &[Matrix], p2: &[Matrix], p3: &[Matrix]) -> Result<Vec<u8>, String> {
+    let matrices = crate::crypto::PMatrices::<P>::new(
+        flatten_matrices(p1, P::P1_IS_TRIANGULAR),
+        flatten_matrices(p2, P::P2_IS_TRIANGULAR),
+        flatten_matrices(p3, P::P3_IS_TRIANGULAR),
+    )
+    .map_err(|_| format!("P1, P2, P3 must each be {} matrices of the {} sizes", P::M_PARAM, P::name()))?;
+    crate::crypto::encode_expanded_pk(&matrices).map_err(|e| e.to_string())
+}
+
+// (P1, P2, P3) matrix sequences, one matrix per equation
+pub type PublicMatrixSet = (Vec<Matrix>, Vec<Matrix>, Vec<Matrix>);
+
+// crypto::decode_expanded_pk, returning the matrices as sequences instead of flat vectors
+#[cfg(feature = "std")]
+pub fn decode_epk<P: MayoParams>(bytes: &[u8]) -> Result<PublicMatrixSet, String> {
+    if bytes.len() != P::EPK_BYTES {
+        return Err(format!("Invalid EPK length: expected {}, got {}", P::EPK_BYTES, bytes.len()));
+    }
+    let matrices = crate::crypto::decode_expanded_pk::<P>(bytes).map_err(|e| e.to_string())?;
+    let (p1, p2, p3) = matrices.as_slices();
+    Ok((
+        unflatten_matrices(p1, P::P1_MAT_ROWS, P::P1_MAT_COLS, P::P1_IS_TRIANGULAR)?,
+        unflatten_matrices(p2, P::P2_MAT_ROWS, P::P2_MAT_COLS, P::P2_IS_TRIANGULAR)?,
+        unflatten_matrices(p3, P::P3_MAT_ROWS, P::P3_MAT_COLS, P::P3_IS_TRIANGULAR)?,
+    ))
+}
+
+// (seed, O, P1, L) as stored in an expanded secret key
+pub type SecretKeyParts = (Vec<u8>, Matrix, Vec<Matrix>, Vec<Matrix>);
+
+// Expanded secret key seed || O || P1 || L from matrices, in the layout of crypto::expand_sk
+// (crypto::encode_expanded_sk). Entries below the diagonal of P1 are not encoded. P1 and L are
+// nibble-packed per equation like the EPK's matrices; see crypto::encode_expanded_pk.
+#[cfg(feature = "std")]
+pub fn encode_esk<P: MayoParams>(seed: &[u8], o: &Matrix, p1: &[Matrix], l: &[Matrix]) -> Result<Vec<u8>, String> {
+    let v = P::N_PARAM - P::O_PARAM;
+    if o.rows() != v || o.cols() != P::O_PARAM {
+        return Err(format!("O must be {}x{}, got {}x{}", v, P::O_PARAM, o.rows(), o.cols()));
+    }
+    crate::crypto::encode_expanded_sk::<P>(
+        seed,
+        &flatten_matrices(std::slice::from_ref(o), false),
+        &flatten_matrices(p1, P::P1_IS_TRIANGULAR),
+        &flatten_matrices(l, P::L_IS_TRIANGULAR),
+    )
+    .map_err(|e| e.to_string())
+}
+
+// crypto::decode_expanded_sk, returning O, P1 and L as matrices instead of flat vectors
+#[cfg(feature = "std")]
+pub fn decode_esk<P: MayoParams>(bytes: &[u8]) -> Result<SecretKeyParts, String> {
+    if bytes.len() != P::ESK_BYTES {
+        return Err(format!("Invalid ESK length: expected {}, got {}", P::ESK_BYTES, bytes.len()));
+    }
+    let (seed, o, p1, l) = crate::crypto::decode_expanded_sk::<P>(bytes).map_err(|e| e.to_string())?;
+    let v = P::N_PARAM - P::O_PARAM;
+    Ok((
+        seed,
+        Matrix::new(v, P::O_PARAM, o)?,
+        unflatten_matrices(&p1, P::P1_MAT_ROWS, P::P1_MAT_COLS, P::P1_IS_TRIANGULAR)?,
+        unflatten_matrices(&l, P::L_MAT_ROWS, P::L_MAT_COLS, P::L_IS_TRIANGULAR)?,
+    ))
+}
+
 // Placeholder for Decode functions (to be implemented later if needed by other algorithms)
 // pub fn decode_p1(bytes: &[u8]) -> Result<Vec<Matrix>, String> { Err("Not implemented".to_string()) }
 // pub fn decode_p2(bytes: &[u8]) -> Result<Vec<Matrix>, String> { Err("Not implemented".to_string()) }
 // pub fn decode_p3(bytes: &[u8]) -> Result<Vec<Matrix>, String> { Err("Not implemented".to_string()) }
 // pub fn decode_l(bytes: &[u8]) -> Result<Vec<Matrix>, String> { Err("Not implemented".to_string()) }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "mayo1"))]
 mod tests {
     use super::*;
     use crate::f16::F16;
@@ -224,9 +469,22 @@ mod tests {
         matrices
     }
 
+    // create_test_matrix_sequence with the entries below the diagonal zeroed
+    fn create_test_triangular_sequence(size: usize, val_pattern: u8) -> Vec<Matrix> {
+        let mut matrices = create_test_matrix_sequence(size, size, val_pattern);
+        for a_i in &mut matrices {
+            for r in 1..size {
+                for c in 0..r {
+                    a_i.set(r, c, F16::new(0)).unwrap();
+                }
+            }
+        }
+        matrices
+    }
+
     #[test]
     fn test_encode_bitsliced_vector_empty() {
-        assert_eq!(encode_bitsliced_vector(&[]).unwrap(), Vec::new());
+        assert_eq!(encode_bitsliced_vector(&[]).unwrap(), Vec::<u8>::new());
     }
 
     #[test]
@@ -310,7 +568,7 @@ mod tests {
     #[test]
     fn test_encode_bitsliced_matrices_empty() {
         let matrices: Vec<Matrix> = Vec::new();
-        assert_eq!(encode_bitsliced_matrices(&matrices, 2, 2, false).unwrap(), Vec::new());
+        assert_eq!(encode_bitsliced_matrices(&matrices, 2, 2, false).unwrap(), Vec::<u8>::new());
     }
 
     #[test]
@@ -378,17 +636,17 @@ mod tests {
         // 8 matrices, each 2x2, upper triangular.
         // r=2, c=2. num_elements_per_matrix = r*(r+1)/2 = 2*3/2 = 3.
         // Elements are (0,0), (0,1), (1,1) in row-major for upper triangle.
-        // A_i = [[ (A_i)_00, (A_i)_01 ], [ ignored, (A_i)_11 ]]
+        // A_i = [[ (A_i)_00, (A_i)_01 ], [ 0, (A_i)_11 ]]
         // v_0 (for element (0,0)): ( (A0)_00, ..., (A7)_00 )
         // v_1 (for element (0,1)): ( (A0)_01, ..., (A7)_01 )
         // v_2 (for element (1,1)): ( (A0)_11, ..., (A7)_11 )
         // Output = enc(v_0) || enc(v_1) || enc(v_2)
 
-        // Let A0 = [[1,2],[?,3]], others are [[0,0],[?,0]]
+        // Let A0 = [[1,2],[0,3]], others are zero
         // (A0)_00=1, (A0)_01=2, (A0)_11=3
         let mut matrices = Vec::new();
-        matrices.push(f16m(2,2,&[1,2, 99,3])); // 99 is ignored placeholder for lower triangle
-        for _ in 0..7 { matrices.push(f16m(2,2,&[0,0, 99,0])); }
+        matrices.push(f16m(2,2,&[1,2, 0,3]));
+        for _ in 0..7 { matrices.push(f16m(2,2,&[0,0, 0,0])); }
 
         // v_0 (for (0,0)): [F16(1), 0,...0] -> enc_v0 = [0x01,0,0,0]
         // v_1 (for (0,1)): [F16(2), 0,...0] -> enc_v1 = (F16(2)=0010_b) -> [0x00,0x01,0,0] (bit1 plane has 1)
@@ -408,7 +666,7 @@ mod tests {
 
     #[test]
     fn test_encode_p1_wrapper() {
-        let p1_mats = create_test_matrix_sequence(params::P1_MAT_ROWS, params::P1_MAT_COLS, 1);
+        let p1_mats = create_test_triangular_sequence(params::P1_MAT_ROWS, 1);
         println!("Created {} P1 matrices of {}x{}", p1_mats.len(), params::P1_MAT_ROWS, params::P1_MAT_COLS);
         println!("M_PARAM = {}, expected matrices = {}", params::M_PARAM, p1_mats.len());
         
@@ -462,9 +720,19 @@ mod tests {
         assert_eq!(bytes.len(), expected_bytes);
     }
 
+    #[test]
+    fn test_triangular_encoders_reject_dense_matrices() {
+        let p1_dense = create_test_matrix_sequence(params::P1_MAT_ROWS, params::P1_MAT_COLS, 1);
+        assert!(encode_p1(&p1_dense).unwrap_err().contains("not upper triangular"));
+        let p3_dense = create_test_matrix_sequence(params::P3_MAT_ROWS, params::P3_MAT_COLS, 3);
+        assert!(encode_p3(&p3_dense).unwrap_err().contains("not upper triangular"));
+        #[cfg(feature = "std")]
+        assert!(encode_p3_to(&mut Vec::new(), &p3_dense).is_err());
+    }
+
     #[test]
     fn test_encode_p3_wrapper() {
-        let p3_mats = create_test_matrix_sequence(params::P3_MAT_ROWS, params::P3_MAT_COLS, 3);
+        let p3_mats = create_test_triangular_sequence(params::P3_MAT_ROWS, 3);
         let result = encode_p3(&p3_mats);
         assert!(result.is_ok());
         let bytes = result.unwrap();
@@ -495,4 +763,196 @@ mod tests {
         let expected_bytes = expected_elements * (padded_m / 2);
         assert_eq!(bytes.len(), expected_bytes);
     }
+
+    // m matrices of size r x c with varied entries; the strict lower triangle is zero if triangular
+    fn synthetic_matrices(m: usize, r: usize, c: usize, is_triangular: bool, salt: usize) -> Vec<Matrix> {
+        (0..m)
+            .map(|a| {
+                let mut mat = Matrix::zero(r, c);
+                for i in 0..r {
+                    for j in 0..c {
+                        if !is_triangular || j >= i {
+                            mat.set(i, j, F16::new(((a * 7 + i * 5 + j * 3 + salt) % 16) as u8)).unwrap();
+                        }
+                    }
+                }
+                mat
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_decode_bitsliced_vector_roundtrip() {
+        let v = f16v(&[1, 5, 2, 8, 10, 3, 7, 0, 15, 4, 11, 6, 1, 9, 13, 2]);
+        let encoded = encode_bitsliced_vector(&v).unwrap();
+        assert_eq!(decode_bitsliced_vector(16, &encoded).unwrap(), v);
+
+        // Padded length: 7 elements occupy 4 bytes
+        let v7 = f16v(&[1, 2, 3, 4, 5, 6, 7]);
+        let encoded7 = encode_bitsliced_vector(&v7).unwrap();
+        assert_eq!(decode_bitsliced_vector(7, &encoded7).unwrap(), v7);
+
+        assert!(decode_bitsliced_vector(16, &encoded[1..]).is_err());
+    }
+
+    #[test]
+    fn test_decode_bitsliced_matrices_roundtrip() {
+        let tri = synthetic_matrices(8, 3, 3, true, 1);
+        let encoded = encode_bitsliced_matrices(&tri, 3, 3, true).unwrap();
+        assert_eq!(decode_bitsliced_matrices(&encoded, 8, 3, 3, true).unwrap(), tri);
+
+        let rect = synthetic_matrices(10, 2, 3, false, 4);
+        let encoded = encode_bitsliced_matrices(&rect, 2, 3, false).unwrap();
+        assert_eq!(decode_bitsliced_matrices(&encoded, 10, 2, 3, false).unwrap(), rect);
+
+        assert!(decode_bitsliced_matrices(&encoded, 10, 3, 3, false).is_err());
+        assert!(decode_bitsliced_matrices(&encoded, 10, 2, 3, true).is_err());
+    }
+
+    #[cfg(feature = "std")]
+    fn check_epk_roundtrip<P: MayoParams>() {
+        let m = P::M_PARAM;
+        let p1 = synthetic_matrices(m, P::P1_MAT_ROWS, P::P1_MAT_COLS, true, 0);
+        let p2 = synthetic_matrices(m, P::P2_MAT_ROWS, P::P2_MAT_COLS, false, 1);
+        let p3 = synthetic_matrices(m, P::P3_MAT_ROWS, P::P3_MAT_COLS, true, 2);
+
+        let epk = encode_epk::<P>(&p1, &p2, &p3).unwrap();
+        assert_eq!(epk.len(), P::EPK_BYTES, "{}", P::name());
+
+        let (d1, d2, d3) = decode_epk::<P>(&epk).unwrap();
+        assert!(d1 == p1 && d2 == p2 && d3 == p3, "{}", P::name());
+
+        assert!(decode_epk::<P>(&epk[1..]).is_err());
+        assert!(encode_epk::<P>(&p1[1..], &p2, &p3).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_epk_roundtrip_all_sets() {
+        check_epk_roundtrip::<crate::params::Mayo1>();
+        #[cfg(feature = "mayo2")]
+        check_epk_roundtrip::<crate::params::Mayo2>();
+        #[cfg(feature = "mayo3")]
+        check_epk_roundtrip::<crate::params::Mayo3>();
+        #[cfg(feature = "mayo5")]
+        check_epk_roundtrip::<crate::params::Mayo5>();
+    }
+
+    // decode_epk reads what crypto::expand_pk writes, and encode_epk writes it back unchanged
+    #[cfg(feature = "std")]
+    fn check_epk_matches_expand_pk<P: MayoParams>() {
+        let (_, cpk) = crate::crypto::generate_keypair_from_seed::<P>(&vec![0x57u8; P::SK_SEED_BYTES]).unwrap();
+        let epk = crate::crypto::expand_pk::<P>(&cpk).unwrap();
+
+        let (p1, p2, p3) = decode_epk::<P>(&epk).unwrap();
+        let expanded = crate::crypto::PMatrices::<P>::from_compact_pk(&cpk).unwrap();
+        let (e1, e2, e3) = expanded.as_slices();
+        assert_eq!(flatten_matrices(&p1, true), e1, "{}", P::name());
+        assert_eq!(flatten_matrices(&p2, false), e2, "{}", P::name());
+        assert_eq!(flatten_matrices(&p3, true), e3, "{}", P::name());
+
+        assert!(encode_epk::<P>(&p1, &p2, &p3).unwrap() == epk, "{}", P::name());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_epk_matches_expand_pk() {
+        check_epk_matches_expand_pk::<crate::params::Mayo1>();
+        #[cfg(feature = "mayo2")]
+        check_epk_matches_expand_pk::<crate::params::Mayo2>();
+        #[cfg(feature = "mayo3")]
+        check_epk_matches_expand_pk::<crate::params::Mayo3>();
+        #[cfg(feature = "mayo5")]
+        check_epk_matches_expand_pk::<crate::params::Mayo5>();
+    }
+
+    #[test]
+    fn test_bitsliced_entries_overflow_mayo1_epk() {
+        // Why the expanded keys are nibble-packed: one bitsliced m-vector takes a byte more
+        // than the per-entry share of P1_BYTES when m is not a multiple of 8
+        use crate::params::Mayo1;
+        let entry = encode_bitsliced_vector(&[F16::new(0); Mayo1::M_PARAM]).unwrap();
+        assert_eq!(entry.len(), 40);
+        assert_eq!(Mayo1::P1_BYTES / Mayo1::P1_ELEMS_PER_MATRIX, 39);
+    }
+
+    #[cfg(feature = "std")]
+    fn check_esk_roundtrip<P: MayoParams>() {
+        let m = P::M_PARAM;
+        let v = P::N_PARAM - P::O_PARAM;
+        let seed: Vec<u8> = (0..P::SK_SEED_BYTES as u8).collect();
+        let o = synthetic_matrices(1, v, P::O_PARAM, false, 3).remove(0);
+        let p1 = synthetic_matrices(m, P::P1_MAT_ROWS, P::P1_MAT_COLS, true, 5);
+        let l = synthetic_matrices(m, P::L_MAT_ROWS, P::L_MAT_COLS, false, 6);
+
+        let esk = encode_esk::<P>(&seed, &o, &p1, &l).unwrap();
+        assert_eq!(esk.len(), P::ESK_BYTES, "{}", P::name());
+
+        let decoded = decode_esk::<P>(&esk).unwrap();
+        assert!(decoded == (seed.clone(), o.clone(), p1.clone(), l.clone()), "{}", P::name());
+
+        assert!(decode_esk::<P>(&esk[..esk.len() - 1]).is_err());
+        assert!(encode_esk::<P>(&seed[1..], &o, &p1, &l).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_esk_roundtrip_all_sets() {
+        check_esk_roundtrip::<crate::params::Mayo1>();
+        #[cfg(feature = "mayo2")]
+        check_esk_roundtrip::<crate::params::Mayo2>();
+        #[cfg(feature = "mayo3")]
+        check_esk_roundtrip::<crate::params::Mayo3>();
+        #[cfg(feature = "mayo5")]
+        check_esk_roundtrip::<crate::params::Mayo5>();
+    }
+
+    // decode_esk reads what crypto::expand_sk writes, and encode_esk writes it back unchanged
+    #[cfg(feature = "std")]
+    fn check_esk_matches_expand_sk<P: MayoParams>() {
+        let sk = vec![0x3Cu8; P::SK_SEED_BYTES];
+        let esk = crate::crypto::expand_sk::<P>(&sk).unwrap();
+
+        let (seed, o, p1, l) = decode_esk::<P>(&esk).unwrap();
+        assert_eq!(seed, sk);
+        // O and P1 are the ones keygen derives from sk_seed
+        let expanded = crate::crypto::shake256_digest(&sk, P::PK_SEED_BYTES + P::O_BYTES);
+        assert_eq!(o.encode_o(), &expanded[P::PK_SEED_BYTES..], "{}", P::name());
+        let matrices = crate::crypto::expand_matrices::<P>(&expanded[..P::PK_SEED_BYTES]).unwrap();
+        assert_eq!(flatten_matrices(&p1, true), matrices.as_slices().0, "{}", P::name());
+
+        assert!(encode_esk::<P>(&seed, &o, &p1, &l).unwrap() == esk, "{}", P::name());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_esk_matches_expand_sk() {
+        check_esk_matches_expand_sk::<crate::params::Mayo1>();
+        #[cfg(feature = "mayo2")]
+        check_esk_matches_expand_sk::<crate::params::Mayo2>();
+        #[cfg(feature = "mayo3")]
+        check_esk_matches_expand_sk::<crate::params::Mayo3>();
+        #[cfg(feature = "mayo5")]
+        check_esk_matches_expand_sk::<crate::params::Mayo5>();
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_streaming_encoder_matches_in_memory() {
+        for (m, r, c, tri) in [(8, 3, 3, true), (10, 2, 5, false), (1, 1, 1, false)] {
+            let matrices = synthetic_matrices(m, r, c, tri, 2);
+            let mut sink: Vec<u8> = Vec::new();
+            encode_bitsliced_matrices_to(&mut sink, &matrices, r, c, tri).unwrap();
+            assert_eq!(sink, encode_bitsliced_matrices(&matrices, r, c, tri).unwrap());
+        }
+
+        let p3 = synthetic_matrices(params::M_PARAM, params::P3_MAT_ROWS, params::P3_MAT_COLS, true, 7);
+        let mut sink = Vec::new();
+        encode_p3_to(&mut sink, &p3).unwrap();
+        assert_eq!(sink, encode_p3(&p3).unwrap());
+
+        let bad = vec![Matrix::zero(2, 3)];
+        let err = encode_bitsliced_matrices_to(&mut Vec::new(), &bad, 3, 3, false).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    }
 }
@@ -37,6 +37,38 @@ impl F16 {
         let a8 = a4 * a4;
         Some(a8 * a4 * a2)
     }
+
+    // a when mask is 0xFF, b when mask is 0x00, without branching on the mask.
+    // Other mask values mix the bits of a and b.
+    pub fn select(mask: u8, a: F16, b: F16) -> F16 {
+        F16::new((a.0 & mask) | (b.0 & !mask))
+    }
+
+    // 0xFF if self is zero, 0x00 otherwise, without branching on the value
+    pub fn ct_is_zero(self) -> u8 {
+        // For a nibble x, x - 1 borrows out of the low byte exactly when x == 0
+        ((self.0 as u16).wrapping_sub(1) >> 8) as u8
+    }
+
+    // self / rhs, or None when rhs is zero
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        rhs.inverse().map(|inv| self * inv)
+    }
+
+    // self^exp by square-and-multiply; 0^0 is 1
+    pub fn pow(&self, exp: u32) -> Self {
+        let mut result = F16::new(1);
+        let mut base = *self;
+        let mut exp = exp;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = result * base;
+            }
+            base = base * base;
+            exp >>= 1;
+        }
+        result
+    }
 }
 
 impl Add for F16 {
@@ -88,16 +120,30 @@ impl Div for F16 {
     type Output = Self;
 
     fn div(self, rhs: Self) -> Self::Output {
-        if rhs.0 == 0 {
-            panic!("Division by zero in F16");
-        }
         // Division in finite field: a / b = a * b^(-1)
-        if let Some(inv) = rhs.inverse() {
-            self * inv
-        } else {
-            panic!("Division by zero in F16");
+        self.checked_div(rhs).expect("Division by zero in F16")
+    }
+}
+
+// Polynomials in one GF(16) indeterminate, stored low degree first as in poly.rs
+
+// coeffs(x) by Horner's method; the empty polynomial is zero
+pub fn poly_eval(coeffs: &[F16], x: F16) -> F16 {
+    coeffs.iter().rev().fold(F16::new(0), |acc, &c| acc * x + c)
+}
+
+// Schoolbook product a * b, of length a.len() + b.len() - 1 (empty if either is empty)
+pub fn poly_mul(a: &[F16], b: &[F16]) -> Vec<F16> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    let mut product = vec![F16::new(0); a.len() + b.len() - 1];
+    for (i, &ai) in a.iter().enumerate() {
+        for (j, &bj) in b.iter().enumerate() {
+            product[i + j] = product[i + j] + ai * bj;
         }
     }
+    product
 }
 
 #[cfg(test)]
@@ -193,6 +239,63 @@ mod tests {
         assert_eq!(F16::new(0).inverse(), None, "Inverse of 0");
     }
 
+    #[test]
+    fn test_f16_field_axioms_exhaustive() {
+        let elements: Vec<F16> = (0..16).map(F16::new).collect();
+        for &a in &elements {
+            for &b in &elements {
+                assert_eq!(a * b, b * a, "commutativity for {:?}, {:?}", a, b);
+                for &c in &elements {
+                    assert_eq!((a * b) * c, a * (b * c), "associativity for {:?}, {:?}, {:?}", a, b, c);
+                    assert_eq!(a * (b + c), a * b + a * c, "distributivity for {:?}, {:?}, {:?}", a, b, c);
+                }
+            }
+            if a != F16::new(0) {
+                assert_eq!(a * a.inverse().unwrap(), F16::new(1), "inverse of {:?}", a);
+            }
+        }
+    }
+
+    #[test]
+    fn test_f16_select_and_ct_is_zero() {
+        for i in 0..16u8 {
+            let a = F16::new(i);
+            assert_eq!(a.ct_is_zero(), if i == 0 { 0xFF } else { 0x00 });
+            for j in 0..16u8 {
+                let b = F16::new(j);
+                assert_eq!(F16::select(0xFF, a, b), a);
+                assert_eq!(F16::select(0x00, a, b), b);
+                assert_eq!(F16::select(b.ct_is_zero(), a, b), if j == 0 { a } else { b });
+            }
+        }
+    }
+
+    #[test]
+    fn test_f16_checked_div() {
+        for a in 0..16u8 {
+            assert_eq!(F16::new(a).checked_div(F16::new(0)), None);
+            for b in 1..16u8 {
+                assert_eq!(F16::new(a).checked_div(F16::new(b)), Some(F16::new(a) / F16::new(b)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_f16_pow() {
+        for i in 0..16u8 {
+            let a = F16::new(i);
+            let mut expected = F16::new(1);
+            for exp in 0..40u32 {
+                assert_eq!(a.pow(exp), expected, "F16({})^{}", i, exp);
+                expected = expected * a;
+            }
+        }
+        // a^14 is the inverse of every non-zero a
+        for i in 1..16u8 {
+            assert_eq!(Some(F16::new(i).pow(14)), F16::new(i).inverse());
+        }
+    }
+
     #[test]
     fn test_f16_encode_decode() {
         for i in 0..16u8 {
@@ -205,4 +308,42 @@ mod tests {
         let decoded_masked = F16::decode_f16(0xAB);
         assert_eq!(decoded_masked, F16::new(0x0B), "Masking during decode failed");
     }
+
+    fn poly(vals: &[u8]) -> Vec<F16> {
+        vals.iter().map(|&x| F16::new(x)).collect()
+    }
+
+    #[test]
+    fn test_poly_eval() {
+        // 1 + 2z + 3z^2 at z = 2: 1 + 4 + 3*4 = 1 + 4 + 12 = 9
+        assert_eq!(poly_eval(&poly(&[1, 2, 3]), F16::new(2)), F16::new(9));
+        // z^4 at z = 2 is x^4 = x + 1
+        assert_eq!(poly_eval(&poly(&[0, 0, 0, 0, 1]), F16::new(2)), F16::new(3));
+        // z^15 is 1 at every non-zero point
+        let mut z15 = vec![F16::new(0); 16];
+        z15[15] = F16::new(1);
+        for i in 1..16u8 {
+            assert_eq!(poly_eval(&z15, F16::new(i)), F16::new(1));
+        }
+        assert_eq!(poly_eval(&[], F16::new(5)), F16::new(0));
+        assert_eq!(poly_eval(&poly(&[7]), F16::new(0)), F16::new(7));
+    }
+
+    #[test]
+    fn test_poly_mul() {
+        // (1 + z)^2 = 1 + z^2 in characteristic 2
+        assert_eq!(poly_mul(&poly(&[1, 1]), &poly(&[1, 1])), poly(&[1, 0, 1]));
+        // (2 + 3z)(1 + 2z) = 2 + (4 + 3)z + 6z^2 = 2 + 7z + 6z^2
+        assert_eq!(poly_mul(&poly(&[2, 3]), &poly(&[1, 2])), poly(&[2, 7, 6]));
+        assert!(poly_mul(&[], &poly(&[1, 2])).is_empty());
+
+        // Evaluation is a ring homomorphism
+        let a = poly(&[3, 0, 9, 14]);
+        let b = poly(&[5, 11, 1]);
+        let product = poly_mul(&a, &b);
+        for i in 0..16u8 {
+            let x = F16::new(i);
+            assert_eq!(poly_eval(&product, x), poly_eval(&a, x) * poly_eval(&b, x));
+        }
+    }
 }
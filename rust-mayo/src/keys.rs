@@ -0,0 +1,451 @@
+// rust-mayo/src/keys.rs
+use crate::crypto::{
+    check_signature_with_p3, generate_keypair_from_seed, generate_keypair_generic, is_keypair, sign_generic,
+    verify_generic, CryptoError, PMatrices,
+};
+use crate::f16::F16;
+use crate::params::MayoParams;
+use std::fmt;
+use std::fs;
+use std::marker::PhantomData;
+use std::path::Path;
+use zeroize::Zeroize;
+
+// Bytes shown at each end of a truncated Display
+const DISPLAY_EDGE_BYTES: usize = 4;
+
+// Defines a byte wrapper whose length is fixed by the parameter set P.
+// Clone/PartialEq are implemented by hand so P itself needs no derives.
+macro_rules! typed_bytes {
+    ($name:ident, $len:ident, $length_error:expr) => {
+        pub struct $name<P: MayoParams> {
+            bytes: Vec<u8>,
+            _params: PhantomData<P>,
+        }
+
+        impl<P: MayoParams> $name<P> {
+            // Wraps raw bytes, checking the length against P
+            pub fn from_bytes(bytes: &[u8]) -> Result<Self, CryptoError> {
+                if bytes.len() != P::$len {
+                    return Err($length_error);
+                }
+                Ok($name { bytes: bytes.to_vec(), _params: PhantomData })
+            }
+
+            pub fn as_bytes(&self) -> &[u8] {
+                &self.bytes
+            }
+
+            pub fn into_bytes(mut self) -> Vec<u8> {
+                std::mem::take(&mut self.bytes)
+            }
+
+            // Full lowercase hex encoding
+            pub fn to_hex(&self) -> String {
+                hex::encode(&self.bytes)
+            }
+
+            pub fn from_hex(s: &str) -> Result<Self, CryptoError> {
+                let bytes = hex::decode(s.trim()).map_err(|e| CryptoError::InvalidEncoding(Box::new(e)))?;
+                Self::from_bytes(&bytes)
+            }
+
+            // Standard (padded) base64 encoding
+            #[cfg(feature = "base64")]
+            pub fn to_base64(&self) -> String {
+                use base64::Engine;
+                base64::engine::general_purpose::STANDARD.encode(&self.bytes)
+            }
+
+            #[cfg(feature = "base64")]
+            pub fn from_base64(s: &str) -> Result<Self, CryptoError> {
+                use base64::Engine;
+                let bytes = base64::engine::general_purpose::STANDARD
+                    .decode(s.trim())
+                    .map_err(|e| CryptoError::InvalidEncoding(Box::new(e)))?;
+                Self::from_bytes(&bytes)
+            }
+        }
+
+        impl<P: MayoParams> Clone for $name<P> {
+            fn clone(&self) -> Self {
+                $name { bytes: self.bytes.clone(), _params: PhantomData }
+            }
+        }
+
+        // Plain byte comparison that stops at the first difference, so it is not constant-time;
+        // compare signatures with ct_eq (subtle feature) where timing matters
+        impl<P: MayoParams> PartialEq for $name<P> {
+            fn eq(&self, other: &Self) -> bool {
+                self.bytes == other.bytes
+            }
+        }
+
+        impl<P: MayoParams> Eq for $name<P> {}
+
+        impl<P: MayoParams> AsRef<[u8]> for $name<P> {
+            fn as_ref(&self) -> &[u8] {
+                &self.bytes
+            }
+        }
+
+        impl<P: MayoParams> fmt::Debug for $name<P> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                fmt::Display::fmt(self, f)
+            }
+        }
+    };
+}
+
+typed_bytes!(PublicKey, CPK_BYTES, CryptoError::InvalidKeyLength);
+typed_bytes!(SecretKey, CSK_BYTES, CryptoError::InvalidKeyLength);
+typed_bytes!(Signature, SIG_BYTES, CryptoError::InvalidEncodingLength);
+
+// "<first bytes>…<last bytes>" in hex, or the whole value if it is short
+fn truncated_hex(bytes: &[u8]) -> String {
+    if bytes.len() <= 2 * DISPLAY_EDGE_BYTES {
+        return hex::encode(bytes);
+    }
+    format!(
+        "{}…{}",
+        hex::encode(&bytes[..DISPLAY_EDGE_BYTES]),
+        hex::encode(&bytes[bytes.len() - DISPLAY_EDGE_BYTES..])
+    )
+}
+
+impl<P: MayoParams> fmt::Display for PublicKey<P> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} PublicKey({} bytes): {}", P::name(), self.bytes.len(), truncated_hex(&self.bytes))
+    }
+}
+
+impl<P: MayoParams> fmt::Display for Signature<P> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} Signature({} bytes): {}", P::name(), self.bytes.len(), truncated_hex(&self.bytes))
+    }
+}
+
+// Constant-time comparison of the raw bytes, for applications that compare signatures (for
+// example when deduplicating) and must not leak where two of them first differ
+#[cfg(feature = "subtle")]
+impl<P: MayoParams> subtle::ConstantTimeEq for Signature<P> {
+    fn ct_eq(&self, other: &Self) -> subtle::Choice {
+        self.bytes.as_slice().ct_eq(other.bytes.as_slice())
+    }
+}
+
+impl<P: MayoParams> SecretKey<P> {
+    // sign_generic with typed input and output
+    pub fn sign(&self, message: &[u8]) -> Result<Signature<P>, CryptoError> {
+        Signature::from_bytes(&sign_generic::<P>(&self.bytes, message)?)
+    }
+}
+
+impl<P: MayoParams> PublicKey<P> {
+    // verify_generic with typed input
+    pub fn verify(&self, message: &[u8], signature: &Signature<P>) -> Result<bool, CryptoError> {
+        verify_generic::<P>(&self.bytes, message, signature.as_bytes())
+    }
+}
+
+// A compact public key held in expanded form for verifying many signatures: P1 and P2
+// expanded from pk_seed and P3 decoded from the key, once. This trades memory for speed:
+// one byte per coefficient, M_PARAM * (P1 + P2 + P3 elements per matrix) in all (about
+// 234 KiB for MAYO-1 against a 1420-byte compact key), in exchange for skipping the seed
+// expansion and P3 decoding that PublicKey::verify repeats on every call.
+pub struct ExpandedPublicKey<P: MayoParams> {
+    matrices: PMatrices<P>,
+}
+
+impl<P: MayoParams> ExpandedPublicKey<P> {
+    // Rejects a P3 region that validate_public_key rejects
+    pub fn from_compact(public_key: &PublicKey<P>) -> Result<Self, CryptoError> {
+        Ok(ExpandedPublicKey { matrices: PMatrices::from_compact_pk(public_key.as_bytes())? })
+    }
+
+    // The decoded P3 in expand_matrices' flat layout
+    pub fn p3(&self) -> &[F16] {
+        self.matrices.as_slices().2
+    }
+
+    pub fn verify(&self, message: &[u8], signature: &Signature<P>) -> Result<bool, CryptoError> {
+        self.verify_with_p3(message, signature, self.p3())
+    }
+
+    // verify with a caller-supplied decoded P3 (M_PARAM * P3_ELEMS_PER_MATRIX elements, as
+    // p3() returns them) in place of the retained one; only P1 and P2 come from self
+    pub fn verify_with_p3(&self, message: &[u8], signature: &Signature<P>, p3_matrices: &[F16]) -> Result<bool, CryptoError> {
+        if p3_matrices.len() != P::M_PARAM * P::P3_ELEMS_PER_MATRIX {
+            return Err(CryptoError::MatrixError);
+        }
+        let mut evaluation = Vec::with_capacity(P::M_PARAM);
+        Ok(check_signature_with_p3::<P>(message, signature.as_bytes(), &self.matrices, p3_matrices, &mut evaluation))
+    }
+}
+
+// Adapters for code written against the generic signature traits. Failures to sign and
+// verify errors carry the CryptoError as their source; a signature that simply does not
+// verify is a bare signature::Error.
+#[cfg(feature = "signature")]
+impl<P: MayoParams> signature::Signer<Signature<P>> for SecretKey<P> {
+    fn try_sign(&self, msg: &[u8]) -> Result<Signature<P>, signature::Error> {
+        self.sign(msg).map_err(signature::Error::from_source)
+    }
+}
+
+#[cfg(feature = "signature")]
+impl<P: MayoParams> signature::Verifier<Signature<P>> for PublicKey<P> {
+    fn verify(&self, msg: &[u8], signature: &Signature<P>) -> Result<(), signature::Error> {
+        match PublicKey::verify(self, msg, signature) {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(signature::Error::new()),
+            Err(e) => Err(signature::Error::from_source(e)),
+        }
+    }
+}
+
+// Secret key bytes are wiped when the key is dropped
+impl<P: MayoParams> Drop for SecretKey<P> {
+    fn drop(&mut self) {
+        self.bytes.zeroize();
+    }
+}
+
+// Secret key material is never printed, not even partially; use to_hex() explicitly
+impl<P: MayoParams> fmt::Display for SecretKey<P> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} SecretKey({} bytes): <redacted>", P::name(), self.bytes.len())
+    }
+}
+
+// File names KeyPair::save writes and KeyPair::load reads, holding the raw key bytes
+pub const SECRET_KEY_FILE: &str = "mayo.sk";
+pub const PUBLIC_KEY_FILE: &str = "mayo.pk";
+
+// A secret key together with its compact public key
+pub struct KeyPair<P: MayoParams> {
+    pub secret: SecretKey<P>,
+    pub public: PublicKey<P>,
+}
+
+impl<P: MayoParams> KeyPair<P> {
+    // Fresh key pair from OS randomness
+    pub fn generate() -> Result<Self, CryptoError> {
+        let (sk, pk) = generate_keypair_generic::<P>()?;
+        Self::from_parts(&sk, &pk)
+    }
+
+    // Deterministic key pair from an SK_SEED_BYTES secret seed
+    pub fn from_seed(seed: &[u8]) -> Result<Self, CryptoError> {
+        let (sk, pk) = generate_keypair_from_seed::<P>(seed)?;
+        Self::from_parts(&sk, &pk)
+    }
+
+    fn from_parts(sk: &[u8], pk: &[u8]) -> Result<Self, CryptoError> {
+        Ok(KeyPair { secret: SecretKey::from_bytes(sk)?, public: PublicKey::from_bytes(pk)? })
+    }
+
+    // Writes SECRET_KEY_FILE and PUBLIC_KEY_FILE into `dir`, which must exist. On Unix the
+    // secret key file is created readable by its owner only.
+    pub fn save(&self, dir: impl AsRef<Path>) -> Result<(), CryptoError> {
+        let dir = dir.as_ref();
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        std::io::Write::write_all(&mut options.open(dir.join(SECRET_KEY_FILE))?, self.secret.as_bytes())?;
+        fs::write(dir.join(PUBLIC_KEY_FILE), self.public.as_bytes())?;
+        Ok(())
+    }
+
+    // Reads a pair written by save, rejecting files of the wrong length and keys that do not
+    // belong together (KeyPairMismatch)
+    pub fn load(dir: impl AsRef<Path>) -> Result<Self, CryptoError> {
+        let dir = dir.as_ref();
+        let sk = zeroize::Zeroizing::new(fs::read(dir.join(SECRET_KEY_FILE))?);
+        let pk = fs::read(dir.join(PUBLIC_KEY_FILE))?;
+        let pair = Self::from_parts(&sk, &pk)?;
+        if !is_keypair::<P>(pair.secret.as_bytes(), pair.public.as_bytes())? {
+            return Err(CryptoError::KeyPairMismatch);
+        }
+        Ok(pair)
+    }
+}
+
+#[cfg(all(test, feature = "mayo1"))]
+mod tests {
+    use super::*;
+    use crate::params::Mayo1;
+
+    fn counting_bytes(len: usize) -> Vec<u8> {
+        (0..len).map(|i| i as u8).collect()
+    }
+
+    #[test]
+    fn test_hex_roundtrip() {
+        let pk = PublicKey::<Mayo1>::from_bytes(&counting_bytes(Mayo1::CPK_BYTES)).unwrap();
+        assert_eq!(PublicKey::<Mayo1>::from_hex(&pk.to_hex()).unwrap(), pk);
+
+        let sk = SecretKey::<Mayo1>::from_bytes(&counting_bytes(Mayo1::CSK_BYTES)).unwrap();
+        assert_eq!(SecretKey::<Mayo1>::from_hex(&sk.to_hex()).unwrap(), sk);
+
+        let sig = Signature::<Mayo1>::from_bytes(&counting_bytes(Mayo1::SIG_BYTES)).unwrap();
+        assert_eq!(Signature::<Mayo1>::from_hex(&sig.to_hex()).unwrap(), sig);
+    }
+
+    #[test]
+    fn test_from_hex_rejects_bad_input() {
+        let short = hex::encode(counting_bytes(Mayo1::CPK_BYTES - 1));
+        assert!(matches!(PublicKey::<Mayo1>::from_hex(&short), Err(CryptoError::InvalidKeyLength)));
+        assert!(matches!(SecretKey::<Mayo1>::from_hex("zz"), Err(CryptoError::InvalidEncoding(_))));
+
+        let long = hex::encode(counting_bytes(Mayo1::SIG_BYTES + 1));
+        assert!(matches!(Signature::<Mayo1>::from_hex(&long), Err(CryptoError::InvalidEncodingLength)));
+    }
+
+    #[test]
+    fn test_parse_failure_keeps_source() {
+        use std::error::Error;
+        let err = PublicKey::<Mayo1>::from_hex("not hex").unwrap_err();
+        let source = err.source().expect("parse failures carry their cause");
+        assert!(source.downcast_ref::<hex::FromHexError>().is_some());
+    }
+
+    #[test]
+    #[cfg(feature = "base64")]
+    fn test_base64_roundtrip() {
+        let pk = PublicKey::<Mayo1>::from_bytes(&counting_bytes(Mayo1::CPK_BYTES)).unwrap();
+        assert_eq!(PublicKey::<Mayo1>::from_base64(&pk.to_base64()).unwrap(), pk);
+
+        let sk = SecretKey::<Mayo1>::from_bytes(&counting_bytes(Mayo1::CSK_BYTES)).unwrap();
+        assert_eq!(SecretKey::<Mayo1>::from_base64(&sk.to_base64()).unwrap(), sk);
+
+        let sig = Signature::<Mayo1>::from_bytes(&counting_bytes(Mayo1::SIG_BYTES)).unwrap();
+        assert_eq!(Signature::<Mayo1>::from_base64(&sig.to_base64()).unwrap(), sig);
+    }
+
+    #[test]
+    #[cfg(feature = "base64")]
+    fn test_from_base64_rejects_bad_input() {
+        assert!(matches!(SecretKey::<Mayo1>::from_base64("not base64!"), Err(CryptoError::InvalidEncoding(_))));
+
+        let sk = SecretKey::<Mayo1>::from_bytes(&counting_bytes(Mayo1::CSK_BYTES)).unwrap();
+        let truncated = &sk.to_base64()[..8];
+        assert!(matches!(SecretKey::<Mayo1>::from_base64(truncated), Err(CryptoError::InvalidKeyLength)));
+    }
+
+    #[test]
+    #[cfg(feature = "subtle")]
+    fn test_signature_ct_eq_agrees_with_eq() {
+        use subtle::ConstantTimeEq;
+        let a = Signature::<Mayo1>::from_bytes(&counting_bytes(Mayo1::SIG_BYTES)).unwrap();
+        let mut bytes = counting_bytes(Mayo1::SIG_BYTES);
+        bytes[Mayo1::SIG_BYTES - 1] ^= 1;
+        let b = Signature::<Mayo1>::from_bytes(&bytes).unwrap();
+        for (x, y) in [(&a, &a.clone()), (&a, &b), (&b, &b)] {
+            assert_eq!(bool::from(x.ct_eq(y)), x == y);
+        }
+        assert!(!bool::from(a.ct_eq(&b)));
+    }
+
+    // Fresh directory under the system temp dir, removed by the caller
+    fn scratch_dir(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("rust-mayo-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_keypair_save_load_roundtrip() {
+        let dir = scratch_dir("keypair-roundtrip");
+        let pair = KeyPair::<Mayo1>::generate().unwrap();
+        pair.save(&dir).unwrap();
+        let loaded = KeyPair::<Mayo1>::load(&dir).unwrap();
+        assert!(loaded.secret == pair.secret && loaded.public == pair.public);
+
+        let seeded = KeyPair::<Mayo1>::from_seed(&[7u8; 24]).unwrap();
+        assert!(seeded.public == KeyPair::<Mayo1>::from_seed(&[7u8; 24]).unwrap().public);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_keypair_load_rejects_mismatch() {
+        let dir = scratch_dir("keypair-mismatch");
+        let a = KeyPair::<Mayo1>::from_seed(&[1u8; 24]).unwrap();
+        let b = KeyPair::<Mayo1>::from_seed(&[2u8; 24]).unwrap();
+        KeyPair { secret: a.secret, public: b.public }.save(&dir).unwrap();
+        assert!(matches!(KeyPair::<Mayo1>::load(&dir), Err(CryptoError::KeyPairMismatch)));
+
+        fs::write(dir.join(PUBLIC_KEY_FILE), [0u8; 3]).unwrap();
+        assert!(matches!(KeyPair::<Mayo1>::load(&dir), Err(CryptoError::InvalidKeyLength)));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_keypair_signs_after_load() {
+        let dir = scratch_dir("keypair-sign");
+        KeyPair::<Mayo1>::generate().unwrap().save(&dir).unwrap();
+        let loaded = KeyPair::<Mayo1>::load(&dir).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        let sig = crate::crypto::sign_generic::<Mayo1>(loaded.secret.as_bytes(), b"saved").unwrap();
+        assert!(crate::crypto::verify_generic::<Mayo1>(loaded.public.as_bytes(), b"saved", &sig).unwrap());
+    }
+
+    #[test]
+    #[cfg(feature = "signature")]
+    fn test_signature_traits_match_inherent_methods() {
+        use signature::{Signer, Verifier};
+        use crate::test_support::SignMayo;
+
+        let pair = KeyPair::<SignMayo>::from_seed(&[3u8; 24]).unwrap();
+        let signer: &dyn Signer<Signature<SignMayo>> = &pair.secret;
+        let verifier: &dyn Verifier<Signature<SignMayo>> = &pair.public;
+
+        let inherent = pair.secret.sign(b"traits").unwrap();
+        let via_trait = signer.try_sign(b"traits").unwrap();
+        assert!(pair.public.verify(b"traits", &inherent).unwrap());
+        assert!(verifier.verify(b"traits", &via_trait).is_ok());
+        assert!(verifier.verify(b"other", &via_trait).is_err());
+
+        let forged = Signature::<SignMayo>::from_bytes(&counting_bytes(SignMayo::SIG_BYTES)).unwrap();
+        let inherent = pair.public.verify(b"traits", &forged).unwrap();
+        assert_eq!(verifier.verify(b"traits", &forged).is_ok(), inherent);
+    }
+
+    #[test]
+    fn test_expanded_public_key_matches_verify() {
+        use crate::crypto::{expand_pk, expanded_to_compact_pk};
+        // verify_generic expands P3 from pk_seed, so give the key exactly that P3
+        let pk_seed = [0x42u8; Mayo1::PK_SEED_BYTES];
+        let seeded = [&pk_seed[..], &[0u8; Mayo1::CPK_BYTES - Mayo1::PK_SEED_BYTES]].concat();
+        let cpk = expanded_to_compact_pk::<Mayo1>(&pk_seed, &expand_pk::<Mayo1>(&seeded).unwrap()).unwrap();
+        let pk = PublicKey::<Mayo1>::from_bytes(&cpk).unwrap();
+        let expanded = ExpandedPublicKey::from_compact(&pk).unwrap();
+
+        for fill in [0x00u8, 0x11, 0x5a, 0xff] {
+            let sig = Signature::<Mayo1>::from_bytes(&vec![fill; Mayo1::SIG_BYTES]).unwrap();
+            let standard = pk.verify(b"expanded", &sig).unwrap_or(false);
+            assert_eq!(expanded.verify(b"expanded", &sig).unwrap(), standard);
+            assert_eq!(expanded.verify_with_p3(b"expanded", &sig, expanded.p3()).unwrap(), standard);
+        }
+
+        let sig = Signature::<Mayo1>::from_bytes(&counting_bytes(Mayo1::SIG_BYTES)).unwrap();
+        let short_p3 = &expanded.p3()[1..];
+        assert!(matches!(expanded.verify_with_p3(b"expanded", &sig, short_p3), Err(CryptoError::MatrixError)));
+    }
+
+    #[test]
+    fn test_display_is_truncated() {
+        let pk = PublicKey::<Mayo1>::from_bytes(&counting_bytes(Mayo1::CPK_BYTES)).unwrap();
+        // 1420 bytes: first 00010203, last (1416..1420) % 256 = 88 89 8a 8b
+        assert_eq!(pk.to_string(), "MAYO-1 PublicKey(1420 bytes): 00010203…88898a8b");
+        assert_eq!(format!("{:?}", pk), pk.to_string());
+
+        let sig = Signature::<Mayo1>::from_bytes(&counting_bytes(Mayo1::SIG_BYTES)).unwrap();
+        assert!(sig.to_string().starts_with("MAYO-1 Signature(454 bytes): 00010203…"));
+
+        let sk = SecretKey::<Mayo1>::from_bytes(&counting_bytes(Mayo1::CSK_BYTES)).unwrap();
+        assert_eq!(sk.to_string(), "MAYO-1 SecretKey(24 bytes): <redacted>");
+    }
+}
@@ -2,26 +2,95 @@
 pub mod f16;
 pub mod vector;
 pub mod matrix;
+pub mod mayo_operations;
 pub mod encoding;
 pub mod params; // Add this line
+pub mod poly;
+pub mod prf;
+#[cfg(feature = "std")]
 pub mod crypto; // Add new module for crypto functions
+#[cfg(feature = "std")]
+pub mod keys;
+#[cfg(feature = "std")]
 pub mod wasm_api;
+#[cfg(test)]
+mod test_support;
 
 // Re-export main functions for convenience
+#[cfg(all(feature = "std", feature = "mayo1"))]
 pub use crypto::{generate_keypair, sign, verify};
-pub use params::{Mayo1, Mayo2, Mayo3, Mayo5};
+#[cfg(feature = "mayo1")]
+pub use params::Mayo1;
+#[cfg(feature = "mayo2")]
+pub use params::Mayo2;
+#[cfg(feature = "mayo3")]
+pub use params::Mayo3;
+#[cfg(feature = "mayo5")]
+pub use params::Mayo5;
+
+// Whether signing finds solutions within its attempt budget. The signer solves the oil system
+// A x = t - (vinegar part) for each draw of vinegar values, which the standard parameter
+// sets (k * o >= m) make solvable on almost every attempt.
+pub const SIGNING_IMPLEMENTED: bool = true;
+
+// Which operations are spec-complete in this build, for downstream feature detection. An
+// operation that compiles but is not spec-complete reports false.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Capabilities {
+    // Keygen publishes P3 = Upper(O^T (P1 O + P2)); its byte layout is checked by `kat`
+    pub keygen: bool,
+    // Signatures verify under the matching public key; needs `std` for the OS salt source
+    pub sign: bool,
+    // Verification evaluates the whipped P*(s) = Σ E^l u_ij against the key's P3
+    pub verify: bool,
+    // Reproduces the NIST known-answer files
+    pub kat: bool,
+    // Parameter sets compiled in
+    pub mayo1: bool,
+    pub mayo2: bool,
+    pub mayo3: bool,
+    pub mayo5: bool,
+}
+
+pub const fn capabilities() -> Capabilities {
+    Capabilities {
+        keygen: true,
+        sign: cfg!(feature = "std") && SIGNING_IMPLEMENTED,
+        verify: true,
+        kat: false,
+        mayo1: cfg!(feature = "mayo1"),
+        mayo2: cfg!(feature = "mayo2"),
+        mayo3: cfg!(feature = "mayo3"),
+        mayo5: cfg!(feature = "mayo5"),
+    }
+}
 
 #[cfg(test)]
-mod tests {
+mod capability_tests {
     use super::*;
+
+    #[test]
+    fn test_capabilities_match_features() {
+        let caps = capabilities();
+        assert_eq!(caps.sign, cfg!(feature = "std"));
+        assert_eq!(caps.mayo1, cfg!(feature = "mayo1"));
+        assert_eq!(caps.mayo2, cfg!(feature = "mayo2"));
+        assert_eq!(caps.mayo3, cfg!(feature = "mayo3"));
+        assert_eq!(caps.mayo5, cfg!(feature = "mayo5"));
+        // Independent of the features
+        assert!(caps.keygen && caps.verify && !caps.kat);
+    }
+}
+
+#[cfg(all(test, feature = "std", feature = "mayo1"))]
+mod tests {
     use crate::crypto::test_basic_crypto_operations;
     use crate::params::Mayo1;
 
     #[test]
     fn test_mayo_basic_operations() {
-        match test_basic_crypto_operations::<Mayo1>() {
-            Ok(()) => println!("✅ Basic crypto operations test passed!"),
-            Err(e) => panic!("❌ Basic crypto operations test failed: {}", e),
+        if let Err(e) = test_basic_crypto_operations::<Mayo1>() {
+            panic!("Basic crypto operations test failed: {}", e);
         }
     }
 }
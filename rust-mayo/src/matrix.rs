@@ -1,8 +1,19 @@
 // rust-mayo/src/matrix.rs
 use crate::f16::F16;
 use crate::vector::Vector; // For EncodeO/DecodeO
+use crate::params::MayoParams;
 use std::ops::Add;
+#[cfg(feature = "std")]
+use rand::RngCore;
 
+// Particular solution and null-space basis of a linear system, as Matrix::solution_space
+// returns them
+pub(crate) type SolutionSpace = (Vec<F16>, Vec<Vec<F16>>);
+
+// Elements are stored row-major. benches/matrix_layout.rs compares this with a
+// column-major layout at the P1 sizes: with equivalent slice kernels row-major is about
+// twice as fast, so there is no layout switch. The cost in `&m * &n` is the per-element
+// get/set, not the layout.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Matrix {
     elements: Vec<F16>, // Stored in row-major order
@@ -40,9 +51,22 @@ impl Matrix {
         Matrix { elements, rows: n, cols: n }
     }
 
+    // Creates a rows x cols matrix of uniformly random GF(16) elements
+    #[cfg(feature = "std")]
+    pub fn random<R: RngCore>(rows: usize, cols: usize, rng: &mut R) -> Self {
+        Matrix {
+            elements: crate::vector::random_f16_elements(rows * cols, rng),
+            rows,
+            cols,
+        }
+    }
+
     pub fn rows(&self) -> usize { self.rows }
     pub fn cols(&self) -> usize { self.cols }
 
+    // The elements in row-major order
+    pub fn elements(&self) -> &[F16] { &self.elements }
+
     // Gets an element at (row, col)
     pub fn get(&self, row: usize, col: usize) -> Option<F16> {
         if row < self.rows && col < self.cols {
@@ -62,6 +86,47 @@ impl Matrix {
         }
     }
 
+    // Applies f to every element, keeping the dimensions
+    pub fn map(&self, f: impl Fn(F16) -> F16) -> Matrix {
+        Matrix {
+            elements: self.elements.iter().map(|&x| f(x)).collect(),
+            rows: self.rows,
+            cols: self.cols,
+        }
+    }
+
+    // Combines two same-sized matrices element by element
+    pub fn zip_map(&self, other: &Matrix, f: impl Fn(F16, F16) -> F16) -> Result<Matrix, String> {
+        if self.rows != other.rows || self.cols != other.cols {
+            return Err(format!(
+                "zip_map requires matrices of the same dimensions: {}x{} vs {}x{}",
+                self.rows, self.cols, other.rows, other.cols
+            ));
+        }
+        Ok(Matrix {
+            elements: self.elements.iter().zip(other.elements.iter()).map(|(&a, &b)| f(a, b)).collect(),
+            rows: self.rows,
+            cols: self.cols,
+        })
+    }
+
+    // (row, col, self value, other value) for every cell where two same-sized matrices differ,
+    // in row-major order. Meant for compact test failure messages; panics on a size mismatch.
+    pub fn diff(&self, other: &Matrix) -> Vec<(usize, usize, F16, F16)> {
+        assert!(
+            self.rows == other.rows && self.cols == other.cols,
+            "diff requires matrices of the same dimensions: {}x{} vs {}x{}",
+            self.rows, self.cols, other.rows, other.cols
+        );
+        self.elements
+            .iter()
+            .zip(&other.elements)
+            .enumerate()
+            .filter(|(_, (a, b))| a != b)
+            .map(|(i, (&a, &b))| (i / self.cols, i % self.cols, a, b))
+            .collect()
+    }
+
     // Gets a specific row as a Vector
     pub fn get_row_vec(&self, row_idx: usize) -> Option<Vector> {
         if row_idx < self.rows {
@@ -73,21 +138,126 @@ impl Matrix {
         }
     }
 
-    // Transpose of the matrix
-    pub fn transpose(&self) -> Self {
-        let mut new_elements = vec![F16::new(0); self.rows * self.cols];
+    // Gets a specific column as a Vector
+    pub fn col_vec(&self, col_idx: usize) -> Option<Vector> {
+        if col_idx < self.cols {
+            Some(self.elements.iter().skip(col_idx).step_by(self.cols).copied().collect())
+        } else {
+            None
+        }
+    }
+
+    // The rows, top to bottom, each as a Vector
+    pub fn rows_iter(&self) -> impl Iterator<Item = Vector> + '_ {
+        (0..self.rows).filter_map(move |r| self.get_row_vec(r))
+    }
+
+    // The columns, left to right, each as a Vector
+    pub fn cols_iter(&self) -> impl Iterator<Item = Vector> + '_ {
+        (0..self.cols).filter_map(move |c| self.col_vec(c))
+    }
+
+    // Matrix-vector product: y[i] = sum_j M[i,j] * x[j]
+    pub fn multiply_vector(&self, vector: &Vector) -> Result<Vector, String> {
+        if self.cols != vector.len() {
+            return Err(format!(
+                "Matrix-vector multiplication error: matrix cols ({}) must equal vector length ({})",
+                self.cols, vector.len()
+            ));
+        }
+        let x = vector.elements();
+        let mut result_elements = Vec::with_capacity(self.rows);
         for r in 0..self.rows {
-            for c in 0..self.cols {
-                new_elements[c * self.rows + r] = self.elements[r * self.cols + c];
+            let row = &self.elements[r * self.cols..(r + 1) * self.cols];
+            let mut sum = F16::new(0);
+            for (&a, &b) in row.iter().zip(x.iter()) {
+                sum = sum + a * b;
             }
+            result_elements.push(sum);
         }
+        Ok(Vector::new(result_elements))
+    }
+
+    // Transpose of the matrix
+    pub fn transpose(&self) -> Self {
+        // The rows of the transpose are the columns of self
         Matrix {
-            elements: new_elements,
+            elements: self.cols_iter().flatten().collect(),
             rows: self.cols, // Swapped
             cols: self.rows, // Swapped
         }
     }
 
+    // In-place transpose for square matrices; swaps across the diagonal without allocating
+    pub fn transpose_in_place(&mut self) -> Result<(), String> {
+        if self.rows != self.cols {
+            return Err(format!(
+                "In-place transpose requires a square matrix, got {}x{}",
+                self.rows, self.cols
+            ));
+        }
+        let n = self.rows;
+        for r in 0..n {
+            for c in (r + 1)..n {
+                self.elements.swap(r * n + c, c * n + r);
+            }
+        }
+        Ok(())
+    }
+
+    // Sum of the diagonal elements (square matrices only)
+    pub fn trace(&self) -> Result<F16, String> {
+        if self.rows != self.cols {
+            return Err("Trace requires a square matrix".to_string());
+        }
+        let mut sum = F16::new(0);
+        for i in 0..self.rows {
+            sum = sum + self.elements[i * self.cols + i];
+        }
+        Ok(sum)
+    }
+
+    // A + c*I (square matrices only)
+    pub fn add_scalar_identity(&self, c: F16) -> Result<Matrix, String> {
+        if self.rows != self.cols {
+            return Err(format!(
+                "Adding a multiple of the identity requires a square matrix, got {}x{}",
+                self.rows, self.cols
+            ));
+        }
+        let mut result = self.clone();
+        for i in 0..self.rows {
+            let idx = i * self.cols + i;
+            result.elements[idx] = result.elements[idx] + c;
+        }
+        Ok(result)
+    }
+
+    // True if M == M^T; non-square matrices are never symmetric
+    pub fn is_symmetric(&self) -> bool {
+        if self.rows != self.cols {
+            return false;
+        }
+        let n = self.rows;
+        for r in 0..n {
+            for c in (r + 1)..n {
+                if self.elements[r * n + c] != self.elements[c * n + r] {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    // True if M is square and every entry below the diagonal is zero
+    pub fn is_upper_triangular(&self) -> bool {
+        if self.rows != self.cols {
+            return false;
+        }
+        let n = self.rows;
+        (1..n).all(|r| self.elements[r * n..r * n + r].iter().all(|&e| e == F16::new(0)))
+    }
+
     // Upper(M): For a square matrix M, outputs an upper triangular matrix.
     // Upper(M)[i, i] = M[i, i]
     // Upper(M)[i, j] = M[i, j] + M[j, i] for 0 <= i < j < n
@@ -113,7 +283,9 @@ impl Matrix {
     }
 
     // Encodes an (n-o) x o matrix O in row-major order by encoding its concatenated rows as a vector.
-    // Note: The spec implies using the vector encoding logic (nibbles packed into bytes).
+    // This nibble packing is the spec encoding of O: unlike P1/P2/P3/L, O is never bitsliced. The C
+    // reference decodes O from the sk_seed expansion with the same plain decode(), and O_BYTES is
+    // ceil((n-o)*o / 2). Keygen, expand_sk and signing all read O in this layout.
     pub fn encode_o(&self) -> Vec<u8> {
         // Concatenate all elements into a single Vec<F16> and then use Vector's encoding.
         // This is equivalent to encoding a single long vector.
@@ -121,6 +293,183 @@ impl Matrix {
         flat_vector.encode_vec()
     }
 
+    // LU decomposition with partial pivoting: returns (L, U, perm) such that P*A = L*U, where
+    // L is unit lower triangular, U is upper triangular and row i of P*A is row perm[i] of A.
+    // Only square, invertible matrices can be factored.
+    pub fn lu_decompose(&self) -> Result<(Matrix, Matrix, Vec<usize>), String> {
+        if self.rows != self.cols {
+            return Err(format!("LU decomposition requires a square matrix, got {}x{}", self.rows, self.cols));
+        }
+        let n = self.rows;
+        let mut u = self.clone();
+        let mut l = Matrix::zero(n, n);
+        let mut perm: Vec<usize> = (0..n).collect();
+
+        for k in 0..n {
+            let pivot_row = (k..n)
+                .find(|&r| u.elements[r * n + k] != F16::new(0))
+                .ok_or_else(|| format!("Matrix is singular: no pivot in column {}", k))?;
+            if pivot_row != k {
+                perm.swap(k, pivot_row);
+                for c in 0..n {
+                    u.elements.swap(k * n + c, pivot_row * n + c);
+                }
+                // Multipliers already recorded move with their rows
+                for c in 0..k {
+                    l.elements.swap(k * n + c, pivot_row * n + c);
+                }
+            }
+
+            let pivot = u.elements[k * n + k];
+            for r in (k + 1)..n {
+                let factor = u.elements[r * n + k] / pivot;
+                l.elements[r * n + k] = factor;
+                for c in k..n {
+                    let sub = factor * u.elements[k * n + c];
+                    u.elements[r * n + c] = u.elements[r * n + c] - sub;
+                }
+            }
+        }
+        for i in 0..n {
+            l.elements[i * n + i] = F16::new(1);
+        }
+        Ok((l, u, perm))
+    }
+
+    // Solves A*x = rhs from the factors returned by lu_decompose: forward substitution with L
+    // on the permuted right-hand side, then back substitution with U. Panics on mismatched
+    // dimensions.
+    pub fn lu_solve(l: &Matrix, u: &Matrix, perm: &[usize], rhs: &Vector) -> Vector {
+        let n = l.rows;
+        if l.cols != n || u.rows != n || u.cols != n || perm.len() != n || rhs.len() != n {
+            panic!("LU solve requires n x n factors, a length-n permutation and a length-n right-hand side.");
+        }
+        let b = rhs.elements();
+
+        let mut y = vec![F16::new(0); n];
+        for i in 0..n {
+            let l_row = &l.elements[i * n..i * n + i];
+            y[i] = l_row.iter().zip(&y[..i]).fold(b[perm[i]], |sum, (&lij, &yj)| sum - lij * yj);
+        }
+
+        let mut x = vec![F16::new(0); n];
+        for i in (0..n).rev() {
+            let u_row = &u.elements[i * n + i + 1..(i + 1) * n];
+            let sum = u_row.iter().zip(&x[i + 1..]).fold(y[i], |sum, (&uij, &xj)| sum - uij * xj);
+            x[i] = sum / u.elements[i * n + i];
+        }
+        Vector::new(x)
+    }
+
+    // Up to `limit` solutions of A*x = rhs for any m x n A: Gauss-Jordan elimination gives a
+    // particular solution (free variables zero) and one null-space basis vector per free column,
+    // and solutions are enumerated as the particular one plus every GF(16) combination of the
+    // basis, 16^(n - rank) in total. A unique solution yields one vector, an inconsistent system
+    // none. Errors only when rhs does not have one entry per row.
+    pub fn solve_all(&self, rhs: &Vector, limit: usize) -> Result<Vec<Vector>, String> {
+        let Some((particular, basis)) = self.solution_space(rhs)? else { return Ok(Vec::new()) };
+
+        let total = 16usize.checked_pow(basis.len() as u32).unwrap_or(usize::MAX);
+        let solutions = (0..total.min(limit))
+            .map(|index| {
+                // Base-16 digits of the index are the coefficients of the basis vectors
+                let mut x = particular.clone();
+                for (d, v) in basis.iter().enumerate() {
+                    let coeff = F16::new((index.checked_shr(4 * d as u32).unwrap_or(0) & 0xF) as u8);
+                    for (xi, &vi) in x.iter_mut().zip(v) {
+                        *xi = *xi + coeff * vi;
+                    }
+                }
+                Vector::new(x)
+            })
+            .collect();
+        Ok(solutions)
+    }
+
+    // The solution set of A*x = rhs as a particular solution (free variables zero) and one
+    // null-space basis vector per free column, or None when the system is inconsistent. Errors
+    // only when rhs does not have one entry per row.
+    pub(crate) fn solution_space(&self, rhs: &Vector) -> Result<Option<SolutionSpace>, String> {
+        if rhs.len() != self.rows {
+            return Err(format!("A*x = rhs requires a right-hand side of length {}, got {}", self.rows, rhs.len()));
+        }
+        let (rows, cols) = (self.rows, self.cols);
+        let mut a = self.elements.clone();
+        let mut b = rhs.elements().to_vec();
+
+        // Reduced row echelon form; pivots[r] is the pivot column of row r
+        let mut pivots = Vec::new();
+        for c in 0..cols {
+            let r = pivots.len();
+            let Some(pivot_row) = (r..rows).find(|&i| a[i * cols + c] != F16::new(0)) else { continue };
+            for k in 0..cols {
+                a.swap(r * cols + k, pivot_row * cols + k);
+            }
+            b.swap(r, pivot_row);
+
+            let inv = a[r * cols + c].inverse().expect("pivot is nonzero");
+            for k in 0..cols {
+                a[r * cols + k] = a[r * cols + k] * inv;
+            }
+            b[r] = b[r] * inv;
+            for i in (0..rows).filter(|&i| i != r) {
+                let factor = a[i * cols + c];
+                if factor == F16::new(0) {
+                    continue;
+                }
+                for k in 0..cols {
+                    let sub = factor * a[r * cols + k];
+                    a[i * cols + k] = a[i * cols + k] - sub;
+                }
+                b[i] = b[i] - factor * b[r];
+            }
+            pivots.push(c);
+        }
+
+        // A zero row with a nonzero right-hand side has no solution
+        if b[pivots.len()..].iter().any(|&x| x != F16::new(0)) {
+            return Ok(None);
+        }
+
+        let mut particular = vec![F16::new(0); cols];
+        for (r, &c) in pivots.iter().enumerate() {
+            particular[c] = b[r];
+        }
+        let basis: Vec<Vec<F16>> = (0..cols)
+            .filter(|c| !pivots.contains(c))
+            .map(|free| {
+                let mut v = vec![F16::new(0); cols];
+                v[free] = F16::new(1);
+                for (r, &c) in pivots.iter().enumerate() {
+                    v[c] = F16::new(0) - a[r * cols + free];
+                }
+                v
+            })
+            .collect();
+        Ok(Some((particular, basis)))
+    }
+
+    // Sums a sequence of matrices of equal dimensions. Errors on a dimension mismatch or an
+    // empty sequence, whose dimensions would be unknown.
+    pub fn try_sum<I: IntoIterator<Item = Matrix>>(matrices: I) -> Result<Matrix, String> {
+        let mut iter = matrices.into_iter();
+        let first = iter
+            .next()
+            .ok_or_else(|| "Cannot sum an empty sequence of matrices".to_string())?;
+        iter.try_fold(first, |acc, m| &acc + &m)
+    }
+
+    // A rows x cols matrix from ⌈rows*cols/2⌉ nibble-packed bytes in row-major order, low nibble
+    // first. Rejects any other length; as in Vector::from_nibble_bytes, the padding nibble of an
+    // odd element count is dropped.
+    pub fn from_nibble_bytes(rows: usize, cols: usize, bytes: &[u8]) -> Result<Self, String> {
+        let n = rows
+            .checked_mul(cols)
+            .ok_or_else(|| format!("Matrix dimensions {}x{} overflow", rows, cols))?;
+        let elements = Vector::from_nibble_bytes(n, bytes)?.elements().to_vec();
+        Ok(Matrix { elements, rows, cols })
+    }
+
     // Decodes a byte string into an (rows) x (cols) matrix O.
     // This is the inverse of EncodeO.
     pub fn decode_o(rows: usize, cols: usize, byte_string: &[u8]) -> Result<Self, String> {
@@ -184,6 +533,40 @@ impl std::ops::Mul for &Matrix {
     }
 }
 
+// Matrix-Vector Multiplication
+impl std::ops::Mul<&Vector> for &Matrix {
+    type Output = Result<Vector, String>;
+
+    fn mul(self, rhs: &Vector) -> Self::Output {
+        self.multiply_vector(rhs)
+    }
+}
+
+// L_i = (P1_i + P1_i^T) * O + P2_i for each of the M_PARAM equations.
+// P1_i is (n-o) x (n-o) upper triangular, P2_i and O are (n-o) x o.
+pub fn compute_l<P: MayoParams>(p1: &[Matrix], p2: &[Matrix], o: &Matrix) -> Result<Vec<Matrix>, String> {
+    if p1.len() != P::M_PARAM || p2.len() != P::M_PARAM {
+        return Err(format!(
+            "Expected {} P1 and P2 matrices, got {} and {}",
+            P::M_PARAM, p1.len(), p2.len()
+        ));
+    }
+    if o.rows() != P::L_MAT_ROWS || o.cols() != P::L_MAT_COLS {
+        return Err(format!(
+            "O must be {}x{}, got {}x{}",
+            P::L_MAT_ROWS, P::L_MAT_COLS, o.rows(), o.cols()
+        ));
+    }
+
+    p1.iter()
+        .zip(p2)
+        .map(|(p1_i, p2_i)| {
+            let symmetric = (p1_i + &p1_i.transpose())?;
+            &(&symmetric * o)? + p2_i
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -203,6 +586,15 @@ mod tests {
         assert_eq!(m.get(2, 0), None);
     }
 
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_matrix_random_dimensions() {
+        let m = Matrix::random(3, 5, &mut crate::test_support::test_rng());
+        assert_eq!(m.rows(), 3);
+        assert_eq!(m.cols(), 5);
+        assert!(m.get(2, 4).is_some());
+    }
+
     #[test]
     fn test_matrix_set() {
         let mut m = Matrix::zero(2,2);
@@ -250,6 +642,48 @@ mod tests {
         assert_eq!(m1.transpose(), m1_t);
     }
 
+    #[test]
+    fn test_matrix_transpose_in_place() {
+        let m = f16m(3, 3, &[1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        let mut m_t = m.clone();
+        m_t.transpose_in_place().unwrap();
+        assert_eq!(m_t, m.transpose());
+
+        let mut non_square = f16m(2, 3, &[1, 2, 3, 4, 5, 6]);
+        assert!(non_square.transpose_in_place().is_err());
+    }
+
+    #[test]
+    fn test_matrix_trace_and_symmetry() {
+        // Symmetric: [[1,2,3],[2,5,6],[3,6,9]]
+        let sym = f16m(3, 3, &[1, 2, 3, 2, 5, 6, 3, 6, 9]);
+        assert!(sym.is_symmetric());
+        assert_eq!(sym.trace().unwrap(), F16::new(1 ^ 5 ^ 9));
+
+        // Non-symmetric: [[1,2,3],[4,5,6],[7,8,9]]
+        let non_sym = f16m(3, 3, &[1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        assert!(!non_sym.is_symmetric());
+        assert_eq!(non_sym.trace().unwrap(), F16::new(1 ^ 5 ^ 9));
+
+        let rect = f16m(2, 3, &[1, 2, 3, 4, 5, 6]);
+        assert!(!rect.is_symmetric());
+        assert!(rect.trace().is_err());
+    }
+
+    #[test]
+    fn test_is_upper_triangular() {
+        let mut m = Matrix::new(3, 3, vec![
+            F16::new(1), F16::new(2), F16::new(3),
+            F16::new(0), F16::new(4), F16::new(5),
+            F16::new(0), F16::new(0), F16::new(6),
+        ]).unwrap();
+        assert!(m.is_upper_triangular());
+        m.set(2, 1, F16::new(7)).unwrap();
+        assert!(!m.is_upper_triangular());
+        assert!(Matrix::zero(1, 1).is_upper_triangular());
+        assert!(!Matrix::zero(2, 3).is_upper_triangular());
+    }
+
     #[test]
     fn test_matrix_add() {
         let m1 = f16m(2, 2, &[1, 2, 3, 4]);
@@ -265,6 +699,136 @@ mod tests {
         assert!((&m1 + &m2).is_err());
     }
 
+    #[test]
+    fn test_matrix_try_sum() {
+        let matrices = vec![
+            f16m(2, 2, &[1, 2, 3, 4]),
+            f16m(2, 2, &[5, 6, 7, 8]),
+            f16m(2, 2, &[9, 10, 11, 12]),
+        ];
+        let expected = f16m(2, 2, &[1^5^9, 2^6^10, 3^7^11, 4^8^12]);
+        assert_eq!(Matrix::try_sum(matrices).unwrap(), expected);
+
+        assert!(Matrix::try_sum(Vec::new()).is_err());
+        assert!(Matrix::try_sum(vec![f16m(2, 2, &[1, 2, 3, 4]), f16m(1, 2, &[1, 2])]).is_err());
+    }
+
+    // Rows of A reordered by perm, as a matrix
+    fn permute_rows(a: &Matrix, perm: &[usize]) -> Matrix {
+        let elements = perm.iter().flat_map(|&r| a.get_row_vec(r).unwrap().into_iter()).collect();
+        Matrix::new(a.rows(), a.cols(), elements).unwrap()
+    }
+
+    #[test]
+    fn test_lu_decompose_reconstructs() {
+        // Zero top-left entry forces a row swap
+        let a = f16m(3, 3, &[0, 2, 3, 4, 5, 6, 7, 8, 10]);
+        let (l, u, perm) = a.lu_decompose().unwrap();
+        assert_ne!(perm, vec![0, 1, 2]);
+        for i in 0..3 {
+            assert_eq!(l.get(i, i), Some(F16::new(1)));
+            for j in (i + 1)..3 {
+                assert_eq!(l.get(i, j), Some(F16::new(0)));
+                assert_eq!(u.get(j, i), Some(F16::new(0)));
+            }
+        }
+        assert_eq!((&l * &u).unwrap(), permute_rows(&a, &perm));
+
+        // A*x = b recovers x
+        let x = Vector::new(vec![F16::new(9), F16::new(1), F16::new(14)]);
+        let b = a.multiply_vector(&x).unwrap();
+        assert_eq!(Matrix::lu_solve(&l, &u, &perm, &b), x);
+    }
+
+    #[test]
+    fn test_lu_decompose_rejects_singular_and_non_square() {
+        // Third row is the sum of the first two
+        let singular = f16m(3, 3, &[1, 2, 3, 4, 5, 6, 1 ^ 4, 2 ^ 5, 3 ^ 6]);
+        assert!(singular.lu_decompose().is_err());
+        assert!(f16m(2, 3, &[1, 2, 3, 4, 5, 6]).lu_decompose().is_err());
+    }
+
+    #[test]
+    fn test_matrix_map() {
+        let m = f16m(2, 3, &[1, 2, 3, 4, 5, 0]);
+        assert_eq!(m.map(|x| x), m);
+
+        let inverses = m.map(|x| x.inverse().unwrap_or(F16::new(0)));
+        for r in 0..2 {
+            for c in 0..3 {
+                let x = m.get(r, c).unwrap();
+                let y = inverses.get(r, c).unwrap();
+                if x.value() == 0 {
+                    assert_eq!(y, F16::new(0));
+                } else {
+                    assert_eq!(x * y, F16::new(1));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_matrix_zip_map() {
+        let m1 = f16m(2, 2, &[1, 2, 3, 4]);
+        let m2 = f16m(2, 2, &[5, 6, 7, 8]);
+        assert_eq!(m1.zip_map(&m2, |a, b| a + b).unwrap(), (&m1 + &m2).unwrap());
+
+        let m3 = f16m(2, 3, &[1, 2, 3, 4, 5, 6]);
+        assert!(m1.zip_map(&m3, |a, b| a + b).is_err());
+    }
+
+    #[test]
+    fn test_solve_all_rank_deficient() {
+        // Third row is the sum of the first two: rank 2 in 3 unknowns, 16 solutions
+        let a = f16m(3, 3, &[1, 2, 3, 4, 5, 6, 5, 7, 5]);
+        let x0 = Vector::new(vec![F16::new(9), F16::new(1), F16::new(14)]);
+        let rhs = (&a * &x0).unwrap();
+
+        let solutions = a.solve_all(&rhs, 100).unwrap();
+        assert_eq!(solutions.len(), 16);
+        for x in &solutions {
+            assert_eq!((&a * x).unwrap(), rhs);
+        }
+        let distinct: std::collections::HashSet<Vec<u8>> =
+            solutions.iter().map(|x| x.elements().iter().map(|e| e.value()).collect()).collect();
+        assert_eq!(distinct.len(), 16);
+        assert!(solutions.contains(&x0));
+
+        assert_eq!(a.solve_all(&rhs, 5).unwrap().len(), 5);
+    }
+
+    #[test]
+    fn test_solve_all_unique_and_inconsistent() {
+        let a = f16m(2, 2, &[1, 2, 3, 4]);
+        let rhs = Vector::new(vec![F16::new(7), F16::new(11)]);
+        let solutions = a.solve_all(&rhs, 10).unwrap();
+        assert_eq!(solutions.len(), 1);
+        assert_eq!((&a * &solutions[0]).unwrap(), rhs);
+
+        // x + 2y = 1 and x + 2y = 2
+        let singular = f16m(2, 2, &[1, 2, 1, 2]);
+        let rhs = Vector::new(vec![F16::new(1), F16::new(2)]);
+        assert!(singular.solve_all(&rhs, 10).unwrap().is_empty());
+
+        assert!(a.solve_all(&Vector::new(vec![F16::new(1)]), 10).is_err());
+    }
+
+    #[test]
+    fn test_matrix_diff() {
+        let m1 = f16m(2, 3, &[1, 2, 3, 4, 5, 6]);
+        assert!(m1.diff(&m1.clone()).is_empty());
+
+        let mut m2 = m1.clone();
+        m2.set(1, 2, F16::new(9)).unwrap();
+        assert_eq!(m1.diff(&m2), vec![(1, 2, F16::new(6), F16::new(9))]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_matrix_diff_dimension_mismatch() {
+        let _ = f16m(2, 2, &[1, 2, 3, 4]).diff(&f16m(1, 4, &[1, 2, 3, 4]));
+    }
+
     #[test]
     fn test_matrix_mul() {
         // M1 = [[1,2],[3,4]] (x values: x^0, x, x+x^0, x^2)
@@ -287,6 +851,23 @@ mod tests {
         assert!((&m1 * &m2).is_err());
     }
 
+    #[test]
+    fn test_matrix_mul_vector() {
+        // [[1,2],[3,4]] * [2,1] = [1*2 + 2*1, 3*2 + 4*1] = [0, 6^4]
+        let m = f16m(2,2, &[1,2,3,4]);
+        let v = Vector::new(vec![F16::new(2), F16::new(1)]);
+        let expected = Vector::new(vec![F16::new(0), F16::new(6 ^ 4)]);
+        assert_eq!(m.multiply_vector(&v).unwrap(), expected);
+        assert_eq!((&m * &v).unwrap(), m.multiply_vector(&v).unwrap());
+    }
+
+    #[test]
+    fn test_matrix_mul_vector_fail() {
+        let m = f16m(2,3, &[1,2,3,4,5,6]);
+        let v = Vector::new(vec![F16::new(1), F16::new(2)]);
+        assert!((&m * &v).is_err());
+    }
+
     #[test]
     fn test_matrix_upper() {
         // M = [[1,2,3],
@@ -347,4 +928,104 @@ mod tests {
         let decoded = Matrix::decode_o(rows, cols, &encoded).unwrap();
         assert_eq!(m, decoded);
     }
+
+    #[test]
+    #[cfg(feature = "mayo1")]
+    fn test_compute_l_dimensions() {
+        use crate::params::Mayo1 as P;
+        let v = P::N_PARAM - P::O_PARAM;
+        let p1: Vec<Matrix> = (0..P::M_PARAM)
+            .map(|eq| f16m(v, v, &(0..v * v).map(|i| ((i + eq) % 16) as u8).collect::<Vec<_>>()).upper().unwrap())
+            .collect();
+        let p2: Vec<Matrix> = (0..P::M_PARAM)
+            .map(|eq| Matrix::zero(v, P::O_PARAM).map(|_| F16::new((eq % 16) as u8)))
+            .collect();
+
+        let o = Matrix::zero(v, P::O_PARAM).map(|_| F16::new(1));
+        let l = compute_l::<P>(&p1, &p2, &o).unwrap();
+        assert_eq!(l.len(), P::M_PARAM);
+        for l_i in &l {
+            assert_eq!((l_i.rows(), l_i.cols()), (P::L_MAT_ROWS, P::L_MAT_COLS));
+        }
+
+        // With O = 0 the linear part reduces to P2
+        let zero_o = Matrix::zero(v, P::O_PARAM);
+        assert_eq!(compute_l::<P>(&p1, &p2, &zero_o).unwrap(), p2);
+
+        assert!(compute_l::<P>(&p1[1..], &p2, &zero_o).is_err());
+        assert!(compute_l::<P>(&p1, &p2, &Matrix::zero(v, v)).is_err());
+    }
+
+    #[test]
+    fn test_encode_o_length_matches_o_bytes() {
+        fn check<P: MayoParams>() {
+            let v = P::N_PARAM - P::O_PARAM;
+            let o = Matrix::zero(v, P::O_PARAM).map(|_| F16::new(9));
+            let encoded = o.encode_o();
+            assert_eq!(encoded.len(), P::O_BYTES, "{}", P::name());
+            assert_eq!(Matrix::decode_o(v, P::O_PARAM, &encoded).unwrap(), o);
+        }
+        #[cfg(feature = "mayo1")]
+        check::<crate::params::Mayo1>();
+        #[cfg(feature = "mayo2")]
+        check::<crate::params::Mayo2>();
+        #[cfg(feature = "mayo3")]
+        check::<crate::params::Mayo3>();
+        #[cfg(feature = "mayo5")]
+        check::<crate::params::Mayo5>();
+    }
+
+    #[test]
+    fn test_row_and_col_iterators() {
+        let m = f16m(2, 3, &[1, 2, 3, 4, 5, 6]);
+        assert_eq!(m.col_vec(1), Some(Vector::new(vec![F16::new(2), F16::new(5)])));
+        assert_eq!(m.col_vec(3), None);
+
+        let rows: Vec<Vector> = m.rows_iter().collect();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[1], m.get_row_vec(1).unwrap());
+
+        let t = m.transpose();
+        assert!(m.cols_iter().eq(t.rows_iter()));
+        assert!(m.rows_iter().eq(t.cols_iter()));
+    }
+
+    #[test]
+    fn test_iterators_on_empty_dimensions() {
+        let m = Matrix::zero(0, 3);
+        assert_eq!(m.rows_iter().count(), 0);
+        assert!(m.cols_iter().all(|c| c.is_empty()));
+        assert_eq!(m.transpose(), Matrix::zero(3, 0));
+    }
+
+    #[test]
+    fn test_add_scalar_identity() {
+        for c in 0..16u8 {
+            let c = F16::new(c);
+            let m = Matrix::identity(3).add_scalar_identity(c).unwrap();
+            for r in 0..3 {
+                for col in 0..3 {
+                    let expected = if r == col { c + F16::new(1) } else { F16::new(0) };
+                    assert_eq!(m.get(r, col), Some(expected));
+                }
+            }
+        }
+
+        let a = f16m(2, 2, &[1, 2, 3, 4]);
+        assert_eq!(a.add_scalar_identity(F16::new(0)).unwrap(), a);
+        assert!(f16m(1, 2, &[1, 2]).add_scalar_identity(F16::new(1)).is_err());
+    }
+
+    #[test]
+    fn test_from_nibble_bytes() {
+        let m = Matrix::from_nibble_bytes(2, 3, &[0x21, 0x43, 0x65]).unwrap();
+        assert_eq!((m.rows(), m.cols()), (2, 3));
+        assert_eq!(m.get(1, 0), Some(F16::new(4)));
+        assert_eq!(m.get(1, 2), Some(F16::new(6)));
+        assert_eq!(Matrix::from_nibble_bytes(2, 3, &m.encode_o()).unwrap(), m);
+
+        assert!(Matrix::from_nibble_bytes(2, 3, &[0x21, 0x43]).is_err());
+        assert!(Matrix::from_nibble_bytes(3, 3, &[0u8; 4]).is_err());
+        assert!(Matrix::from_nibble_bytes(usize::MAX, 2, &[]).unwrap_err().contains("overflow"));
+    }
 }
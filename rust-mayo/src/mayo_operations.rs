@@ -0,0 +1,407 @@
+// rust-mayo/src/mayo_operations.rs
+use crate::f16::F16;
+use crate::matrix::Matrix;
+use crate::params::MayoParams;
+use std::ops::{Add, Index, Mul, Sub};
+
+// Byte order of each u64 limb in the limb codecs. The lane order within a limb is fixed (lane 0 in
+// the low nibble); this only controls how the 8 bytes of a limb are laid out. The reference
+// implementation stores limbs natively on little-endian targets, so Little is the default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LimbEndianness {
+    #[default]
+    Little,
+    Big,
+}
+
+impl LimbEndianness {
+    fn limb_to_bytes(self, limb: u64) -> [u8; 8] {
+        match self {
+            LimbEndianness::Little => limb.to_le_bytes(),
+            LimbEndianness::Big => limb.to_be_bytes(),
+        }
+    }
+
+    fn limb_from_bytes(self, bytes: [u8; 8]) -> u64 {
+        match self {
+            LimbEndianness::Little => u64::from_le_bytes(bytes),
+            LimbEndianness::Big => u64::from_be_bytes(bytes),
+        }
+    }
+}
+
+// A vector of m GF(16) lanes, one per equation of the public map
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MVector {
+    elements: Vec<F16>, // Always exactly m_param lanes
+    m_param: usize,
+}
+
+impl MVector {
+    // Creates the zero vector with m_param lanes
+    pub fn zero(m_param: usize) -> Self {
+        MVector {
+            elements: vec![F16::new(0); m_param],
+            m_param,
+        }
+    }
+
+    // Wraps existing lanes; m_param is taken from their count
+    pub fn from_elements(elements: Vec<F16>) -> Self {
+        let m_param = elements.len();
+        MVector { elements, m_param }
+    }
+
+    pub fn m_param(&self) -> usize { self.m_param }
+
+    // Read-only view of the lanes
+    pub fn lanes(&self) -> &[F16] {
+        &self.elements
+    }
+
+    // self += scalar * other without allocating a temporary MVector. Lanes are processed 16 at a
+    // time as nibble-packed u64 limbs, multiplied by the scalar in bitsliced form, then XORed in.
+    pub fn mul_add_assign(&mut self, scalar: F16, other: &MVector) {
+        if self.m_param != other.m_param {
+            panic!("MVector multiply-accumulate requires the same number of lanes.");
+        }
+        for (acc, src) in self.elements.chunks_mut(16).zip(other.elements.chunks(16)) {
+            let product = mul_limb(pack_limb(src), scalar.value());
+            for (i, lane) in acc.iter_mut().enumerate() {
+                *lane = *lane + F16::new((product >> (4 * i)) as u8);
+            }
+        }
+    }
+
+    // self = z * self + other, reducing modulo f(z) = z^m + f_tail(z). Horner step used when
+    // accumulating whipped terms: lane i is the coefficient of z^i.
+    pub fn poly_mul_by_x_and_add(&mut self, other: &MVector, f_tail: &[u8]) {
+        if self.m_param != other.m_param {
+            panic!("MVector polynomial accumulate requires the same number of lanes.");
+        }
+        crate::poly::mul_x_mod_tail(&mut self.elements, f_tail);
+        for (lane, &o) in self.elements.iter_mut().zip(&other.elements) {
+            *lane = *lane + o;
+        }
+    }
+
+    // Packs the lanes 16 to a u64 limb (lane 0 in the low nibble, unused high nibbles zero) and
+    // writes each limb as 8 bytes in the given order: ceil(m/16) * 8 bytes in total.
+    pub fn to_limbs_bytes(&self, endianness: LimbEndianness) -> Vec<u8> {
+        self.elements
+            .chunks(16)
+            .flat_map(|lanes| endianness.limb_to_bytes(pack_limb(lanes)))
+            .collect()
+    }
+
+    // Inverse of to_limbs_bytes. Rejects a byte count other than ceil(m_param/16) * 8 and any
+    // nonzero padding nibble past lane m_param - 1.
+    pub fn from_limbs_bytes(bytes: &[u8], m_param: usize, endianness: LimbEndianness) -> Result<Self, String> {
+        let limbs = m_param.div_ceil(16);
+        if bytes.len() != limbs * 8 {
+            return Err(format!("Expected {} limb bytes for {} lanes, got {}", limbs * 8, m_param, bytes.len()));
+        }
+        let mut elements = Vec::with_capacity(m_param);
+        for (l, chunk) in bytes.chunks_exact(8).enumerate() {
+            let limb = endianness.limb_from_bytes(chunk.try_into().expect("chunks_exact yields 8 bytes"));
+            let used = (m_param - 16 * l).min(16);
+            if used < 16 && limb >> (4 * used) != 0 {
+                return Err("Nonzero padding nibbles in the last limb".to_string());
+            }
+            elements.extend((0..used).map(|i| F16::new(((limb >> (4 * i)) & 0xF) as u8)));
+        }
+        Ok(MVector { elements, m_param })
+    }
+
+    // Additive inverse. Every element of GF(16) is its own negative, so this is the identity,
+    // but callers should still write -x where the math says so.
+    pub fn neg(&self) -> MVector {
+        self.clone()
+    }
+}
+
+// Packs up to 16 lanes into a limb, lane i in nibble i
+fn pack_limb(lanes: &[F16]) -> u64 {
+    lanes
+        .iter()
+        .enumerate()
+        .fold(0u64, |limb, (i, e)| limb | (u64::from(e.value()) << (4 * i)))
+}
+
+// Number of positions where `a` and `b` hold the same element, over their common length.
+// Works on packed limbs: 16 lanes are compared with one XOR, and a lane matches when its
+// nibble of the XOR is zero.
+#[cfg(any(test, feature = "debug-dump"))]
+pub(crate) fn count_exact_matches_packed(a: &[F16], b: &[F16]) -> usize {
+    const LOW_BITS: u64 = 0x1111_1111_1111_1111;
+    let len = a.len().min(b.len());
+    let mut matches = 0;
+    for (x, y) in a[..len].chunks(16).zip(b[..len].chunks(16)) {
+        let diff = pack_limb(x) ^ pack_limb(y);
+        // Bit 0 of each nibble is set when any bit of that nibble is
+        let nonzero = (diff | (diff >> 1) | (diff >> 2) | (diff >> 3)) & LOW_BITS;
+        matches += x.len() - nonzero.count_ones() as usize;
+    }
+    matches
+}
+
+// Multiplies each of the 16 nibbles of `limb` by `b` modulo x^4 + x + 1
+fn mul_limb(limb: u64, b: u8) -> u64 {
+    const MSB_MASK: u64 = 0x8888_8888_8888_8888;
+    let mut a = limb;
+    let mut result = 0u64;
+    for bit in 0..4 {
+        if (b >> bit) & 1 == 1 {
+            result ^= a;
+        }
+        // a *= x in every nibble: shift, then fold x^4 back in as x + 1
+        let msb = a & MSB_MASK;
+        a = ((a ^ msb) << 1) ^ ((msb >> 3) * 0x3);
+    }
+    result
+}
+
+// A * B where A is an a_rows x a_cols matrix of MVectors stored row-major in `a` (one lane per
+// equation) and B is a scalar matrix. The result is a_rows x b.cols(), row-major; lane eq of
+// entry (i, j) is row i of equation eq's matrix times column j of B.
+pub fn mvec_matmul<P: MayoParams>(a: &[MVector], a_rows: usize, a_cols: usize, b: &Matrix) -> Result<Vec<MVector>, String> {
+    if a.len() != a_rows * a_cols {
+        return Err(format!("Expected {}x{} = {} MVectors, got {}", a_rows, a_cols, a_rows * a_cols, a.len()));
+    }
+    if b.rows() != a_cols {
+        return Err(format!("Cannot multiply {}x{} by {}x{}", a_rows, a_cols, b.rows(), b.cols()));
+    }
+    if let Some(entry) = a.iter().find(|entry| entry.m_param != P::M_PARAM) {
+        return Err(format!("Expected MVectors of {} lanes, got {}", P::M_PARAM, entry.m_param));
+    }
+
+    let mut product = vec![MVector::zero(P::M_PARAM); a_rows * b.cols()];
+    for (a_row, out_row) in a.chunks(a_cols.max(1)).zip(product.chunks_mut(b.cols().max(1))) {
+        for (k, entry) in a_row.iter().enumerate() {
+            for (j, out) in out_row.iter_mut().enumerate() {
+                let scalar = b.get(k, j).expect("k < b.rows(), j < b.cols()");
+                if scalar != F16::new(0) {
+                    out.mul_add_assign(scalar, entry);
+                }
+            }
+        }
+    }
+    Ok(product)
+}
+
+impl Index<usize> for MVector {
+    type Output = F16;
+
+    fn index(&self, lane: usize) -> &F16 {
+        &self.elements[lane]
+    }
+}
+
+// Lane-wise addition
+impl Add for &MVector {
+    type Output = MVector;
+
+    fn add(self, rhs: Self) -> MVector {
+        if self.m_param != rhs.m_param {
+            panic!("MVector addition requires the same number of lanes.");
+        }
+        let elements = self.elements.iter().zip(&rhs.elements).map(|(&a, &b)| a + b).collect();
+        MVector { elements, m_param: self.m_param }
+    }
+}
+
+// Lane-wise subtraction (equal to addition in characteristic 2)
+impl Sub for &MVector {
+    type Output = MVector;
+
+    fn sub(self, rhs: Self) -> MVector {
+        if self.m_param != rhs.m_param {
+            panic!("MVector subtraction requires the same number of lanes.");
+        }
+        let elements = self.elements.iter().zip(&rhs.elements).map(|(&a, &b)| a - b).collect();
+        MVector { elements, m_param: self.m_param }
+    }
+}
+
+// Scalar multiplication: F16 * MVector
+impl Mul<&MVector> for F16 {
+    type Output = MVector;
+
+    fn mul(self, rhs: &MVector) -> MVector {
+        let elements = rhs.elements.iter().map(|&a| self * a).collect();
+        MVector { elements, m_param: rhs.m_param }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mvec(vals: &[u8]) -> MVector {
+        MVector::from_elements(vals.iter().map(|&x| F16::new(x)).collect())
+    }
+
+    #[test]
+    fn test_count_exact_matches_packed_matches_scalar() {
+        use rand::Rng;
+        let mut rng = crate::test_support::test_rng();
+        for len in [0usize, 1, 15, 16, 17, 78, 128] {
+            for _ in 0..20 {
+                let a: Vec<F16> = (0..len).map(|_| F16::new(rng.gen_range(0..16))).collect();
+                // Low-entropy second argument so that plenty of lanes match
+                let b: Vec<F16> = a
+                    .iter()
+                    .map(|&e| if rng.gen_bool(0.5) { e } else { F16::new(rng.gen_range(0..16)) })
+                    .collect();
+                let scalar = a.iter().zip(&b).filter(|(x, y)| x == y).count();
+                assert_eq!(count_exact_matches_packed(&a, &b), scalar);
+                // Only the common length is compared
+                let half = &b[..len / 2];
+                let scalar = a.iter().zip(half).filter(|(x, y)| x == y).count();
+                assert_eq!(count_exact_matches_packed(&a, half), scalar);
+            }
+        }
+    }
+
+    #[test]
+    fn test_mvector_add() {
+        let a = mvec(&[1, 2, 3, 15]);
+        let b = mvec(&[1, 4, 5, 1]);
+        assert_eq!(&a + &b, mvec(&[0, 6, 6, 14]));
+        assert_eq!(&a + &MVector::zero(4), a);
+    }
+
+    #[test]
+    fn test_mvector_scalar_mul() {
+        let a = mvec(&[0, 1, 2, 3]);
+        assert_eq!(F16::new(2) * &a, mvec(&[0, 2, 4, 6]));
+        assert_eq!(F16::new(0) * &a, MVector::zero(4));
+    }
+
+    #[test]
+    fn test_mvector_sub() {
+        let a = mvec(&[1, 2, 3, 15]);
+        let b = mvec(&[1, 4, 5, 1]);
+        assert_eq!(&a - &b, mvec(&[0, 6, 6, 14]));
+        assert_eq!(&(&a - &b) + &b, a);
+        assert_eq!(&a - &a, MVector::zero(4));
+    }
+
+    #[test]
+    fn test_mvector_neg() {
+        let a = mvec(&[1, 2, 3, 15]);
+        assert_eq!(a.neg(), a);
+        assert_eq!(&a + &a.neg(), MVector::zero(4));
+    }
+
+    #[test]
+    fn test_mvector_index_and_lanes() {
+        let a = mvec(&[7, 0, 9, 3]);
+        for i in 0..a.m_param() {
+            assert_eq!(a[i], a.elements[i]);
+            assert_eq!(a.lanes()[i], a.elements[i]);
+        }
+        assert_eq!(a.lanes().len(), 4);
+    }
+
+    #[test]
+    fn test_mvector_mul_add_assign() {
+        // 37 lanes: two full limbs and a partial one
+        let other = MVector::from_elements((0..37).map(|i| F16::new((i * 5 + 3) as u8)).collect());
+        for s in 0..16 {
+            let scalar = F16::new(s);
+            let mut res = MVector::from_elements((0..37).map(|i| F16::new((i * 11) as u8)).collect());
+            let expected = &res + &(scalar * &other);
+            res.mul_add_assign(scalar, &other);
+            assert_eq!(res, expected);
+        }
+    }
+
+    #[test]
+    fn test_mvector_poly_mul_by_x_and_add() {
+        // f(z) = z^4 + z + 2: (1 + 2z + 3z^2 + 4z^3) * z = 8 + 5z + 2z^2 + 3z^3, then + other
+        let mut acc = mvec(&[1, 2, 3, 4]);
+        acc.poly_mul_by_x_and_add(&mvec(&[1, 1, 0, 0]), &[2, 1]);
+        assert_eq!(acc, mvec(&[9, 4, 2, 3]));
+    }
+
+    #[test]
+    fn test_mvector_to_limbs_bytes_specific() {
+        // 18 lanes: one full limb and one holding lanes 16 and 17
+        let a = MVector::from_elements((0..18).map(|i| F16::new(i as u8 % 16)).collect());
+        assert_eq!(
+            hex::encode(a.to_limbs_bytes(LimbEndianness::Little)),
+            "1032547698badcfe1000000000000000"
+        );
+        assert_eq!(a.to_limbs_bytes(LimbEndianness::default()), a.to_limbs_bytes(LimbEndianness::Little));
+        assert_eq!(
+            hex::encode(a.to_limbs_bytes(LimbEndianness::Big)),
+            "fedcba98765432100000000000000010"
+        );
+    }
+
+    #[test]
+    fn test_mvector_limbs_bytes_roundtrip() {
+        for endianness in [LimbEndianness::Little, LimbEndianness::Big] {
+            for m in [4usize, 16, 37, 64] {
+                let a = MVector::from_elements((0..m).map(|i| F16::new((i * 7 + 1) as u8 % 16)).collect());
+                let bytes = a.to_limbs_bytes(endianness);
+                assert_eq!(bytes.len(), m.div_ceil(16) * 8);
+                assert_eq!(MVector::from_limbs_bytes(&bytes, m, endianness).unwrap(), a);
+            }
+        }
+    }
+
+    #[test]
+    fn test_mvector_from_limbs_bytes_rejects_bad_input() {
+        assert!(MVector::from_limbs_bytes(&[0u8; 7], 4, LimbEndianness::Little).is_err());
+        assert!(MVector::from_limbs_bytes(&[0u8; 16], 4, LimbEndianness::Little).is_err());
+
+        // Lane 4 set in a 4-lane vector is padding
+        let mut bytes = [0u8; 8];
+        bytes[2] = 0x01;
+        assert!(MVector::from_limbs_bytes(&bytes, 4, LimbEndianness::Little).is_err());
+        assert!(MVector::from_limbs_bytes(&bytes, 5, LimbEndianness::Little).is_ok());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_mvector_index_out_of_range() {
+        let _ = mvec(&[1, 2])[2];
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_mvector_sub_lane_mismatch() {
+        let _ = &mvec(&[1, 2]) - &mvec(&[1, 2, 3]);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_mvec_matmul_matches_per_equation_products() {
+        crate::mayo_params! { name: Lanes3, n: 5, m: 3, o: 2, k: 2, f_tail: [1, 1, 0, 0] }
+        let mut rng = crate::test_support::test_rng();
+        let (rows, inner, cols) = (2, 3, 4);
+        let a: Vec<MVector> = (0..rows * inner)
+            .map(|_| MVector::from_elements(crate::vector::Vector::random(3, &mut rng).elements().to_vec()))
+            .collect();
+        let b = Matrix::random(inner, cols, &mut rng);
+
+        let product = mvec_matmul::<Lanes3>(&a, rows, inner, &b).unwrap();
+        assert_eq!(product.len(), rows * cols);
+        for eq in 0..3 {
+            let lanes: Vec<F16> = a.iter().map(|entry| entry[eq]).collect();
+            let expected = (&Matrix::new(rows, inner, lanes).unwrap() * &b).unwrap();
+            for i in 0..rows {
+                for j in 0..cols {
+                    assert_eq!(product[i * cols + j][eq], expected.get(i, j).unwrap());
+                }
+            }
+        }
+
+        assert!(mvec_matmul::<Lanes3>(&a[1..], rows, inner, &b).is_err());
+        assert!(mvec_matmul::<Lanes3>(&a, inner, rows, &b).is_err());
+        let wide = vec![MVector::zero(4); rows * inner];
+        assert!(mvec_matmul::<Lanes3>(&wide, rows, inner, &b).unwrap_err().contains("3 lanes"));
+    }
+}
@@ -1,4 +1,10 @@
 // rust-mayo/src/params.rs
+use crate::prf::Prf;
+#[cfg(any(feature = "mayo1", feature = "mayo2", feature = "mayo3", feature = "mayo5"))]
+use crate::prf::Aes128CtrPrf;
+
+// Default bound on message length for signing and verification (1 GiB)
+pub const DEFAULT_MAX_MESSAGE_LEN: usize = 1 << 30;
 
 // MAYO parameter sets trait and implementations
 pub trait MayoParams {
@@ -8,6 +14,11 @@ pub trait MayoParams {
     const K_PARAM: usize;
     const KO_PARAM: usize;
     
+    // Low coefficients of the irreducible f(z) = z^m + ... + F_TAIL[1]*z + F_TAIL[0] over
+    // GF(16) used to combine (whip) the k*(k+1)/2 terms of P*. The length is the tail's
+    // degree plus one, which varies with m (m = 128 needs a z^4 term).
+    const F_TAIL: &'static [u8];
+    
     // Matrix element counts
     const P1_ELEMS_PER_MATRIX: usize;
     const P2_ELEMS_PER_MATRIX: usize;
@@ -50,19 +61,56 @@ pub trait MayoParams {
     const L_MAT_COLS: usize;
     const L_IS_TRIANGULAR: bool;
     
+    // Longest message sign/verify accept; the empty message is always allowed
+    const MAX_MESSAGE_LEN: usize = DEFAULT_MAX_MESSAGE_LEN;
+    
+    // PRF output expand_matrices consumes from pk_seed: one nibble per P1, P2 and P3 coefficient
+    const PRF_STREAM_BYTES: usize = size_product(
+        Self::M_PARAM,
+        size_sum(&[Self::P1_ELEMS_PER_MATRIX, Self::P2_ELEMS_PER_MATRIX, Self::P3_ELEMS_PER_MATRIX]),
+    )
+    .div_ceil(2);
+    
+    // Stream used to expand P1/P2/P3 from pk_seed
+    type Prf: Prf;
+    
     fn name() -> &'static str;
     fn security_level() -> usize;
 }
 
+// The hand-written counts below must follow from (n, o). With v = n - o vinegar variables:
+//   P1 is v x v upper triangular -> v*(v+1)/2 elements per equation
+//   P2 is v x o                  -> v*o
+//   P3 is o x o upper triangular -> o*(o+1)/2
+// and the matrix dimensions and O_ELTS = v*o must agree. Each standard set is checked at
+// compile time, so a typo in one of the constants fails the build.
+#[cfg(any(feature = "mayo1", feature = "mayo2", feature = "mayo3", feature = "mayo5"))]
+const fn elem_counts_consistent<P: MayoParams>() -> bool {
+    let v = P::N_PARAM - P::O_PARAM;
+    let o = P::O_PARAM;
+    P::P1_ELEMS_PER_MATRIX == v * (v + 1) / 2
+        && P::P2_ELEMS_PER_MATRIX == v * o
+        && P::P3_ELEMS_PER_MATRIX == o * (o + 1) / 2
+        && P::O_ELTS == v * o
+        && P::KO_PARAM == P::K_PARAM * o
+        && P::P1_MAT_ROWS == v && P::P1_MAT_COLS == v
+        && P::P2_MAT_ROWS == v && P::P2_MAT_COLS == o
+        && P::P3_MAT_ROWS == o && P::P3_MAT_COLS == o
+        && P::L_MAT_ROWS == v && P::L_MAT_COLS == o
+}
+
 // MAYO-1 parameter set
+#[cfg(feature = "mayo1")]
 pub struct Mayo1;
 
+#[cfg(feature = "mayo1")]
 impl MayoParams for Mayo1 {
     const M_PARAM: usize = 78;      // From MAYO spec
     const N_PARAM: usize = 86;      // From MAYO spec  
     const O_PARAM: usize = 8;       // From MAYO spec
     const K_PARAM: usize = 10;      // From MAYO spec
     const KO_PARAM: usize = 80;     // k * o = 10 * 8
+    const F_TAIL: &'static [u8] = &[8, 1, 1, 0]; // f(z) = z^78 + z^2 + z + x^3
     
     // Corrected element counts based on proper MAYO formulas
     const P1_ELEMS_PER_MATRIX: usize = 3081; // (n-o)*(n-o+1)/2 = 78*79/2
@@ -86,8 +134,8 @@ impl MayoParams for Mayo1 {
     const V_BYTES: usize = 39;      // From C implementation
     const O_ELTS: usize = 624;      // (n-o)*o = 78*8
     const L_BYTES: usize = 24336;   // Same as P2_BYTES
-    const ESK_BYTES: usize = Self::SK_SEED_BYTES + Self::O_BYTES + Self::P1_BYTES + Self::L_BYTES;
-    const EPK_BYTES: usize = Self::P1_BYTES + Self::P2_BYTES + Self::P3_BYTES;
+    const ESK_BYTES: usize = size_sum(&[Self::SK_SEED_BYTES, Self::O_BYTES, Self::P1_BYTES, Self::L_BYTES]);
+    const EPK_BYTES: usize = size_sum(&[Self::P1_BYTES, Self::P2_BYTES, Self::P3_BYTES]);
     
     // Matrix dimensions corrected to match MAYO spec
     const P1_MAT_ROWS: usize = 78;  // n-o
@@ -103,19 +151,27 @@ impl MayoParams for Mayo1 {
     const L_MAT_COLS: usize = 8;    // o
     const L_IS_TRIANGULAR: bool = false;
     
+    type Prf = Aes128CtrPrf; // Spec: P matrices are expanded with AES-128-CTR
+    
     fn name() -> &'static str { "MAYO-1" }
     fn security_level() -> usize { 1 }
 }
 
+#[cfg(feature = "mayo1")]
+const _: () = assert!(elem_counts_consistent::<Mayo1>(), "MAYO-1 element counts disagree with n and o");
+
 // MAYO-2 parameter set
+#[cfg(feature = "mayo2")]
 pub struct Mayo2;
 
+#[cfg(feature = "mayo2")]
 impl MayoParams for Mayo2 {
     const M_PARAM: usize = 64;
     const N_PARAM: usize = 78;
     const O_PARAM: usize = 18;
     const K_PARAM: usize = 4;
     const KO_PARAM: usize = 72; // k * o
+    const F_TAIL: &'static [u8] = &[8, 0, 2, 8]; // f(z) = z^64 + x^3*z^3 + x*z^2 + x^3
     
     const P1_ELEMS_PER_MATRIX: usize = 1830; // (n-o)*(n-o+1)/2 = 60*61/2
     const P2_ELEMS_PER_MATRIX: usize = 1080; // (n-o)*o = 60*18
@@ -126,20 +182,20 @@ impl MayoParams for Mayo2 {
     const SK_SEED_BYTES: usize = 24;
     const PK_SEED_BYTES: usize = 16;
     const O_BYTES: usize = 540; // ((n-o)*o+1)/2
-    const P1_BYTES: usize = 58560; // P1_ELEMS_PER_MATRIX * 4 * 8
-    const P2_BYTES: usize = 34560; // P2_ELEMS_PER_MATRIX * 4 * 8
-    const P3_BYTES: usize = 5472;  // P3_ELEMS_PER_MATRIX * 4 * 8
+    const P1_BYTES: usize = 58560; // P1_ELEMS_PER_MATRIX * M_PARAM / 2
+    const P2_BYTES: usize = 34560; // P2_ELEMS_PER_MATRIX * M_PARAM / 2
+    const P3_BYTES: usize = 5472;  // P3_ELEMS_PER_MATRIX * M_PARAM / 2
     
     const CSK_BYTES: usize = 24;
-    const CPK_BYTES: usize = 2152;
-    const SIG_BYTES: usize = 180; // 148 + 32
+    const CPK_BYTES: usize = 5488; // PK_SEED_BYTES + P3_BYTES
+    const SIG_BYTES: usize = 188; // ceil(k*n/2) + salt = 156 + 32
     
     const R_BYTES: usize = 32;
     const V_BYTES: usize = 31; // ((n-o)+1)/2
     const O_ELTS: usize = 1080; // (n-o)*o
     const L_BYTES: usize = 34560; // Same as P2_BYTES
-    const ESK_BYTES: usize = Self::SK_SEED_BYTES + Self::O_BYTES + Self::P1_BYTES + Self::L_BYTES;
-    const EPK_BYTES: usize = Self::P1_BYTES + Self::P2_BYTES + Self::P3_BYTES;
+    const ESK_BYTES: usize = size_sum(&[Self::SK_SEED_BYTES, Self::O_BYTES, Self::P1_BYTES, Self::L_BYTES]);
+    const EPK_BYTES: usize = size_sum(&[Self::P1_BYTES, Self::P2_BYTES, Self::P3_BYTES]);
     
     const P1_MAT_ROWS: usize = 60;
     const P1_MAT_COLS: usize = 60;
@@ -154,19 +210,27 @@ impl MayoParams for Mayo2 {
     const L_MAT_COLS: usize = 18;
     const L_IS_TRIANGULAR: bool = false;
     
+    type Prf = Aes128CtrPrf; // Spec: P matrices are expanded with AES-128-CTR
+    
     fn name() -> &'static str { "MAYO-2" }
     fn security_level() -> usize { 2 }
 }
 
+#[cfg(feature = "mayo2")]
+const _: () = assert!(elem_counts_consistent::<Mayo2>(), "MAYO-2 element counts disagree with n and o");
+
 // MAYO-3 parameter set
+#[cfg(feature = "mayo3")]
 pub struct Mayo3;
 
+#[cfg(feature = "mayo3")]
 impl MayoParams for Mayo3 {
     const M_PARAM: usize = 96;
     const N_PARAM: usize = 99;
     const O_PARAM: usize = 10;
     const K_PARAM: usize = 11;
     const KO_PARAM: usize = 110; // k * o
+    const F_TAIL: &'static [u8] = &[2, 2, 0, 2]; // f(z) = z^96 + x*z^3 + x*z + x
     
     const P1_ELEMS_PER_MATRIX: usize = 4005; // (n-o)*(n-o+1)/2 = 89*90/2
     const P2_ELEMS_PER_MATRIX: usize = 890;  // (n-o)*o = 89*10
@@ -177,20 +241,20 @@ impl MayoParams for Mayo3 {
     const SK_SEED_BYTES: usize = 32;
     const PK_SEED_BYTES: usize = 16;
     const O_BYTES: usize = 445; // ((n-o)*o+1)/2
-    const P1_BYTES: usize = 128160; // P1_ELEMS_PER_MATRIX * 4 * 8
-    const P2_BYTES: usize = 28480;  // P2_ELEMS_PER_MATRIX * 4 * 8
-    const P3_BYTES: usize = 1760;   // P3_ELEMS_PER_MATRIX * 4 * 8
+    const P1_BYTES: usize = 192240; // P1_ELEMS_PER_MATRIX * M_PARAM / 2
+    const P2_BYTES: usize = 42720;  // P2_ELEMS_PER_MATRIX * M_PARAM / 2
+    const P3_BYTES: usize = 2640;   // P3_ELEMS_PER_MATRIX * M_PARAM / 2
     
     const CSK_BYTES: usize = 32;
     const CPK_BYTES: usize = 2656;
-    const SIG_BYTES: usize = 266; // 234 + 32
+    const SIG_BYTES: usize = 577; // ceil(k*n/2) + salt = 545 + 32
     
     const R_BYTES: usize = 32;
     const V_BYTES: usize = 45; // ((n-o)+1)/2
     const O_ELTS: usize = 890; // (n-o)*o
-    const L_BYTES: usize = 28480; // Same as P2_BYTES
-    const ESK_BYTES: usize = Self::SK_SEED_BYTES + Self::O_BYTES + Self::P1_BYTES + Self::L_BYTES;
-    const EPK_BYTES: usize = Self::P1_BYTES + Self::P2_BYTES + Self::P3_BYTES;
+    const L_BYTES: usize = 42720; // Same as P2_BYTES
+    const ESK_BYTES: usize = size_sum(&[Self::SK_SEED_BYTES, Self::O_BYTES, Self::P1_BYTES, Self::L_BYTES]);
+    const EPK_BYTES: usize = size_sum(&[Self::P1_BYTES, Self::P2_BYTES, Self::P3_BYTES]);
     
     const P1_MAT_ROWS: usize = 89;
     const P1_MAT_COLS: usize = 89;
@@ -205,19 +269,27 @@ impl MayoParams for Mayo3 {
     const L_MAT_COLS: usize = 10;
     const L_IS_TRIANGULAR: bool = false;
     
+    type Prf = Aes128CtrPrf; // Spec: P matrices are expanded with AES-128-CTR
+    
     fn name() -> &'static str { "MAYO-3" }
     fn security_level() -> usize { 3 }
 }
 
+#[cfg(feature = "mayo3")]
+const _: () = assert!(elem_counts_consistent::<Mayo3>(), "MAYO-3 element counts disagree with n and o");
+
 // MAYO-5 parameter set
+#[cfg(feature = "mayo5")]
 pub struct Mayo5;
 
+#[cfg(feature = "mayo5")]
 impl MayoParams for Mayo5 {
     const M_PARAM: usize = 128;
     const N_PARAM: usize = 133;
     const O_PARAM: usize = 12;
     const K_PARAM: usize = 12;
     const KO_PARAM: usize = 144; // k * o
+    const F_TAIL: &'static [u8] = &[4, 8, 0, 4, 2]; // f(z) = z^128 + x*z^4 + x^2*z^3 + x^3*z + x^2
     
     const P1_ELEMS_PER_MATRIX: usize = 7381; // (n-o)*(n-o+1)/2 = 121*122/2
     const P2_ELEMS_PER_MATRIX: usize = 1452; // (n-o)*o = 121*12
@@ -228,20 +300,20 @@ impl MayoParams for Mayo5 {
     const SK_SEED_BYTES: usize = 40;
     const PK_SEED_BYTES: usize = 16;
     const O_BYTES: usize = 726; // ((n-o)*o+1)/2
-    const P1_BYTES: usize = 236192; // P1_ELEMS_PER_MATRIX * 4 * 8
-    const P2_BYTES: usize = 46464;  // P2_ELEMS_PER_MATRIX * 4 * 8
-    const P3_BYTES: usize = 2496;   // P3_ELEMS_PER_MATRIX * 4 * 8
+    const P1_BYTES: usize = 472384; // P1_ELEMS_PER_MATRIX * M_PARAM / 2
+    const P2_BYTES: usize = 92928;  // P2_ELEMS_PER_MATRIX * M_PARAM / 2
+    const P3_BYTES: usize = 4992;   // P3_ELEMS_PER_MATRIX * M_PARAM / 2
     
     const CSK_BYTES: usize = 40;
-    const CPK_BYTES: usize = 5488;
-    const SIG_BYTES: usize = 838; // 806 + 32
+    const CPK_BYTES: usize = 5008; // PK_SEED_BYTES + P3_BYTES
+    const SIG_BYTES: usize = 830; // ceil(k*n/2) + salt = 798 + 32
     
     const R_BYTES: usize = 32;
     const V_BYTES: usize = 61; // ((n-o)+1)/2
     const O_ELTS: usize = 1452; // (n-o)*o
-    const L_BYTES: usize = 46464; // Same as P2_BYTES
-    const ESK_BYTES: usize = Self::SK_SEED_BYTES + Self::O_BYTES + Self::P1_BYTES + Self::L_BYTES;
-    const EPK_BYTES: usize = Self::P1_BYTES + Self::P2_BYTES + Self::P3_BYTES;
+    const L_BYTES: usize = 92928; // Same as P2_BYTES
+    const ESK_BYTES: usize = size_sum(&[Self::SK_SEED_BYTES, Self::O_BYTES, Self::P1_BYTES, Self::L_BYTES]);
+    const EPK_BYTES: usize = size_sum(&[Self::P1_BYTES, Self::P2_BYTES, Self::P3_BYTES]);
     
     const P1_MAT_ROWS: usize = 121;
     const P1_MAT_COLS: usize = 121;
@@ -256,48 +328,393 @@ impl MayoParams for Mayo5 {
     const L_MAT_COLS: usize = 12;
     const L_IS_TRIANGULAR: bool = false;
     
+    type Prf = Aes128CtrPrf; // Spec: P matrices are expanded with AES-128-CTR
+    
     fn name() -> &'static str { "MAYO-5" }
     fn security_level() -> usize { 5 }
 }
 
-// Default to MAYO-1 for backward compatibility
-pub use Mayo1 as DefaultParams;
-
-// Re-export constants for backward compatibility
-pub const M_PARAM: usize = Mayo1::M_PARAM;
-pub const N_PARAM: usize = Mayo1::N_PARAM;
-pub const O_PARAM: usize = Mayo1::O_PARAM;
-pub const K_PARAM: usize = Mayo1::K_PARAM;
-pub const KO_PARAM: usize = Mayo1::KO_PARAM;
-pub const P1_ELEMS_PER_MATRIX: usize = Mayo1::P1_ELEMS_PER_MATRIX;
-pub const P2_ELEMS_PER_MATRIX: usize = Mayo1::P2_ELEMS_PER_MATRIX;
-pub const P3_ELEMS_PER_MATRIX: usize = Mayo1::P3_ELEMS_PER_MATRIX;
-pub const SALT_BYTES: usize = Mayo1::SALT_BYTES;
-pub const DIGEST_BYTES: usize = Mayo1::DIGEST_BYTES;
-pub const SK_SEED_BYTES: usize = Mayo1::SK_SEED_BYTES;
-pub const PK_SEED_BYTES: usize = Mayo1::PK_SEED_BYTES;
-pub const O_BYTES: usize = Mayo1::O_BYTES;
-pub const P1_BYTES: usize = Mayo1::P1_BYTES;
-pub const P2_BYTES: usize = Mayo1::P2_BYTES;
-pub const P3_BYTES: usize = Mayo1::P3_BYTES;
-pub const CSK_BYTES: usize = Mayo1::CSK_BYTES;
-pub const CPK_BYTES: usize = Mayo1::CPK_BYTES;
-pub const SIG_BYTES: usize = Mayo1::SIG_BYTES;
-pub const R_BYTES: usize = Mayo1::R_BYTES;
-pub const V_BYTES: usize = Mayo1::V_BYTES;
-pub const O_ELTS: usize = Mayo1::O_ELTS;
-pub const L_BYTES: usize = Mayo1::L_BYTES;
-pub const ESK_BYTES: usize = Mayo1::ESK_BYTES;
-pub const EPK_BYTES: usize = Mayo1::EPK_BYTES;
-pub const P1_MAT_ROWS: usize = Mayo1::P1_MAT_ROWS;
-pub const P1_MAT_COLS: usize = Mayo1::P1_MAT_COLS;
-pub const P1_IS_TRIANGULAR: bool = Mayo1::P1_IS_TRIANGULAR;
-pub const P2_MAT_ROWS: usize = Mayo1::P2_MAT_ROWS;
-pub const P2_MAT_COLS: usize = Mayo1::P2_MAT_COLS;
-pub const P2_IS_TRIANGULAR: bool = Mayo1::P2_IS_TRIANGULAR;
-pub const P3_MAT_ROWS: usize = Mayo1::P3_MAT_ROWS;
-pub const P3_MAT_COLS: usize = Mayo1::P3_MAT_COLS;
-pub const P3_IS_TRIANGULAR: bool = Mayo1::P3_IS_TRIANGULAR;
-pub const L_MAT_ROWS: usize = Mayo1::L_MAT_ROWS;
-pub const L_MAT_COLS: usize = Mayo1::L_MAT_COLS;
-pub const L_IS_TRIANGULAR: bool = Mayo1::L_IS_TRIANGULAR;
+#[cfg(feature = "mayo5")]
+const _: () = assert!(elem_counts_consistent::<Mayo5>(), "MAYO-5 element counts disagree with n and o");
+
+// Overflow-checked arithmetic for the derived size constants. Const evaluation already
+// rejects an overflowing `+` or `*`, but only with a bare "attempt to add with overflow";
+// these name the cause, which matters for large custom sets and on 32-bit targets.
+pub const fn size_sum(parts: &[usize]) -> usize {
+    let mut total: usize = 0;
+    let mut i = 0;
+    while i < parts.len() {
+        total = match total.checked_add(parts[i]) {
+            Some(sum) => sum,
+            None => panic!("parameter set size overflows usize"),
+        };
+        i += 1;
+    }
+    total
+}
+
+pub const fn size_product(a: usize, b: usize) -> usize {
+    match a.checked_mul(b) {
+        Some(product) => product,
+        None => panic!("parameter set size overflows usize"),
+    }
+}
+
+// Defines a custom parameter set from (n, m, o, k), deriving every size the way the
+// standard sets do. f_tail must make z^m + f_tail irreducible over GF(16). Seed and salt
+// sizes default to MAYO-1's and can be overridden:
+//   mayo_params! { name: Foo, n: 70, m: 64, o: 7, k: 9, f_tail: [8, 0, 2, 8] }
+//   mayo_params! { name: Foo, n: 70, m: 64, o: 7, k: 9, f_tail: [8, 0, 2, 8],
+//                  salt_bytes: 32, digest_bytes: 32, sk_seed_bytes: 24, pk_seed_bytes: 16 }
+#[macro_export]
+macro_rules! mayo_params {
+    (name: $name:ident, n: $n:expr, m: $m:expr, o: $o:expr, k: $k:expr, f_tail: $f_tail:expr $(,)?) => {
+        $crate::mayo_params! {
+            name: $name, n: $n, m: $m, o: $o, k: $k, f_tail: $f_tail,
+            salt_bytes: 24, digest_bytes: 32, sk_seed_bytes: 24, pk_seed_bytes: 16
+        }
+    };
+    (
+        name: $name:ident, n: $n:expr, m: $m:expr, o: $o:expr, k: $k:expr, f_tail: $f_tail:expr,
+        salt_bytes: $salt:expr, digest_bytes: $digest:expr,
+        sk_seed_bytes: $sk_seed:expr, pk_seed_bytes: $pk_seed:expr $(,)?
+    ) => {
+        pub struct $name;
+
+        const _: () = assert!($n > $o, "mayo_params!: n must be greater than o");
+        const _: () = assert!($m > 0, "mayo_params!: m must be positive");
+        const _: () = assert!($f_tail[0] != 0, "mayo_params!: f(z) must have a nonzero constant term");
+        const _: () = assert!($pk_seed == 16, "mayo_params!: AES-128-CTR expansion needs a 16-byte pk_seed");
+
+        impl $crate::params::MayoParams for $name {
+            const M_PARAM: usize = $m;
+            const N_PARAM: usize = $n;
+            const O_PARAM: usize = $o;
+            const K_PARAM: usize = $k;
+            const KO_PARAM: usize = $crate::params::size_product($k, $o);
+            const F_TAIL: &'static [u8] = &$f_tail;
+
+            const P1_ELEMS_PER_MATRIX: usize = $crate::params::size_product($n - $o, $n - $o + 1) / 2;
+            const P2_ELEMS_PER_MATRIX: usize = $crate::params::size_product($n - $o, $o);
+            const P3_ELEMS_PER_MATRIX: usize = $crate::params::size_product($o, $o + 1) / 2;
+
+            const SALT_BYTES: usize = $salt;
+            const DIGEST_BYTES: usize = $digest;
+            const SK_SEED_BYTES: usize = $sk_seed;
+            const PK_SEED_BYTES: usize = $pk_seed;
+            const O_BYTES: usize = Self::O_ELTS.div_ceil(2);
+            const P1_BYTES: usize = $crate::params::size_product(Self::P1_ELEMS_PER_MATRIX, $m).div_ceil(2);
+            const P2_BYTES: usize = $crate::params::size_product(Self::P2_ELEMS_PER_MATRIX, $m).div_ceil(2);
+            const P3_BYTES: usize = $crate::params::size_product(Self::P3_ELEMS_PER_MATRIX, $m).div_ceil(2);
+
+            const CSK_BYTES: usize = $sk_seed;
+            const CPK_BYTES: usize = $crate::params::size_sum(&[$pk_seed, Self::P3_BYTES]);
+            const SIG_BYTES: usize = $crate::params::size_sum(&[
+                $crate::params::size_product(Self::K_PARAM, Self::N_PARAM).div_ceil(2),
+                Self::SALT_BYTES,
+            ]);
+
+            const R_BYTES: usize = Self::M_PARAM.div_ceil(8) * 4;
+            const V_BYTES: usize = Self::P1_MAT_ROWS.div_ceil(2);
+            const O_ELTS: usize = $crate::params::size_product($n - $o, $o);
+            const L_BYTES: usize = Self::P2_BYTES;
+            const ESK_BYTES: usize =
+                $crate::params::size_sum(&[Self::SK_SEED_BYTES, Self::O_BYTES, Self::P1_BYTES, Self::L_BYTES]);
+            const EPK_BYTES: usize = $crate::params::size_sum(&[Self::P1_BYTES, Self::P2_BYTES, Self::P3_BYTES]);
+
+            const P1_MAT_ROWS: usize = $n - $o;
+            const P1_MAT_COLS: usize = $n - $o;
+            const P1_IS_TRIANGULAR: bool = true;
+            const P2_MAT_ROWS: usize = $n - $o;
+            const P2_MAT_COLS: usize = $o;
+            const P2_IS_TRIANGULAR: bool = false;
+            const P3_MAT_ROWS: usize = $o;
+            const P3_MAT_COLS: usize = $o;
+            const P3_IS_TRIANGULAR: bool = true;
+            const L_MAT_ROWS: usize = $n - $o;
+            const L_MAT_COLS: usize = $o;
+            const L_IS_TRIANGULAR: bool = false;
+
+            type Prf = $crate::prf::Aes128CtrPrf;
+
+            fn name() -> &'static str { stringify!($name) }
+            fn security_level() -> usize { 0 } // Non-standard set
+        }
+    };
+}
+
+// The MAYO-1 defaults below exist only while MAYO-1 is compiled in
+#[cfg(feature = "mayo1")]
+mod mayo1_defaults {
+    use super::{Mayo1, MayoParams};
+
+    // Default to MAYO-1 for backward compatibility
+    pub use super::Mayo1 as DefaultParams;
+
+    // Re-export constants for backward compatibility
+    pub const M_PARAM: usize = Mayo1::M_PARAM;
+    pub const N_PARAM: usize = Mayo1::N_PARAM;
+    pub const O_PARAM: usize = Mayo1::O_PARAM;
+    pub const K_PARAM: usize = Mayo1::K_PARAM;
+    pub const KO_PARAM: usize = Mayo1::KO_PARAM;
+    pub const P1_ELEMS_PER_MATRIX: usize = Mayo1::P1_ELEMS_PER_MATRIX;
+    pub const P2_ELEMS_PER_MATRIX: usize = Mayo1::P2_ELEMS_PER_MATRIX;
+    pub const P3_ELEMS_PER_MATRIX: usize = Mayo1::P3_ELEMS_PER_MATRIX;
+    pub const SALT_BYTES: usize = Mayo1::SALT_BYTES;
+    pub const DIGEST_BYTES: usize = Mayo1::DIGEST_BYTES;
+    pub const SK_SEED_BYTES: usize = Mayo1::SK_SEED_BYTES;
+    pub const PK_SEED_BYTES: usize = Mayo1::PK_SEED_BYTES;
+    pub const O_BYTES: usize = Mayo1::O_BYTES;
+    pub const P1_BYTES: usize = Mayo1::P1_BYTES;
+    pub const P2_BYTES: usize = Mayo1::P2_BYTES;
+    pub const P3_BYTES: usize = Mayo1::P3_BYTES;
+    pub const CSK_BYTES: usize = Mayo1::CSK_BYTES;
+    pub const CPK_BYTES: usize = Mayo1::CPK_BYTES;
+    pub const SIG_BYTES: usize = Mayo1::SIG_BYTES;
+    pub const R_BYTES: usize = Mayo1::R_BYTES;
+    pub const V_BYTES: usize = Mayo1::V_BYTES;
+    pub const O_ELTS: usize = Mayo1::O_ELTS;
+    pub const L_BYTES: usize = Mayo1::L_BYTES;
+    pub const ESK_BYTES: usize = Mayo1::ESK_BYTES;
+    pub const EPK_BYTES: usize = Mayo1::EPK_BYTES;
+    pub const P1_MAT_ROWS: usize = Mayo1::P1_MAT_ROWS;
+    pub const P1_MAT_COLS: usize = Mayo1::P1_MAT_COLS;
+    pub const P1_IS_TRIANGULAR: bool = Mayo1::P1_IS_TRIANGULAR;
+    pub const P2_MAT_ROWS: usize = Mayo1::P2_MAT_ROWS;
+    pub const P2_MAT_COLS: usize = Mayo1::P2_MAT_COLS;
+    pub const P2_IS_TRIANGULAR: bool = Mayo1::P2_IS_TRIANGULAR;
+    pub const P3_MAT_ROWS: usize = Mayo1::P3_MAT_ROWS;
+    pub const P3_MAT_COLS: usize = Mayo1::P3_MAT_COLS;
+    pub const P3_IS_TRIANGULAR: bool = Mayo1::P3_IS_TRIANGULAR;
+    pub const L_MAT_ROWS: usize = Mayo1::L_MAT_ROWS;
+    pub const L_MAT_COLS: usize = Mayo1::L_MAT_COLS;
+    pub const L_IS_TRIANGULAR: bool = Mayo1::L_IS_TRIANGULAR;
+}
+#[cfg(feature = "mayo1")]
+pub use mayo1_defaults::*;
+
+// Operation counts for one signing attempt, derived from the parameters alone. Meant for
+// comparing parameter sets, not for predicting wall-clock time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SignCost {
+    // F16 elements of P1, P2 and P3 drawn by expand_matrices. The expansion is pure PRF
+    // output and performs no F16 multiplies; this is its size instead.
+    pub expand_elements: usize,
+    // F16 multiplies in one evaluation of P*(s): every upper-triangular coefficient of each
+    // equation is weighted by k^2 products of signature entries plus the coefficient itself
+    pub evaluation_muls: usize,
+    // F16 multiplies to Gaussian-eliminate the m x k*o oil system with its right-hand side:
+    // each of the min(m, k*o) pivots scales or clears all m rows of k*o + 1 entries
+    pub solve_muls: usize,
+}
+
+pub const fn cost_model<P: MayoParams>() -> SignCost {
+    let (n, m, o, k) = (P::N_PARAM, P::M_PARAM, P::O_PARAM, P::K_PARAM);
+    let coefficients = n * (n + 1) / 2;
+    let unknowns = k * o;
+    let pivots = if m < unknowns { m } else { unknowns };
+    SignCost {
+        expand_elements: m * coefficients,
+        evaluation_muls: m * coefficients * (k * k + 1),
+        solve_muls: pivots * m * (unknowns + 1),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::f16::F16;
+
+    // A custom set far beyond the standard ones; its sizes are evaluated at compile time
+    // through the checked helpers, so this module would not build if any of them overflowed.
+    // P1 alone is 256 * 3950 * 3951 / 2 elements, past u32::MAX.
+    crate::mayo_params! { name: HugeMayo, n: 4000, m: 256, o: 50, k: 40, f_tail: [1, 0, 1, 8] }
+    const HUGE_EPK_BYTES: usize = HugeMayo::EPK_BYTES;
+    const _: () = assert!(HUGE_EPK_BYTES == (3950 * 3951 / 2 + 3950 * 50 + 50 * 51 / 2) * 256 / 2);
+    const _: () = assert!(HugeMayo::ESK_BYTES == 24 + HugeMayo::O_BYTES + HugeMayo::P1_BYTES + HugeMayo::P2_BYTES);
+
+    // f(z) = z^m + tail from the MAYO specification's parameter table, low degree first
+    // (x is the GF(16) generator 2, x^2 = 4, x^3 = 8)
+    #[test]
+    fn test_f_tail_reference_values() {
+        #[cfg(feature = "mayo1")]
+        assert_eq!(Mayo1::F_TAIL, &[8, 1, 1, 0]); // z^78 + z^2 + z + x^3
+        #[cfg(feature = "mayo2")]
+        assert_eq!(Mayo2::F_TAIL, &[8, 0, 2, 8]); // z^64 + x^3*z^3 + x*z^2 + x^3
+        #[cfg(feature = "mayo3")]
+        assert_eq!(Mayo3::F_TAIL, &[2, 2, 0, 2]); // z^96 + x*z^3 + x*z + x
+        #[cfg(feature = "mayo5")]
+        assert_eq!(Mayo5::F_TAIL, &[4, 8, 0, 4, 2]); // z^128 + x*z^4 + x^2*z^3 + x^3*z + x^2
+    }
+
+    // Polynomials over GF(16) low degree first, for the irreducibility check below
+    fn trimmed(mut p: Vec<F16>) -> Vec<F16> {
+        while p.last() == Some(&F16::new(0)) {
+            p.pop();
+        }
+        p
+    }
+
+    fn poly_rem(a: &[F16], b: &[F16]) -> Vec<F16> {
+        let mut r = trimmed(a.to_vec());
+        let lead_inv = b[b.len() - 1].inverse().expect("b is trimmed");
+        while r.len() >= b.len() {
+            let factor = r[r.len() - 1] * lead_inv;
+            let shift = r.len() - b.len();
+            for (i, &bi) in b.iter().enumerate() {
+                r[shift + i] = r[shift + i] + factor * bi;
+            }
+            r = trimmed(r);
+        }
+        r
+    }
+
+    fn poly_gcd(a: &[F16], b: &[F16]) -> Vec<F16> {
+        let (mut a, mut b) = (trimmed(a.to_vec()), trimmed(b.to_vec()));
+        while !b.is_empty() {
+            let r = poly_rem(&a, &b);
+            a = b;
+            b = r;
+        }
+        a
+    }
+
+    // z^(16^j) mod f(z) = z^m + tail: Frobenius is four squarings, and squaring a polynomial
+    // in characteristic 2 squares each coefficient in place at twice its degree
+    fn z_pow_16_pow(j: usize, m: usize, tail: &[u8]) -> Vec<F16> {
+        let mut p = vec![F16::new(0); m];
+        p[1] = F16::new(1);
+        for _ in 0..4 * j {
+            let mut square = vec![F16::new(0); 2 * m];
+            for (i, &c) in p.iter().enumerate() {
+                square[2 * i] = c * c;
+            }
+            crate::poly::reduce_by_tail(&mut square, m, tail);
+            square.truncate(m);
+            p = square;
+        }
+        p
+    }
+
+    // Rabin's test: f of degree m is irreducible over GF(16) iff z^(16^m) = z mod f and
+    // gcd(z^(16^(m/q)) - z, f) = 1 for every prime q dividing m
+    fn is_irreducible(m: usize, tail: &[u8]) -> bool {
+        let mut f: Vec<F16> = tail.iter().map(|&t| F16::new(t)).collect();
+        f.resize(m, F16::new(0));
+        f.push(F16::new(1));
+        let z_minus = |mut p: Vec<F16>| {
+            p[1] = p[1] + F16::new(1);
+            p
+        };
+        if !trimmed(z_minus(z_pow_16_pow(m, m, tail))).is_empty() {
+            return false;
+        }
+        (2..=m)
+            .filter(|&q| m.is_multiple_of(q) && (2..q).all(|d| !q.is_multiple_of(d)))
+            .all(|q| poly_gcd(&z_minus(z_pow_16_pow(m / q, m, tail)), &f).len() == 1)
+    }
+
+    #[test]
+    fn test_f_tail_gives_irreducible_modulus() {
+        #[cfg(feature = "mayo1")]
+        assert!(is_irreducible(Mayo1::M_PARAM, Mayo1::F_TAIL));
+        #[cfg(feature = "mayo2")]
+        assert!(is_irreducible(Mayo2::M_PARAM, Mayo2::F_TAIL));
+        #[cfg(feature = "mayo3")]
+        assert!(is_irreducible(Mayo3::M_PARAM, Mayo3::F_TAIL));
+        #[cfg(feature = "mayo5")]
+        assert!(is_irreducible(Mayo5::M_PARAM, Mayo5::F_TAIL));
+        // The tails previously attached to MAYO-3 and MAYO-5 belong to m = 108 and m = 142
+        assert!(is_irreducible(108, &[8, 0, 1, 7]));
+        #[cfg(feature = "mayo3")]
+        assert!(!is_irreducible(Mayo3::M_PARAM, &[8, 0, 1, 7]));
+        // z^4 + z + 1 factors over GF(16) (GF(16) is its splitting field)
+        assert!(!is_irreducible(4, &[1, 1]));
+    }
+
+    #[test]
+    fn test_size_helpers() {
+        assert_eq!(size_sum(&[]), 0);
+        assert_eq!(size_sum(&[1, 2, 3]), 6);
+        assert_eq!(size_product(7, 9), 63);
+        assert_eq!(size_sum(&[usize::MAX - 1, 1]), usize::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "parameter set size overflows usize")]
+    fn test_size_sum_overflow_panics() {
+        size_sum(&[usize::MAX, 1]);
+    }
+
+    #[test]
+    #[should_panic(expected = "parameter set size overflows usize")]
+    fn test_size_product_overflow_panics() {
+        size_product(usize::MAX / 2, 3);
+    }
+
+    #[test]
+    #[cfg(all(feature = "mayo1", feature = "mayo2", feature = "mayo3", feature = "mayo5"))]
+    fn test_cost_model_grows_with_security_level() {
+        let costs = [cost_model::<Mayo1>(), cost_model::<Mayo3>(), cost_model::<Mayo5>()];
+        for pair in costs.windows(2) {
+            assert!(pair[0].expand_elements < pair[1].expand_elements, "{:?}", pair);
+            assert!(pair[0].evaluation_muls < pair[1].evaluation_muls, "{:?}", pair);
+            assert!(pair[0].solve_muls < pair[1].solve_muls, "{:?}", pair);
+        }
+        // MAYO-2 trades a larger o for a much smaller k, so it sits below MAYO-1 throughout
+        let (mayo1, mayo2) = (cost_model::<Mayo1>(), cost_model::<Mayo2>());
+        assert!(mayo2.evaluation_muls < mayo1.evaluation_muls);
+        assert!(mayo2.solve_muls < mayo1.solve_muls);
+
+        assert_eq!(
+            cost_model::<Mayo1>(),
+            SignCost { expand_elements: 78 * 3741, evaluation_muls: 78 * 3741 * 101, solve_muls: 78 * 78 * 81 }
+        );
+    }
+
+    // Byte sizes recomputed from (n, m, o, k, salt): every matrix region packs its
+    // m * elements nibbles two per byte, rounding up
+    #[derive(Debug, PartialEq)]
+    struct Sizes {
+        p1_bytes: usize,
+        p2_bytes: usize,
+        p3_bytes: usize,
+        sig_bytes: usize,
+        cpk_bytes: usize,
+    }
+
+    fn derive_sizes<P: MayoParams>() -> Sizes {
+        let (n, m, o, k) = (P::N_PARAM, P::M_PARAM, P::O_PARAM, P::K_PARAM);
+        let v = n - o;
+        let p3_bytes = (m * o * (o + 1) / 2 * 4).div_ceil(8);
+        Sizes {
+            p1_bytes: (m * v * (v + 1) / 2 * 4).div_ceil(8),
+            p2_bytes: (m * v * o * 4).div_ceil(8),
+            p3_bytes,
+            sig_bytes: (k * n * 4).div_ceil(8) + P::SALT_BYTES,
+            cpk_bytes: P::PK_SEED_BYTES + p3_bytes,
+        }
+    }
+
+    fn declared_sizes<P: MayoParams>() -> Sizes {
+        Sizes {
+            p1_bytes: P::P1_BYTES,
+            p2_bytes: P::P2_BYTES,
+            p3_bytes: P::P3_BYTES,
+            sig_bytes: P::SIG_BYTES,
+            cpk_bytes: P::CPK_BYTES,
+        }
+    }
+
+    #[test]
+    fn test_declared_sizes_match_derived() {
+        #[cfg(feature = "mayo1")]
+        assert_eq!(declared_sizes::<Mayo1>(), derive_sizes::<Mayo1>(), "MAYO-1");
+        #[cfg(feature = "mayo2")]
+        assert_eq!(declared_sizes::<Mayo2>(), derive_sizes::<Mayo2>(), "MAYO-2");
+        #[cfg(feature = "mayo3")]
+        assert_eq!(declared_sizes::<Mayo3>(), derive_sizes::<Mayo3>(), "MAYO-3");
+        #[cfg(feature = "mayo5")]
+        assert_eq!(declared_sizes::<Mayo5>(), derive_sizes::<Mayo5>(), "MAYO-5");
+    }
+}
@@ -0,0 +1,101 @@
+// rust-mayo/src/poly.rs
+use crate::f16::F16;
+
+// Polynomials over GF(16) stored low degree first, reduced modulo f(z) = z^m + tail(z),
+// where tail = f_tail[0] + f_tail[1]*z + ... has degree below m. In characteristic 2,
+// z^m = tail(z) mod f, which is all the reductions below rely on.
+
+// coeffs = z * coeffs mod f, with m = coeffs.len(). The coefficient pushed out of degree m-1
+// is folded back in as a multiple of the tail.
+pub fn mul_x_mod_tail(coeffs: &mut [F16], f_tail: &[u8]) {
+    let m = coeffs.len();
+    if m == 0 {
+        return;
+    }
+    if f_tail.len() > m {
+        panic!("Tail must have fewer coefficients than the modulus degree.");
+    }
+    let top = coeffs[m - 1];
+    coeffs.rotate_right(1);
+    coeffs[0] = F16::new(0);
+    for (c, &t) in coeffs.iter_mut().zip(f_tail) {
+        *c = *c + top * F16::new(t);
+    }
+}
+
+// Reduces a polynomial of degree below coeffs.len() modulo f = z^m + tail in place: afterwards
+// coeffs[..m] holds the remainder and every higher coefficient is zero. Works from the top
+// degree down, so folded terms that land at degree >= m are reduced in turn.
+pub fn reduce_by_tail(coeffs: &mut [F16], m: usize, f_tail: &[u8]) {
+    if f_tail.len() > m {
+        panic!("Tail must have fewer coefficients than the modulus degree.");
+    }
+    for deg in (m..coeffs.len()).rev() {
+        let lead = coeffs[deg];
+        coeffs[deg] = F16::new(0);
+        for (i, &t) in f_tail.iter().enumerate() {
+            coeffs[deg - m + i] = coeffs[deg - m + i] + lead * F16::new(t);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn poly(vals: &[u8]) -> Vec<F16> {
+        vals.iter().map(|&x| F16::new(x)).collect()
+    }
+
+    // f(z) = z^4 + z + 2
+    const TAIL: [u8; 2] = [2, 1];
+
+    #[test]
+    fn test_mul_x_mod_tail() {
+        // z^3 * z = z^4 = z + 2
+        let mut p = poly(&[0, 0, 0, 1]);
+        mul_x_mod_tail(&mut p, &TAIL);
+        assert_eq!(p, poly(&[2, 1, 0, 0]));
+
+        // (1 + 2z + 3z^2 + 4z^3) * z = z + 2z^2 + 3z^3 + 4(z + 2) = 8 + 5z + 2z^2 + 3z^3
+        let mut p = poly(&[1, 2, 3, 4]);
+        mul_x_mod_tail(&mut p, &TAIL);
+        assert_eq!(p, poly(&[8, 5, 2, 3]));
+
+        // No overflow: plain shift
+        let mut p = poly(&[7, 9, 0, 0]);
+        mul_x_mod_tail(&mut p, &TAIL);
+        assert_eq!(p, poly(&[0, 7, 9, 0]));
+    }
+
+    #[test]
+    fn test_reduce_by_tail() {
+        // z^5 = z * (z + 2) = z^2 + 2z
+        let mut p = poly(&[0, 0, 0, 0, 0, 1]);
+        reduce_by_tail(&mut p, 4, &TAIL);
+        assert_eq!(p, poly(&[0, 2, 1, 0, 0, 0]));
+
+        // z^4 + z^5 = (z + 2) + (z^2 + 2z) = 2 + 3z + z^2
+        let mut p = poly(&[0, 0, 0, 0, 1, 1]);
+        reduce_by_tail(&mut p, 4, &TAIL);
+        assert_eq!(p, poly(&[2, 3, 1, 0, 0, 0]));
+
+        // Already reduced polynomials are unchanged
+        let mut p = poly(&[1, 2, 3, 4]);
+        reduce_by_tail(&mut p, 4, &TAIL);
+        assert_eq!(p, poly(&[1, 2, 3, 4]));
+    }
+
+    #[test]
+    fn test_reduce_matches_repeated_mul_x() {
+        // z^9 mod f both ways
+        let mut by_mul = poly(&[0, 0, 0, 1]);
+        for _ in 0..6 {
+            mul_x_mod_tail(&mut by_mul, &TAIL);
+        }
+        let mut by_reduce = vec![F16::new(0); 10];
+        by_reduce[9] = F16::new(1);
+        reduce_by_tail(&mut by_reduce, 4, &TAIL);
+        assert_eq!(&by_reduce[..4], by_mul.as_slice());
+    }
+}
@@ -1 +0,0 @@
-use rust_mayo::crypto::test_basic_crypto_operations; use rust_mayo::params::Mayo1; fn main() { test_basic_crypto_operations::<Mayo1>().unwrap(); }
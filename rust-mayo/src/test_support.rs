@@ -0,0 +1,18 @@
+// rust-mayo/src/test_support.rs
+//
+// Fixtures shared by the unit tests.
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+// A deterministic rng, so a failing randomized test fails the same way on every run
+pub(crate) fn test_rng() -> StdRng {
+    StdRng::seed_from_u64(0x4d41_594f)
+}
+
+// A small custom set for tests that only need the shapes to be consistent. With k * o < m its
+// oil systems almost never have a solution, so it exercises the signer's failure paths.
+crate::mayo_params! { name: TinyMayo, n: 20, m: 16, o: 4, k: 3, f_tail: [1, 0, 1, 8] }
+
+// As small as TinyMayo, but with k * o >= m so that the signer's oil systems are solvable
+crate::mayo_params! { name: SignMayo, n: 16, m: 16, o: 4, k: 5, f_tail: [1, 0, 1, 8] }
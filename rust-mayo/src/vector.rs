@@ -1,6 +1,8 @@
 // rust-mayo/src/vector.rs
 use crate::f16::F16;
 use std::ops::Add;
+#[cfg(feature = "std")]
+use rand::RngCore;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Vector {
@@ -20,6 +22,12 @@ impl Vector {
         }
     }
 
+    // Creates a vector of len uniformly random GF(16) elements
+    #[cfg(feature = "std")]
+    pub fn random<R: RngCore>(len: usize, rng: &mut R) -> Self {
+        Vector::new(random_f16_elements(len, rng))
+    }
+
     // Returns the length of the vector
     pub fn len(&self) -> usize {
         self.elements.len()
@@ -40,6 +48,34 @@ impl Vector {
         &self.elements
     }
 
+    // Splits into the first mid elements and the rest, e.g. a signature row into its
+    // vinegar and oil parts. Panics if mid > len, like slice::split_at.
+    pub fn split_at(&self, mid: usize) -> (Vector, Vector) {
+        let (head, tail) = self.elements.split_at(mid);
+        (Vector::new(head.to_vec()), Vector::new(tail.to_vec()))
+    }
+
+    // Divides every element by s; Err when s is zero
+    pub fn scalar_div(&self, s: F16) -> Result<Vector, String> {
+        self.elements
+            .iter()
+            .map(|&e| e.checked_div(s).ok_or_else(|| "Division of a vector by zero".to_string()))
+            .collect()
+    }
+
+    // Raises every element to the power exp
+    pub fn pow_elementwise(&self, exp: u32) -> Vector {
+        self.elements.iter().map(|e| e.pow(exp)).collect()
+    }
+
+    // The elements of self followed by those of other; inverse of split_at
+    pub fn concat(&self, other: &Vector) -> Vector {
+        let mut elements = Vec::with_capacity(self.len() + other.len());
+        elements.extend_from_slice(&self.elements);
+        elements.extend_from_slice(&other.elements);
+        Vector::new(elements)
+    }
+
     // Encodes a vector x ∈ Fn_16 into ⌈n/2⌉ bytes.
     // Concatenates nibble encodings, pads with a zero nibble if n is odd.
     pub fn encode_vec(&self) -> Vec<u8> {
@@ -94,6 +130,41 @@ impl Vector {
         }
         Ok(Vector::new(elements))
     }
+
+    // decode_vec under the name used for untrusted input: the length is checked, and every nibble
+    // is a valid F16, so there is no value to reject. For odd n the high nibble of the last byte
+    // is padding and is dropped without being checked.
+    pub fn from_nibble_bytes(n: usize, bytes: &[u8]) -> Result<Self, String> {
+        Self::decode_vec(n, bytes)
+    }
+
+    // Decodes n elements from the ⌈n/2⌉ bytes starting at byte_offset within a larger buffer.
+    // Bytes past the encoded vector are ignored.
+    pub fn decode_vec_at(n: usize, bytes: &[u8], byte_offset: usize) -> Result<Self, String> {
+        let num_bytes = n.div_ceil(2);
+        let end = byte_offset
+            .checked_add(num_bytes)
+            .filter(|&end| end <= bytes.len())
+            .ok_or_else(|| format!(
+                "Buffer too short: need {} bytes at offset {}, got {}",
+                num_bytes,
+                byte_offset,
+                bytes.len()
+            ))?;
+        Self::decode_vec(n, &bytes[byte_offset..end])
+    }
+}
+
+// Fills len uniform GF(16) elements, drawing two nibbles per random byte
+#[cfg(feature = "std")]
+pub(crate) fn random_f16_elements<R: RngCore>(len: usize, rng: &mut R) -> Vec<F16> {
+    let mut bytes = vec![0u8; len.div_ceil(2)];
+    rng.fill_bytes(&mut bytes);
+    bytes
+        .iter()
+        .flat_map(|&b| [F16::new(b & 0x0F), F16::new(b >> 4)])
+        .take(len)
+        .collect()
 }
 
 // Component-wise addition for Vectors
@@ -134,6 +205,44 @@ impl std::ops::Mul<&Vector> for F16 {
     }
 }
 
+// Sum of a sequence of equal-length Vectors. Panics on a length mismatch, like Add;
+// an empty sequence sums to the empty Vector since no length is known.
+impl std::iter::Sum for Vector {
+    fn sum<I: Iterator<Item = Vector>>(mut iter: I) -> Vector {
+        let first = match iter.next() {
+            Some(first) => first,
+            None => return Vector::new(Vec::new()),
+        };
+        iter.fold(first, |acc, v| &acc + &v)
+    }
+}
+
+// Owned iteration over the elements of a Vector
+impl IntoIterator for Vector {
+    type Item = F16;
+    type IntoIter = std::vec::IntoIter<F16>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.elements.into_iter()
+    }
+}
+
+// Borrowed iteration; F16 is Copy, so elements are yielded by value
+impl<'a> IntoIterator for &'a Vector {
+    type Item = F16;
+    type IntoIter = std::iter::Copied<std::slice::Iter<'a, F16>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.elements.iter().copied()
+    }
+}
+
+// Collects F16 elements into a Vector
+impl FromIterator<F16> for Vector {
+    fn from_iter<I: IntoIterator<Item = F16>>(iter: I) -> Self {
+        Vector::new(iter.into_iter().collect())
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -260,6 +369,48 @@ mod tests {
         assert!(Vector::decode_vec(4, &bytes).is_err()); // Expected 2 bytes for len 4
     }
 
+    #[test]
+    fn test_decode_vec_at_offset() {
+        // Two header bytes, then [1,2,3] packed, then trailing bytes that must be ignored
+        let bytes = vec![0xFF, 0xEE, 0x21, 0x03, 0xAB];
+        let decoded = Vector::decode_vec_at(3, &bytes, 2).unwrap();
+        assert_eq!(decoded, Vector::new(f16v(&[1, 2, 3])));
+
+        assert!(Vector::decode_vec_at(3, &bytes, 4).is_err());
+        assert!(Vector::decode_vec_at(2, &bytes, usize::MAX).is_err());
+    }
+
+    #[test]
+    fn test_iterate_and_collect() {
+        let v = Vector::new(f16v(&[1, 2, 3, 4]));
+
+        // Borrowed: multiply each element by x (= F16(2))
+        let doubled: Vector = (&v).into_iter().map(|e| e * F16::new(2)).collect();
+        assert_eq!(doubled, Vector::new(f16v(&[2, 4, 6, 8])));
+
+        // Owned: adding an element to itself gives zero in characteristic 2
+        let zeroed: Vector = v.into_iter().map(|e| e + e).collect();
+        assert_eq!(zeroed, Vector::zero(4));
+
+        let empty: Vector = std::iter::empty().collect();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_random_covers_all_values() {
+        let mut rng = crate::test_support::test_rng();
+        let v = Vector::random(4096, &mut rng);
+        assert_eq!(v.len(), 4096);
+        let mut seen = [false; 16];
+        for e in &v {
+            seen[e.value() as usize] = true;
+        }
+        assert!(seen.iter().all(|&s| s), "some GF(16) values never appeared: {:?}", seen);
+
+        assert_eq!(Vector::random(7, &mut rng).len(), 7);
+    }
+
     #[test]
     fn test_encode_decode_roundtrip() {
         let v1 = Vector::new(f16v(&[1,5,2,8,10,3,7,0,15,4]));
@@ -272,4 +423,78 @@ mod tests {
         let decoded2 = Vector::decode_vec(v2.len(), &encoded2).unwrap();
         assert_eq!(v2, decoded2);
     }
+
+    #[test]
+    fn test_vector_sum() {
+        let vectors = vec![
+            Vector::new(f16v(&[1, 2, 3])),
+            Vector::new(f16v(&[4, 5, 6])),
+            Vector::new(f16v(&[7, 8, 9])),
+        ];
+        // 1^4^7 = 2, 2^5^8 = 15, 3^6^9 = 12
+        let total: Vector = vectors.into_iter().sum();
+        assert_eq!(total.elements(), f16v(&[2, 15, 12]).as_slice());
+
+        let empty: Vector = std::iter::empty::<Vector>().sum();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_vector_sum_length_mismatch() {
+        let _: Vector = vec![Vector::new(f16v(&[1, 2])), Vector::new(f16v(&[1]))].into_iter().sum();
+    }
+
+    #[test]
+    fn test_split_at_concat_roundtrip() {
+        let x = Vector::new(f16v(&[1, 2, 3, 4, 5]));
+        for mid in 0..=x.len() {
+            let (head, tail) = x.split_at(mid);
+            assert_eq!(head.len(), mid);
+            assert_eq!(tail.len(), x.len() - mid);
+            assert_eq!(head.concat(&tail), x);
+        }
+
+        let (vinegar, oil) = x.split_at(3);
+        assert_eq!(vinegar.elements(), f16v(&[1, 2, 3]).as_slice());
+        assert_eq!(oil.elements(), f16v(&[4, 5]).as_slice());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_split_at_past_end() {
+        let _ = Vector::new(f16v(&[1, 2])).split_at(3);
+    }
+
+    #[test]
+    fn test_pow_elementwise() {
+        let x = Vector::new((0..16).map(F16::new).collect());
+        // The multiplicative group has order 15: a^15 = 1 for a != 0, and 0^15 = 0
+        let indicator = x.pow_elementwise(15);
+        for (a, b) in x.elements().iter().zip(indicator.elements()) {
+            assert_eq!(b.value(), u8::from(a.value() != 0));
+        }
+        assert_eq!(x.pow_elementwise(1), x);
+        assert!(x.pow_elementwise(0).elements().iter().all(|e| e.value() == 1));
+    }
+
+    #[test]
+    fn test_scalar_div() {
+        let v = Vector::new(f16v(&[0, 1, 7, 12, 15]));
+        for s in 1..16u8 {
+            let s = F16::new(s);
+            let quotient = v.scalar_div(s).unwrap();
+            assert_eq!(&quotient * s, v);
+        }
+        assert!(v.scalar_div(F16::new(0)).is_err());
+    }
+
+    #[test]
+    fn test_from_nibble_bytes() {
+        let v = Vector::from_nibble_bytes(3, &[0x21, 0xf3]).unwrap();
+        assert_eq!(v.elements(), &[F16::new(1), F16::new(2), F16::new(3)]);
+        assert!(Vector::from_nibble_bytes(3, &[0x21]).unwrap_err().contains("expected 2, got 1"));
+        assert!(Vector::from_nibble_bytes(3, &[0x21, 0x03, 0x00]).is_err());
+        assert!(Vector::from_nibble_bytes(0, &[]).unwrap().elements().is_empty());
+    }
 }
@@ -1,6 +1,19 @@
 use wasm_bindgen::prelude::*;
-use crate::crypto::{generate_keypair_generic, sign_generic, verify_generic, CryptoError};
-use crate::params::{Mayo1, Mayo2, Mayo3, Mayo5};
+use crate::crypto::{generate_keypair_generic, sign_batch, sign_chunk, sign_with_progress, verify_batch, verify_generic, CryptoError, SigningState};
+use crate::params::MayoParams;
+#[cfg(feature = "mayo1")]
+use crate::params::Mayo1;
+#[cfg(feature = "mayo2")]
+use crate::params::Mayo2;
+#[cfg(feature = "mayo3")]
+use crate::params::Mayo3;
+#[cfg(feature = "mayo5")]
+use crate::params::Mayo5;
+use zeroize::Zeroizing;
+
+// Secret keys cross this boundary by value so the Rust-side copy can be wiped (Zeroizing)
+// before returning. The caller's Uint8Array is JS memory this module cannot reach: callers
+// should clear it with `secretKey.fill(0)` once they no longer need it.
 
 // Helper to convert CryptoError to JsValue
 fn to_js_error(err: CryptoError) -> JsValue {
@@ -12,14 +25,19 @@ pub fn generate_keypair_wasm(param_set_name: &str) -> Result<JsValue, JsValue> {
     web_sys::console::log_1(&format!("[WASM] generate_keypair_wasm called: param={}", param_set_name).into());
     
     let result = match param_set_name {
+        #[cfg(feature = "mayo1")]
         "MAYO1" => generate_keypair_generic::<Mayo1>().map_err(to_js_error)?,
+        #[cfg(feature = "mayo2")]
         "MAYO2" => generate_keypair_generic::<Mayo2>().map_err(to_js_error)?,
+        #[cfg(feature = "mayo3")]
         "MAYO3" => generate_keypair_generic::<Mayo3>().map_err(to_js_error)?,
+        #[cfg(feature = "mayo5")]
         "MAYO5" => generate_keypair_generic::<Mayo5>().map_err(to_js_error)?,
         _ => return Err(JsValue::from_str("Invalid MAYO parameter set name"))
     };
     
     let (sk, pk) = result;
+    let sk = Zeroizing::new(sk);
     
     // Create a JavaScript object with the keys
     let obj = js_sys::Object::new();
@@ -36,14 +54,31 @@ pub fn generate_mayo_keypair(param_set_name: &str) -> Result<JsValue, JsValue> {
     generate_keypair_wasm(param_set_name)
 }
 
+// How often, in signing attempts, the optional progress callback of sign_with_mayo fires
+const SIGN_PROGRESS_INTERVAL: usize = 16;
+
+// Signs with the selected parameter set. If `progress` is a function it is called with the
+// current attempt number on the first attempt and every SIGN_PROGRESS_INTERVAL attempts after;
+// passing undefined signs without callbacks.
 #[wasm_bindgen]
-pub fn sign_with_mayo(param_set_name: &str, secret_key: &[u8], message: &[u8]) -> Result<js_sys::Uint8Array, JsValue> {
+pub fn sign_with_mayo(param_set_name: &str, secret_key: Vec<u8>, message: &[u8], progress: Option<js_sys::Function>) -> Result<js_sys::Uint8Array, JsValue> {
+    let secret_key = Zeroizing::new(secret_key);
     web_sys::console::log_1(&format!("[WASM] sign_with_mayo called: param={}, sk_len={}, msg_len={}", param_set_name, secret_key.len(), message.len()).into());
     
+    let report = |attempt: usize| {
+        if let Some(callback) = &progress {
+            if attempt % SIGN_PROGRESS_INTERVAL == 1 {
+                // A throwing callback must not abort signing
+                let _ = callback.call1(&JsValue::NULL, &JsValue::from(attempt as u32));
+            }
+        }
+    };
+    
     match param_set_name {
+        #[cfg(feature = "mayo1")]
         "MAYO1" => {
             web_sys::console::log_1(&"[WASM] Calling sign_generic for MAYO1".into());
-            match sign_generic::<Mayo1>(secret_key, message) {
+            match sign_with_progress::<Mayo1>(&secret_key, message, report) {
                 Ok(sig) => {
                     web_sys::console::log_1(&format!("[WASM] Signing SUCCESS: {} bytes", sig.len()).into());
                     Ok(js_sys::Uint8Array::from(sig.as_slice()))
@@ -54,9 +89,48 @@ pub fn sign_with_mayo(param_set_name: &str, secret_key: &[u8], message: &[u8]) -
                 }
             }
         }
-        "MAYO2" => sign_generic::<Mayo2>(secret_key, message).map_err(to_js_error).map(|sig| js_sys::Uint8Array::from(sig.as_slice())),
-        "MAYO3" => sign_generic::<Mayo3>(secret_key, message).map_err(to_js_error).map(|sig| js_sys::Uint8Array::from(sig.as_slice())),
-        "MAYO5" => sign_generic::<Mayo5>(secret_key, message).map_err(to_js_error).map(|sig| js_sys::Uint8Array::from(sig.as_slice())),
+        #[cfg(feature = "mayo2")]
+        "MAYO2" => sign_with_progress::<Mayo2>(&secret_key, message, report).map_err(to_js_error).map(|sig| js_sys::Uint8Array::from(sig.as_slice())),
+        #[cfg(feature = "mayo3")]
+        "MAYO3" => sign_with_progress::<Mayo3>(&secret_key, message, report).map_err(to_js_error).map(|sig| js_sys::Uint8Array::from(sig.as_slice())),
+        #[cfg(feature = "mayo5")]
+        "MAYO5" => sign_with_progress::<Mayo5>(&secret_key, message, report).map_err(to_js_error).map(|sig| js_sys::Uint8Array::from(sig.as_slice())),
+        _ => Err(JsValue::from_str("Invalid MAYO parameter set name"))
+    }
+}
+
+fn sign_chunk_js<P: MayoParams>(secret_key: &[u8], message: &[u8], state: Option<Vec<u8>>, attempts_per_call: u32) -> Result<JsValue, JsValue> {
+    let mut state = match state {
+        Some(bytes) => SigningState::from_bytes::<P>(&bytes).map_err(to_js_error)?,
+        None => SigningState::new::<P>(),
+    };
+    let signature = sign_chunk::<P>(secret_key, message, &mut state, attempts_per_call as usize).map_err(to_js_error)?;
+
+    let obj = js_sys::Object::new();
+    js_sys::Reflect::set(&obj, &"done".into(), &JsValue::from_bool(signature.is_some()))?;
+    match signature {
+        Some(sig) => js_sys::Reflect::set(&obj, &"signature".into(), &js_sys::Uint8Array::from(sig.as_slice()))?,
+        None => js_sys::Reflect::set(&obj, &"state".into(), &js_sys::Uint8Array::from(state.to_bytes().as_slice()))?,
+    };
+    Ok(obj.into())
+}
+
+// Signs in bounded steps so JS can yield to the event loop in between. Each call makes at most
+// `attempts_per_call` attempts and returns { done: true, signature } or { done: false, state };
+// pass `state` back (undefined on the first call) with the same key and message to continue.
+// The state holds only the salt and an attempt counter. Throws once the attempt budget is spent.
+#[wasm_bindgen]
+pub fn sign_with_mayo_chunked(param_set_name: &str, secret_key: Vec<u8>, message: &[u8], state: Option<Vec<u8>>, attempts_per_call: u32) -> Result<JsValue, JsValue> {
+    let secret_key = Zeroizing::new(secret_key);
+    match param_set_name {
+        #[cfg(feature = "mayo1")]
+        "MAYO1" => sign_chunk_js::<Mayo1>(&secret_key, message, state, attempts_per_call),
+        #[cfg(feature = "mayo2")]
+        "MAYO2" => sign_chunk_js::<Mayo2>(&secret_key, message, state, attempts_per_call),
+        #[cfg(feature = "mayo3")]
+        "MAYO3" => sign_chunk_js::<Mayo3>(&secret_key, message, state, attempts_per_call),
+        #[cfg(feature = "mayo5")]
+        "MAYO5" => sign_chunk_js::<Mayo5>(&secret_key, message, state, attempts_per_call),
         _ => Err(JsValue::from_str("Invalid MAYO parameter set name"))
     }
 }
@@ -66,6 +140,7 @@ pub fn verify_with_mayo(param_set_name: &str, public_key: &[u8], message: &[u8],
     web_sys::console::log_1(&format!("[WASM] verify_with_mayo called: param={}, pk_len={}, msg_len={}, sig_len={}", param_set_name, public_key.len(), message.len(), signature.len()).into());
     
     match param_set_name {
+        #[cfg(feature = "mayo1")]
         "MAYO1" => {
             web_sys::console::log_1(&"[WASM] Calling verify_generic for MAYO1".into());
             match verify_generic::<Mayo1>(public_key, message, signature) {
@@ -79,9 +154,83 @@ pub fn verify_with_mayo(param_set_name: &str, public_key: &[u8], message: &[u8],
                 }
             }
         }
+        #[cfg(feature = "mayo2")]
         "MAYO2" => verify_generic::<Mayo2>(public_key, message, signature).map_err(to_js_error),
+        #[cfg(feature = "mayo3")]
         "MAYO3" => verify_generic::<Mayo3>(public_key, message, signature).map_err(to_js_error),
+        #[cfg(feature = "mayo5")]
         "MAYO5" => verify_generic::<Mayo5>(public_key, message, signature).map_err(to_js_error),
         _ => Err(JsValue::from_str("Invalid MAYO parameter set name"))
     }
 }
+
+// Copies each element of a JS array of Uint8Arrays into an owned byte vector
+fn byte_arrays_from_js(array: &js_sys::Array, what: &str) -> Result<Vec<Vec<u8>>, JsValue> {
+    array
+        .iter()
+        .enumerate()
+        .map(|(i, value)| {
+            value
+                .dyn_into::<js_sys::Uint8Array>()
+                .map(|bytes| bytes.to_vec())
+                .map_err(|_| JsValue::from_str(&format!("{} {} is not a Uint8Array", what, i)))
+        })
+        .collect()
+}
+
+fn sign_batch_js<P: MayoParams>(secret_key: &[u8], messages: &[Vec<u8>]) -> Result<js_sys::Array, JsValue> {
+    let messages: Vec<&[u8]> = messages.iter().map(|m| m.as_slice()).collect();
+    let signatures = sign_batch::<P>(secret_key, &messages).map_err(to_js_error)?;
+    Ok(signatures.iter().map(|sig| JsValue::from(js_sys::Uint8Array::from(sig.as_slice()))).collect())
+}
+
+fn verify_batch_js<P: MayoParams>(public_key: &[u8], messages: &[Vec<u8>], signatures: &[Vec<u8>]) -> Result<js_sys::Array, JsValue> {
+    let items: Vec<(&[u8], &[u8])> = messages.iter().zip(signatures).map(|(m, s)| (m.as_slice(), s.as_slice())).collect();
+    let results = verify_batch::<P>(public_key, &items).map_err(to_js_error)?;
+    Ok(results.into_iter().map(JsValue::from_bool).collect())
+}
+
+// Signs an array of Uint8Array messages, expanding the secret key once for the whole batch.
+// Returns an array of Uint8Array signatures in the same order.
+#[wasm_bindgen]
+pub fn sign_batch_wasm(param_set_name: &str, secret_key: Vec<u8>, messages: js_sys::Array) -> Result<js_sys::Array, JsValue> {
+    let secret_key = Zeroizing::new(secret_key);
+    web_sys::console::log_1(&format!("[WASM] sign_batch_wasm called: param={}, messages={}", param_set_name, messages.length()).into());
+    
+    let messages = byte_arrays_from_js(&messages, "message")?;
+    match param_set_name {
+        #[cfg(feature = "mayo1")]
+        "MAYO1" => sign_batch_js::<Mayo1>(&secret_key, &messages),
+        #[cfg(feature = "mayo2")]
+        "MAYO2" => sign_batch_js::<Mayo2>(&secret_key, &messages),
+        #[cfg(feature = "mayo3")]
+        "MAYO3" => sign_batch_js::<Mayo3>(&secret_key, &messages),
+        #[cfg(feature = "mayo5")]
+        "MAYO5" => sign_batch_js::<Mayo5>(&secret_key, &messages),
+        _ => Err(JsValue::from_str("Invalid MAYO parameter set name"))
+    }
+}
+
+// Verifies messages[i] against signatures[i] under one public key, expanded once.
+// Returns an array of booleans in input order.
+#[wasm_bindgen]
+pub fn verify_batch_wasm(param_set_name: &str, public_key: &[u8], messages: js_sys::Array, signatures: js_sys::Array) -> Result<js_sys::Array, JsValue> {
+    web_sys::console::log_1(&format!("[WASM] verify_batch_wasm called: param={}, messages={}", param_set_name, messages.length()).into());
+    
+    if messages.length() != signatures.length() {
+        return Err(JsValue::from_str("messages and signatures must have the same length"));
+    }
+    let messages = byte_arrays_from_js(&messages, "message")?;
+    let signatures = byte_arrays_from_js(&signatures, "signature")?;
+    match param_set_name {
+        #[cfg(feature = "mayo1")]
+        "MAYO1" => verify_batch_js::<Mayo1>(public_key, &messages, &signatures),
+        #[cfg(feature = "mayo2")]
+        "MAYO2" => verify_batch_js::<Mayo2>(public_key, &messages, &signatures),
+        #[cfg(feature = "mayo3")]
+        "MAYO3" => verify_batch_js::<Mayo3>(public_key, &messages, &signatures),
+        #[cfg(feature = "mayo5")]
+        "MAYO5" => verify_batch_js::<Mayo5>(public_key, &messages, &signatures),
+        _ => Err(JsValue::from_str("Invalid MAYO parameter set name"))
+    }
+}
@@ -0,0 +1,70 @@
+// rust-mayo/tests/common/mod.rs
+//
+// Shared helpers for the NIST KAT response files in ../KAT. Each integration test
+// binary that needs them declares `mod common;`.
+
+use rust_mayo::crypto::generate_keypair_from_seed;
+use rust_mayo::params::MayoParams;
+use std::fs;
+use std::path::PathBuf;
+
+// One "count = ..." record of a PQCsignKAT_*.rsp file
+#[derive(Debug, Default)]
+pub struct KatEntry {
+    pub count: usize,
+    pub seed: Vec<u8>,
+    pub msg: Vec<u8>,
+    pub pk: Vec<u8>,
+    pub sk: Vec<u8>,
+    pub sm: Vec<u8>,
+}
+
+// Path of a file in the repository's KAT directory
+pub fn kat_path(file_name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("..").join("KAT").join(file_name)
+}
+
+// Parses every record of a .rsp file. Unknown keys (mlen, smlen) are skipped.
+pub fn parse_kat_file(path: &PathBuf) -> Vec<KatEntry> {
+    let content = fs::read_to_string(path).unwrap_or_else(|e| panic!("reading {}: {}", path.display(), e));
+    let mut entries: Vec<KatEntry> = Vec::new();
+    for line in content.lines() {
+        let Some((key, value)) = line.split_once(" = ") else { continue };
+        let value = value.trim();
+        if key == "count" {
+            entries.push(KatEntry { count: value.parse().expect("count is a number"), ..Default::default() });
+            continue;
+        }
+        let Some(entry) = entries.last_mut() else { continue };
+        let field = match key {
+            "seed" => &mut entry.seed,
+            "msg" => &mut entry.msg,
+            "pk" => &mut entry.pk,
+            "sk" => &mut entry.sk,
+            "sm" => &mut entry.sm,
+            _ => continue,
+        };
+        *field = hex::decode(value).unwrap_or_else(|e| panic!("{} in record {}: {}", key, entry.count, e));
+    }
+    entries
+}
+
+// Regenerates each record's key pair from its secret key seed and checks it against the file
+pub fn run_keygen_kat<P: MayoParams>(file_name: &str) {
+    let entries = parse_kat_file(&kat_path(file_name));
+    assert!(!entries.is_empty(), "no KAT records in {}", file_name);
+    for entry in &entries {
+        assert_eq!(entry.sk.len(), P::CSK_BYTES, "{} record {}: sk length", P::name(), entry.count);
+        let (sk, pk) = generate_keypair_from_seed::<P>(&entry.sk)
+            .unwrap_or_else(|e| panic!("{} record {}: keygen failed: {}", P::name(), entry.count, e));
+        assert_eq!(sk, entry.sk, "{} record {}: secret key", P::name(), entry.count);
+        assert_eq!(
+            hex::encode(&pk[..P::PK_SEED_BYTES]),
+            hex::encode(&entry.pk[..P::PK_SEED_BYTES]),
+            "{} record {}: pk_seed",
+            P::name(),
+            entry.count
+        );
+        assert_eq!(hex::encode(&pk), hex::encode(&entry.pk), "{} record {}: public key", P::name(), entry.count);
+    }
+}
@@ -1,4 +1,4 @@
-use rust_mayo::crypto::{generate_keypair_generic, sign_generic, verify_generic, expand_matrices};
+use rust_mayo::crypto::{generate_keypair_generic, generate_keypair_from_seed, sign_generic, verify_generic, expand_matrices};
 use rust_mayo::params::{Mayo1, MayoParams};
 
 #[test]
@@ -28,7 +28,7 @@ fn test_final_mayo_demonstration() {
     println!("\n🧮 Matrix System Setup:");
     let pk_seed = &public_key[..Mayo1::PK_SEED_BYTES];
     let (p1, p2, p3) = expand_matrices::<Mayo1>(pk_seed)
-        .expect("Matrix expansion should work");
+        .expect("Matrix expansion should work").into_parts();
     
     let v = Mayo1::N_PARAM - Mayo1::O_PARAM;
     let expected_p1_size = Mayo1::M_PARAM * (v * (v + 1)) / 2;
@@ -97,7 +97,7 @@ fn test_final_mayo_demonstration() {
     
     // Test deterministic expansion
     let (p1_dup, p2_dup, p3_dup) = expand_matrices::<Mayo1>(pk_seed)
-        .expect("Duplicate matrix expansion should work");
+        .expect("Duplicate matrix expansion should work").into_parts();
     
     assert_eq!(p1, p1_dup, "P1 should be deterministic");
     assert_eq!(p2, p2_dup, "P2 should be deterministic"); 
@@ -133,11 +133,16 @@ fn test_mayo_security_properties() {
     
     // Deterministic key generation from same seed
     let test_seed = vec![0x42u8; Mayo1::SK_SEED_BYTES];
+    assert_eq!(
+        generate_keypair_from_seed::<Mayo1>(&test_seed).unwrap(),
+        generate_keypair_from_seed::<Mayo1>(&test_seed).unwrap(),
+        "Keypairs from the same seed should be equal"
+    );
     
     // Generate matrices from same seed multiple times
     let pk_seed = &pk1[..Mayo1::PK_SEED_BYTES];
-    let (p1_a, p2_a, p3_a) = expand_matrices::<Mayo1>(pk_seed).unwrap();
-    let (p1_b, p2_b, p3_b) = expand_matrices::<Mayo1>(pk_seed).unwrap();
+    let (p1_a, p2_a, p3_a) = expand_matrices::<Mayo1>(pk_seed).unwrap().into_parts();
+    let (p1_b, p2_b, p3_b) = expand_matrices::<Mayo1>(pk_seed).unwrap().into_parts();
     
     assert_eq!(p1_a, p1_b, "P1 matrix should be deterministic");
     assert_eq!(p2_a, p2_b, "P2 matrix should be deterministic");
@@ -146,7 +151,7 @@ fn test_mayo_security_properties() {
     
     // Different seeds should produce different matrices
     let different_seed = vec![0x43u8; Mayo1::PK_SEED_BYTES];
-    let (p1_diff, p2_diff, p3_diff) = expand_matrices::<Mayo1>(&different_seed).unwrap();
+    let (p1_diff, p2_diff, p3_diff) = expand_matrices::<Mayo1>(&different_seed).unwrap().into_parts();
     
     assert_ne!(p1_a, p1_diff, "Different seeds should produce different P1");
     assert_ne!(p2_a, p2_diff, "Different seeds should produce different P2");
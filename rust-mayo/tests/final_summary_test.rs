@@ -37,7 +37,7 @@ fn test_mayo_implementation_summary() {
     println!("\n🧮 Matrix System Test:");
     let pk_seed = &public_key[..Mayo1::PK_SEED_BYTES];
     let (p1, p2, p3) = expand_matrices::<Mayo1>(pk_seed)
-        .expect("Matrix expansion should work");
+        .expect("Matrix expansion should work").into_parts();
     
     let v = Mayo1::N_PARAM - Mayo1::O_PARAM;
     let expected_p1_size = Mayo1::M_PARAM * (v * (v + 1)) / 2;
@@ -125,19 +125,21 @@ fn test_wasm_compatibility() {
     // Test matrix expansion (used internally)
     let pk_seed = &public_key[..Mayo1::PK_SEED_BYTES];
     let (_p1, _p2, _p3) = expand_matrices::<Mayo1>(pk_seed)
-        .expect("Matrix expansion should work for WASM");
+        .expect("Matrix expansion should work for WASM").into_parts();
     
     println!("   ✅ Matrix operations work in WASM context");
     
-    // Test that verification works (even if signing times out)
+    // Test that signing and verification work
     let test_message = b"WASM test";
+    let signature = sign_generic::<Mayo1>(&secret_key, test_message)
+        .expect("Signing should work for WASM");
+    assert!(verify_generic::<Mayo1>(&public_key, test_message, &signature).unwrap());
     
-    // Create a dummy signature to test verification structure
+    // A dummy signature is rejected rather than an error
     let mut dummy_signature = vec![0u8; Mayo1::SIG_BYTES];
     dummy_signature[0] = 42; // Make it non-zero
-    
-    let _result = verify_generic::<Mayo1>(&public_key, test_message, &dummy_signature);
-    println!("   ✅ Verification works in WASM context");
+    assert!(!verify_generic::<Mayo1>(&public_key, test_message, &dummy_signature).unwrap());
+    println!("   ✅ Signing and verification work in WASM context");
     
     println!("   ✅ All WASM-exposed functions are compatible");
 } 
\ No newline at end of file
@@ -1,4 +1,4 @@
-use rust_mayo::crypto::{generate_keypair_generic, sign_generic, verify_generic, expand_matrices, compute_sps, shake256_digest};
+use rust_mayo::crypto::{generate_keypair_generic, expand_matrices, compute_sps, shake256_digest};
 use rust_mayo::params::{Mayo1, MayoParams};
 
 #[test]
@@ -9,7 +9,7 @@ fn test_mayo_polynomial_structure() {
     let seed = vec![1u8; 24];
     let expanded = shake256_digest(&seed, 16 + 8);
     let pk_seed = &expanded[..16];
-    let (p1, p2, p3) = expand_matrices::<Mayo1>(pk_seed).unwrap();
+    let (p1, p2, p3) = expand_matrices::<Mayo1>(pk_seed).unwrap().into_parts();
     
     // Verify matrix sizes match MAYO-1 specification
     let v = Mayo1::N_PARAM - Mayo1::O_PARAM; // 78
@@ -35,7 +35,7 @@ fn test_sps_computation_correctness() {
     let seed = vec![42u8; 24];
     let expanded = shake256_digest(&seed, 16 + 8);
     let pk_seed = &expanded[..16];
-    let (p1, p2, p3) = expand_matrices::<Mayo1>(pk_seed).unwrap();
+    let (p1, p2, p3) = expand_matrices::<Mayo1>(pk_seed).unwrap().into_parts();
     
     // Create deterministic S matrix for testing
     let mut s_matrix = Vec::new();
@@ -104,7 +104,7 @@ fn test_mayo_implementation_readiness() {
     // Test matrix expansion
     let expanded = shake256_digest(&secret_key, Mayo1::PK_SEED_BYTES + Mayo1::O_BYTES);
     let pk_seed = &expanded[..Mayo1::PK_SEED_BYTES];
-    let (p1, p2, p3) = expand_matrices::<Mayo1>(pk_seed).unwrap();
+    let (p1, p2, p3) = expand_matrices::<Mayo1>(pk_seed).unwrap().into_parts();
     
     // Verify matrix structure
     let v = Mayo1::N_PARAM - Mayo1::O_PARAM;
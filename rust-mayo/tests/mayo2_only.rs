@@ -0,0 +1,42 @@
+// rust-mayo/tests/mayo2_only.rs
+//
+// Checks the per-set features. Meaningful when built with MAYO-2 alone:
+//   cargo test --no-default-features --features std,mayo2 --test mayo2_only
+// With the default features only the MAYO-2 round trips run.
+
+use rust_mayo::crypto::{generate_keypair_generic, sign_generic, verify_generic};
+use rust_mayo::params::{Mayo2, MayoParams};
+
+#[test]
+fn test_mayo2_keygen_and_verify_rejects_garbage() {
+    let (sk, pk) = generate_keypair_generic::<Mayo2>().unwrap();
+    assert_eq!(sk.len(), Mayo2::CSK_BYTES);
+    assert_eq!(pk.len(), Mayo2::CPK_BYTES);
+    let signature = vec![0x11u8; Mayo2::SIG_BYTES];
+    assert!(!verify_generic::<Mayo2>(&pk, b"mayo2 only", &signature).unwrap_or(false));
+}
+
+#[test]
+fn test_mayo2_sign_verify() {
+    let (sk, pk) = generate_keypair_generic::<Mayo2>().unwrap();
+    let signature = sign_generic::<Mayo2>(&sk, b"mayo2 only").unwrap();
+    assert!(verify_generic::<Mayo2>(&pk, b"mayo2 only", &signature).unwrap());
+}
+
+// An explicit item would shadow a glob import, so the probe goes through two globs instead:
+// if params still exported Mayo1 the name would be ambiguous and this file would not
+// compile. Without it, Mayo1 resolves to the fallback below.
+#[cfg(not(feature = "mayo1"))]
+mod mayo1_compiled_out {
+    mod fallback {
+        pub struct Mayo1;
+    }
+    use fallback::*;
+    #[allow(unused_imports)]
+    use rust_mayo::params::*;
+
+    #[test]
+    fn test_mayo1_is_not_exported() {
+        assert!(std::any::type_name::<Mayo1>().contains("fallback"));
+    }
+}
@@ -81,7 +81,7 @@ fn test_matrix_expansion() {
     let test_seed = vec![0x42u8; Mayo1::PK_SEED_BYTES];
     
     let (p1, p2, p3) = expand_matrices::<Mayo1>(&test_seed)
-        .expect("Matrix expansion should succeed");
+        .expect("Matrix expansion should succeed").into_parts();
     
     let v = Mayo1::N_PARAM - Mayo1::O_PARAM;
     let expected_p1_size = Mayo1::M_PARAM * (v * (v + 1)) / 2;
@@ -97,7 +97,7 @@ fn test_matrix_expansion() {
     
     // Test determinism - same seed should produce same matrices
     let (p1_2, p2_2, p3_2) = expand_matrices::<Mayo1>(&test_seed)
-        .expect("Second matrix expansion should succeed");
+        .expect("Second matrix expansion should succeed").into_parts();
     
     assert_eq!(p1, p1_2, "P1 should be deterministic");
     assert_eq!(p2, p2_2, "P2 should be deterministic");
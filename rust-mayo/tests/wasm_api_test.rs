@@ -0,0 +1,71 @@
+// Browser/Node tests for the WASM API; run with `wasm-pack test --node`
+#![cfg(target_arch = "wasm32")]
+
+use rust_mayo::wasm_api::{generate_keypair_wasm, sign_batch_wasm, sign_with_mayo_chunked, verify_batch_wasm, verify_with_mayo};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+fn key(keys: &wasm_bindgen::JsValue, name: &str) -> Vec<u8> {
+    js_sys::Reflect::get(keys, &name.into())
+        .unwrap()
+        .dyn_into::<js_sys::Uint8Array>()
+        .unwrap()
+        .to_vec()
+}
+
+fn messages() -> js_sys::Array {
+    [b"first".as_slice(), b"second", b"third"]
+        .iter()
+        .map(|m| wasm_bindgen::JsValue::from(js_sys::Uint8Array::from(*m)))
+        .collect()
+}
+
+#[wasm_bindgen_test]
+fn sign_and_verify_three_messages() {
+    let keys = generate_keypair_wasm("MAYO1").unwrap();
+    let (sk, pk) = (key(&keys, "secret_key"), key(&keys, "public_key"));
+
+    let signatures = sign_batch_wasm("MAYO1", sk, messages()).unwrap();
+    assert_eq!(signatures.length(), 3);
+
+    let results = verify_batch_wasm("MAYO1", &pk, messages(), signatures).unwrap();
+    assert!(results.iter().all(|r| r.as_bool() == Some(true)));
+}
+
+#[wasm_bindgen_test]
+fn verify_batch_rejects_mismatched_lengths() {
+    let keys = generate_keypair_wasm("MAYO1").unwrap();
+    let pk = key(&keys, "public_key");
+    assert!(verify_batch_wasm("MAYO1", &pk, messages(), js_sys::Array::new()).is_err());
+}
+
+#[wasm_bindgen_test]
+fn sign_progress_callback_fires() {
+    let keys = generate_keypair_wasm("MAYO1").unwrap();
+    let sk = key(&keys, "secret_key");
+
+    let global = js_sys::global();
+    js_sys::Reflect::set(&global, &"__mayoProgress".into(), &0.into()).unwrap();
+    let callback = js_sys::Function::new_with_args("attempt", "globalThis.__mayoProgress += 1;");
+
+    // The outcome does not matter here, only that progress was reported along the way
+    let _ = rust_mayo::wasm_api::sign_with_mayo("MAYO1", sk, b"progress", Some(callback));
+    let calls = js_sys::Reflect::get(&global, &"__mayoProgress".into()).unwrap();
+    assert!(calls.as_f64().unwrap() >= 1.0);
+}
+
+#[wasm_bindgen_test]
+fn chunked_signing_produces_verifying_signature() {
+    let keys = generate_keypair_wasm("MAYO1").unwrap();
+    let (sk, pk) = (key(&keys, "secret_key"), key(&keys, "public_key"));
+
+    let mut state: Option<Vec<u8>> = None;
+    let signature = loop {
+        let step = sign_with_mayo_chunked("MAYO1", sk.clone(), b"chunked", state.take(), 8).unwrap();
+        if js_sys::Reflect::get(&step, &"done".into()).unwrap().as_bool() == Some(true) {
+            break key(&step, "signature");
+        }
+        state = Some(key(&step, "state"));
+    };
+    assert!(verify_with_mayo("MAYO1", &pk, b"chunked", &signature).unwrap());
+}